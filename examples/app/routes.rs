@@ -1,8 +1,7 @@
-use std::collections::HashMap;
 use warp::Rejection;
 use warp::Reply;
 use warp::Filter;
-use rustract::types::DataTypeValue;
+use rustract::row::Row;
 
 use crate::ErrorType;
 use crate::DB_DESIGN;
@@ -27,65 +26,22 @@ fn register() -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone
 }
 
 /// Extracts the data from the request body and verifies it in the process.
-/// 
-/// TODO: This method's error handling could probably be cleaned up.
-async fn extract(body: serde_json::Value) -> Result<HashMap<String, DataTypeValue>, warp::reject::Rejection> {
-    // The map this function will extract from the JSON body
-    let mut map: HashMap<String, DataTypeValue> = HashMap::new();
-
+async fn extract(body: serde_json::Value) -> Result<Row, warp::reject::Rejection> {
     // Checks to make sure the data exists/is structured properly
-    if let Some(data_map) = body.as_object() {
-        for key in DB_DESIGN.table("user").unwrap().fields.keys() {
-            let field = DB_DESIGN.table("user")
-                .unwrap()
-                .field(key)
-                .unwrap();
-            if let Some(data) = data_map.get(&field.field_design_title) {
-                match field.extract(data) {
-                    Ok(data_value) => {
-                        map.insert(
-                            field.field_design_title.to_string(),
-                            data_value
-                        );
-                    },
-                    Err(error) => {
-                        return Err(warp::reject::custom(CustomError {
-                            err_type: ErrorType::BadRequest,
-                            message: format!("field {} is not formatted properly: {}", &field.field_design_title, error.to_string())
-                        }));
-                    }
-                }
-            } else if field.required && !field.generated {
-                return Err(warp::reject::custom(CustomError {
-                    err_type: ErrorType::BadRequest,
-                    message: format!("field {} is listed as required, but was not included in the request body", &field.field_design_title),
-                }));
-            }
-        }
-        Ok(map)
-    } else {
-        Err(warp::reject::custom(CustomError {
+    DB_DESIGN.table("user").unwrap().extract(&body).map_err(|error| {
+        warp::reject::custom(CustomError {
             err_type: ErrorType::BadRequest,
-            message: format!("failed to parse JSON as object, JSON: \"{}\" (Err: Body should be a map)", body.to_string()),
-        }))
-    }
+            message: format!("request body is not formatted properly: {}", error),
+        })
+    })
 }
 
 /// Uses the fields to create some query or handle some type of custom logic.
-async fn insert(req: HashMap<String, DataTypeValue>) -> Result<String, warp::reject::Rejection> {
+async fn insert(req: Row) -> Result<String, warp::reject::Rejection> {
     // The req variable now has all the User fields as specified in the field design
-    let name = match req.get("name").unwrap() {
-        DataTypeValue::String(data) => data,
-        _ => panic!("invalid data type retrieved")
-    };
-    let email = match req.get("email").unwrap() {
-        DataTypeValue::String(data) => data,
-        _ => panic!("invalid data type retrieved")
-    };
-    let date = match req.get("date").unwrap() {
-        DataTypeValue::String(data) => data,
-        _ => panic!("invalid data type retrieved")
-    };
+    let name = req.string("name").unwrap_or_default();
+    let email = req.string("email").unwrap_or_default();
+    let date = req.string("date").unwrap_or_default();
 
     // An SQL query can be made here that safely inserts the verified data
     print!("Found User: {{ name: {}, email: {}, date: {} }}", name, email, date);
@@ -0,0 +1,125 @@
+use std::fmt::{Display, Formatter};
+use std::convert::TryInto;
+use std::str::FromStr;
+use serde::{Serialize,Deserialize};
+use crate::error::{RustractError, GenericError};
+
+/// The character set a string column stores its text in, which decides how many bytes each character takes.
+///
+/// Text is measured as `utf8mb4` (UTF-8) unless another character set is declared.
+#[derive(Deserialize, Serialize, Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Charset {
+    /// UTF-8, with up to 4 bytes per character.
+    #[default]
+    Utf8mb4,
+    /// MySQL's older UTF-8 (also named `utf8`), which cannot store characters over 3 bytes such as emoji.
+    Utf8mb3,
+    /// MySQL's `latin1`, which is Windows-1252 with a single byte per character.
+    Latin1,
+    Ascii,
+    /// UCS-2, with 2 bytes per character and no characters outside of the Basic Multilingual Plane.
+    Ucs2,
+    Utf16,
+    Utf32
+}
+
+/// The characters Windows-1252 adds to Latin-1, in the bytes 0x80 to 0x9F.
+const WINDOWS_1252: &str = "€‚ƒ„…†‡ˆ‰Š‹ŒŽ‘’“”•–—˜™š›œžŸ";
+
+impl Charset {
+    /// Gets the most bytes a single character takes.
+    pub fn max_bytes(&self) -> isize {
+        match self {
+            Charset::Utf8mb4 | Charset::Utf16 | Charset::Utf32 => 4,
+            Charset::Utf8mb3 => 3,
+            Charset::Ucs2 => 2,
+            Charset::Latin1 | Charset::Ascii => 1,
+        }
+    }
+
+    /// Returns true if the character can be stored in this character set.
+    pub fn stores(&self, character: char) -> bool {
+        match self {
+            Charset::Utf8mb4 | Charset::Utf16 | Charset::Utf32 => true,
+            Charset::Utf8mb3 => character.len_utf8() < 4,
+            Charset::Latin1 => character <= '\u{FF}' || WINDOWS_1252.contains(character),
+            Charset::Ascii => character.is_ascii(),
+            Charset::Ucs2 => character <= '\u{FFFF}',
+        }
+    }
+
+    /// Gets the number of bytes the text takes in this character set,
+    /// or the first character that cannot be stored in it.
+    pub fn byte_length(&self, text: &str) -> Result<isize, char> {
+        if let Some(character) = text.chars().find(|character| !self.stores(*character)) {
+            return Err(character);
+        }
+        let bytes = match self {
+            Charset::Utf8mb4 | Charset::Utf8mb3 => text.len(),
+            Charset::Latin1 | Charset::Ascii => text.chars().count(),
+            Charset::Ucs2 | Charset::Utf16 => text.encode_utf16().count() * 2,
+            Charset::Utf32 => text.chars().count() * 4,
+        };
+        Ok(bytes.try_into().unwrap_or(isize::MAX))
+    }
+
+    /// Gets the character set of a MySQL collation, such as `utf8mb4` for `utf8mb4_0900_ai_ci`.
+    pub fn from_collation(collation: &str) -> Result<Self, RustractError> {
+        collation.split('_').next().unwrap_or_default().parse()
+    }
+}
+
+impl Display for Charset {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", match self {
+            Charset::Utf8mb4 => "utf8mb4",
+            Charset::Utf8mb3 => "utf8mb3",
+            Charset::Latin1 => "latin1",
+            Charset::Ascii => "ascii",
+            Charset::Ucs2 => "ucs2",
+            Charset::Utf16 => "utf16",
+            Charset::Utf32 => "utf32"
+        })
+    }
+}
+
+impl FromStr for Charset {
+    type Err = RustractError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text.to_ascii_lowercase().as_str() {
+            "utf8mb4" | "utf-8" => Ok(Charset::Utf8mb4),
+            "utf8mb3" | "utf8" => Ok(Charset::Utf8mb3),
+            "latin1" => Ok(Charset::Latin1),
+            "ascii" => Ok(Charset::Ascii),
+            "ucs2" => Ok(Charset::Ucs2),
+            "utf16" | "utf16le" => Ok(Charset::Utf16),
+            "utf32" => Ok(Charset::Utf32),
+            _ => Err(RustractError::Generic(GenericError {
+                message: format!("{} is not a supported character set", text),
+            })),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn charset_test() {
+        let text = "Grüße 👋";
+        assert_eq!(Charset::Utf8mb4.byte_length(text), Ok(12));
+        assert_eq!(Charset::Utf8mb3.byte_length(text), Err('👋'));
+        assert_eq!(Charset::Latin1.byte_length("Grüße €"), Ok(7));
+        assert_eq!(Charset::Latin1.byte_length(text), Err('👋'));
+        assert_eq!(Charset::Ascii.byte_length("Grüße"), Err('ü'));
+        assert_eq!(Charset::Utf16.byte_length(text), Ok(16));
+        assert_eq!(Charset::Utf32.byte_length(text), Ok(28));
+
+        assert_eq!("utf8".parse::<Charset>().unwrap(), Charset::Utf8mb3);
+        assert_eq!(Charset::from_collation("utf8mb4_0900_ai_ci").unwrap(), Charset::Utf8mb4);
+        assert!("gbk".parse::<Charset>().is_err());
+    }
+}
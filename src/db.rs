@@ -1,6 +1,8 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
+use serde_json::Value;
 
-use crate::{error::{RustractError, GenericError}, field::FieldDesign, filesystem::read_file, table::TableDesign, types::{DataType, IndexOf, IntoHashSet}};
+use crate::{charset::Charset, diff::SchemaDiff, error::{RustractError, GenericError}, field::{DefaultValue, FieldDesign, ForeignKey}, format::Format, filesystem::read_file, migration::Migration, table::{Index, TableDesign}, types::{DataType, Dialect}};
+use crate::sql::{AlterAction, AlterTable, Check, ColumnDef, CreateTable, CreateView, DefaultExpr, Reference, Statement, TableConstraint, ViewColumn, parse};
 
 /// A database schema struct that can be used for testing JSON.
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
@@ -15,6 +17,12 @@ impl std::fmt::Display for Database {
     }
 }
 
+impl Default for Database {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Database {
     /// Constructs a new instance of the Database struct.
     pub fn new() -> Self {
@@ -56,37 +64,159 @@ impl Database {
         self.tables.get_mut(title)
     }
 
-    /// Reads a Database schema from the specified filepath.
+    /// Finds the changes made from this database design to the other, such as those in a newer schema.
+    pub fn diff(&self, other: &Database) -> SchemaDiff {
+        SchemaDiff::between(self, other)
+    }
+
+    /// Reads a MySQL Database schema from the specified filepath.
     pub fn from_schema(schema_path: &str) -> Result<Self, RustractError> {
-        let schema = read_file(schema_path)?;
-        let mut reading = false;
-        let mut db = Database::new();
-        let mut table_title = String::new();
-
-        // Loop until all tables are found
-        for line_src in schema.lines() {
-            let line = line_src.trim();
-            // Only read sections that declare new tables
-            if line.contains("CREATE TABLE") {
-                reading = true;
-                table_title = read_name(line)?;
-                db.add(TableDesign::new(&table_title));
-                continue;
+        Database::from_schema_dialect(schema_path, Dialect::MySql)
+    }
+
+    /// Reads a Database schema written in the specified dialect from the filepath.
+    ///
+    /// For the SQLite dialect, the path may also lead to a database file.
+    pub fn from_schema_dialect(schema_path: &str, dialect: Dialect) -> Result<Self, RustractError> {
+        #[cfg(feature = "sqlite")]
+        if dialect == Dialect::Sqlite && is_sqlite_file(schema_path) {
+            return Database::from_sqlite_file(schema_path);
+        }
+        Database::from_sql_dialect(&read_file(schema_path)?, dialect)
+    }
+
+    /// Reads a Database schema from the tables of the SQLite database file at the specified path.
+    ///
+    /// The file is opened read only. Each table's creation SQL is read from `sqlite_master`,
+    /// since unlike `pragma table_info` it also keeps the table's `CHECK` constraints.
+    #[cfg(feature = "sqlite")]
+    pub fn from_sqlite_file(path: &str) -> Result<Self, RustractError> {
+        let connection = rusqlite::Connection::open_with_flags(path, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        let mut query = connection.prepare(
+            "SELECT sql FROM sqlite_master WHERE type = 'table' AND sql IS NOT NULL AND name NOT LIKE 'sqlite\\_%' ESCAPE '\\' ORDER BY rowid"
+        )?;
+        let tables = query
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<String>, rusqlite::Error>>()?;
+        Database::from_sql_dialect(&tables.join(";\n"), Dialect::Sqlite)
+    }
+
+    /// Reads a Database schema from the provided MySQL (such as the contents of a dump).
+    ///
+    /// Statements that do not affect the design (such as `INSERT`) are ignored.
+    pub fn from_sql(schema: &str) -> Result<Self, RustractError> {
+        Database::from_sql_dialect(schema, Dialect::MySql)
+    }
+
+    /// Reads a Database schema from the provided SQL written in the specified dialect.
+    ///
+    /// Statements that do not affect the design (such as `SET` or `CREATE INDEX`) are ignored.
+    pub fn from_sql_dialect(schema: &str, dialect: Dialect) -> Result<Self, RustractError> {
+        let mut reader = SchemaReader::new(dialect);
+        for statement in parse(schema, dialect)? {
+            reader.statement(statement)?;
+        }
+        reader.db.check_foreign_keys()?;
+        Ok(reader.db)
+    }
+
+    /// Reads a Database schema by applying each migration in the folder in order, in the specified dialect.
+    ///
+    /// Migrations are the `.sql` files of the folder (other than `.down.sql` files), ordered by their numeric version prefix,
+    /// such as `20240101120000_create_users.up.sql` or `V2__add_posts.sql`.
+    /// A migration without a version fails, since its place in the order is not known.
+    pub fn from_migrations(folder: &str, dialect: Dialect) -> Result<Self, RustractError> {
+        let mut migrations = Vec::new();
+        for entry in std::fs::read_dir(folder)? {
+            let path = entry?.path();
+            let name = path.file_name().and_then(|name| name.to_str()).unwrap_or_default().to_string();
+            if path.is_file() && name.ends_with(".sql") && !name.ends_with(".down.sql") {
+                let version: String = name.chars()
+                    .skip_while(|c| !c.is_ascii_digit())
+                    .take_while(|c| c.is_ascii_digit())
+                    .collect();
+                let version = match version.parse::<u128>() {
+                    Ok(version) => version,
+                    Err(_) => return Err(RustractError::DB(GenericError {
+                        message: format!("failed to read migration {}, its name does not start with a version number", name)
+                    })),
+                };
+                migrations.push((version, name, path));
             }
+        }
+        migrations.sort();
+
+        let mut reader = SchemaReader::new(dialect);
+        for (_, name, path) in migrations {
+            let apply = |reader: &mut SchemaReader| -> Result<(), RustractError> {
+                for statement in parse(&std::fs::read_to_string(&path)?, dialect)? {
+                    reader.statement(statement)?;
+                }
+                Ok(())
+            };
+            if let Err(error) = apply(&mut reader) {
+                return Err(RustractError::DB(GenericError {
+                    message: format!("failed to apply migration {}, {}", name, error.message())
+                }));
+            }
+        }
+        reader.db.check_foreign_keys()?;
+        Ok(reader.db)
+    }
+
+    /// Checks that each foreign key refers to an existing field of the same type.
+    ///
+    /// Foreign keys that refer to a table's primary key without naming it are updated to name it.
+    fn check_foreign_keys(&mut self) -> Result<(), RustractError> {
+        let mut resolved = Vec::new();
+        for table in self.tables.values() {
+            for field in table.fields.values() {
+                let key = match &field.foreign {
+                    Some(key) => key,
+                    None => continue,
+                };
+                let error = |message: String| RustractError::DB(GenericError {
+                    message: format!(
+                        "foreign key {}.{} {}",
+                        table.table_design_title,
+                        field.field_design_title,
+                        message
+                    )
+                });
+                let referenced_table = self.table(&key.table)
+                    .ok_or_else(|| error(format!("references table {}, which does not exist", key.table)))?;
+
+                // Finds the referenced field, which is the primary key if none was named
+                let title = if key.field.is_empty() {
+                    match referenced_table.primary_key().map(|index| &index.fields[..]) {
+                        Some([title]) => title.clone(),
+                        _ => return Err(error(format!("references table {}, which does not have a single column primary key", key.table))),
+                    }
+                } else {
+                    key.field.clone()
+                };
+                let referenced = referenced_table.field(&title)
+                    .ok_or_else(|| error(format!("references {}.{}, which does not exist", key.table, title)))?;
 
-            // Abort reading if the end of the table is reached
-            if line.starts_with(')') && line.contains(';') {
-                reading = false;
-                continue;
+                if referenced.datatype != field.datatype {
+                    return Err(error(format!(
+                        "is a {}, but references {}.{} which is a {}",
+                        field.datatype,
+                        key.table,
+                        title,
+                        referenced.datatype
+                    )));
+                }
+                resolved.push((table.table_design_title.clone(), field.field_design_title.clone(), title));
             }
+        }
 
-            // Add each line to the database
-            if reading {
-                add_to_db(line, db.table_mut(&table_title).unwrap())?;
+        for (table, field, title) in resolved {
+            if let Some(key) = self.table_mut(&table).and_then(|table| table.field_mut(&field)).and_then(|field| field.foreign.as_mut()) {
+                key.field = title;
             }
         }
-        
-        Ok(db)
+        Ok(())
     }
 
     /// Creates an instance of this struct from the JSON file at the specified path.
@@ -103,6 +233,18 @@ impl Database {
         Ok(())
     }
 
+    /// Writes the SQL statements that create this database design in the specified dialect.
+    ///
+    /// Keys, indexes, enums, defaults and checks are included, but annotations such as a field's regex are not.
+    pub fn to_sql(&self, dialect: Dialect) -> Result<String, RustractError> {
+        crate::ddl::create_database(self, dialect)
+    }
+
+    /// Creates the migration from this database design to the other, in the specified dialect.
+    pub fn migration(&self, other: &Database, dialect: Dialect) -> Result<Migration, RustractError> {
+        Migration::between(self, other, dialect)
+    }
+
     /// Exports this database design to a TypeScript library of types.
     /// 
     /// These types can be used in the front-end to standardize routes.
@@ -124,201 +266,1067 @@ impl Database {
     }
 }
 
-/// Attempts to read the table name from the provided schema line.
-fn read_name(line: &str) -> Result<String, RustractError> {
-    let tokens: Vec<&str> = line.split(' ').collect();
-    for token in tokens {
-        if token.starts_with('`') {
-            return unwrap_str(token);
-        }
-    }
+/// Builds a database design from parsed statements, keeping track of the types they declare.
+struct SchemaReader {
+    db: Database,
+    dialect: Dialect,
+    /// The labels of each enum type created so far, by lower case name.
+    enums: HashMap<String, Vec<String>>,
+    /// The foreign keys and checks that can be dropped by name, by table and lower case name.
+    constraints: HashMap<(String, String), NamedConstraint>,
+    /// The default character set of each table that declares one.
+    charsets: HashMap<String, String>
+}
 
-    Err(RustractError::DB(GenericError {
-        message: format!("no table name found in schema line: {}", line),
-    }))
+/// A foreign key or check, kept by name since the design does not name them.
+enum NamedConstraint {
+    /// The columns of a foreign key.
+    ForeignKey(Vec<String>),
+    /// The expression of a check.
+    Check(String)
 }
 
-/// Attempts to add the schema line's field data to the provided table.
-fn add_to_db(source: &str, table: &mut TableDesign) -> Result<(), RustractError> {
-    // Gathers the tokens in lower case, separated by a single space each
-    let line = source.trim().to_ascii_lowercase();
-    if line.is_empty() {
-        return Ok(())
+impl SchemaReader {
+    fn new(dialect: Dialect) -> Self {
+        Self {
+            db: Database::new(),
+            dialect,
+            enums: HashMap::new(),
+            constraints: HashMap::new(),
+            charsets: HashMap::new()
+        }
     }
-    let tokens: Vec<&str> = line.split(' ').filter(|&substr| !substr.is_empty()).collect();
 
-    // Creates a blank field from the line's field name
-    if tokens.is_empty() {
-        return Err(RustractError::DB(GenericError {
-            message: format!("line {} did not contain any field data.", line),
-        }));
-    }
-    if tokens[0].len() < 3 {
-        return Err(RustractError::DB(GenericError {
-            message: format!("table field {} cannot have empty name, line: {}", tokens[0], line),
-        }));
-    }
-    let mut field = FieldDesign::new("temp");
-
-    // Handles primary key line (returns)
-    if tokens[0] == "primary" {
-        // Skips over the word "KEY"
-        match tokens.get(2) {
-            Some(val) => {
-                // Sets the requested field to primary
-                match table.field_mut(&unwrap_str(*val)?) {
-                    Some(value) => value,
-                    None => {
+    /// Applies the statement to the database design.
+    fn statement(&mut self, statement: Statement) -> Result<(), RustractError> {
+        match statement {
+            Statement::CreateTable(create) => {
+                if self.db.table(&create.name).is_some() {
+                    if create.if_not_exists {
+                        return Ok(());
+                    }
+                    return Err(RustractError::DB(GenericError {
+                        message: format!("failed to create table {}, it already exists", create.name)
+                    }));
+                }
+                if let Some(charset) = &create.charset {
+                    self.charsets.insert(create.name.clone(), charset.clone());
+                }
+                let table = self.read_table(&create)?;
+                self.db.add(table);
+                for constraint in &create.constraints {
+                    self.name_constraint(&create.name, constraint);
+                }
+                for column in &create.columns {
+                    self.name_column_constraints(&create.name, column);
+                }
+            },
+            Statement::CreateView(view) => {
+                if self.db.table(&view.name).is_some() && !view.or_replace {
+                    return Err(RustractError::DB(GenericError {
+                        message: format!("failed to create view {}, it already exists", view.name)
+                    }));
+                }
+                let table = self.read_view(&view);
+                self.db.add(table);
+            },
+            Statement::CreateEnum { name, values } => {
+                self.enums.insert(name.to_ascii_lowercase(), values);
+            },
+            Statement::AlterTable(alter) => self.alter_table(&alter)?,
+            Statement::DropTable { names, if_exists } => {
+                for name in names {
+                    if self.db.tables.remove(&name).is_none() && !if_exists {
                         return Err(RustractError::DB(GenericError {
-                            message: format!("corrupt primary key formation: {} does not exist in new table", *val)
+                            message: format!("failed to drop table {}, it does not exist", name)
                         }));
                     }
-                }.primary = true;
-                return Ok(());
+                    self.constraints.retain(|(table, _), _| *table != name);
+                    self.charsets.remove(&name);
+                }
             },
-            None => {
-                return Err(RustractError::DB(GenericError {
-                    message: String::from("primary key statement found, but end of line reached"),
-                }));
+            Statement::DropIndex { names, if_exists } => {
+                let dialect = self.dialect;
+                for name in names {
+                    let found = self.db.tables.values_mut()
+                        .find_map(|table| find_index(table, &name, dialect).map(|position| (table, position)));
+                    match found {
+                        Some((table, position)) => remove_index(table, position),
+                        None if if_exists => (),
+                        None => return Err(RustractError::DB(GenericError {
+                            message: format!("failed to drop index {}, it does not exist", name)
+                        })),
+                    }
+                }
+            },
+            Statement::RenameTable(names) => {
+                for (name, new_name) in names {
+                    self.rename_table(&name, &new_name)?;
+                }
+            },
+            Statement::Other => (),
+        }
+        Ok(())
+    }
+
+    /// Creates a table design from the parsed table creation statement.
+    fn read_table(&self, create: &CreateTable) -> Result<TableDesign, RustractError> {
+        let mut table = TableDesign::new(&create.name);
+        table.dialect = self.dialect;
+        for column in &create.columns {
+            table.add(self.read_field(&create.name, column)?);
+        }
+
+        // Keys declared with a column are the same as those declared for the table
+        for column in &create.columns {
+            let columns = vec![column.name.clone()];
+            if column.primary {
+                add_constraint(&mut table, &TableConstraint::PrimaryKey { name: None, columns })?;
+            } else if column.unique {
+                add_constraint(&mut table, &TableConstraint::Index { name: None, columns, unique: true })?;
+            }
+        }
+        for constraint in &create.constraints {
+            add_constraint(&mut table, constraint)?;
+        }
+        for column in &create.columns {
+            for expression in &column.checks {
+                add_constraint(&mut table, &TableConstraint::Check { name: None, expression: expression.clone() })?;
+            }
+            if let Some(reference) = &column.references {
+                add_foreign_key(&mut table, std::slice::from_ref(&column.name), reference)?;
             }
         }
+
+        // In SQLite, a single INTEGER primary key is an alias for the row id, which is generated
+        if self.dialect == Dialect::Sqlite {
+            let primary: Vec<&ColumnDef> = create.columns.iter()
+                .filter(|column| table.field(&column.name).is_some_and(|field| field.primary))
+                .collect();
+            if let [column] = primary[..] {
+                if column.data_type.name == "integer" {
+                    if let Some(field) = table.field_mut(&column.name) {
+                        field.increment = true;
+                        field.generated = true;
+                    }
+                }
+            }
+        }
+        Ok(table)
     }
 
-    // Handles column lines
-    if tokens[0].contains('`') {
-        field.field_design_title = unwrap_str(tokens[0])?;
-        let descriptor = tokens[1].trim().to_ascii_lowercase();
+    /// Creates a read only table design from the parsed view creation statement.
+    ///
+    /// Columns that select a column of a known table copy its field's design (without its keys),
+    /// while other columns (such as expressions) are read as optional JSON, since their type is not known.
+    fn read_view(&self, view: &CreateView) -> TableDesign {
+        let mut table = TableDesign::new(&view.name);
+        table.read_only = true;
+        table.dialect = self.dialect;
+        for column in &view.columns {
+            let fields = self.view_fields(view, column);
+            if fields.is_empty() {
+                let mut field = FieldDesign::new(&column.name);
+                field.datatype = DataType::Json;
+                field.dialect = self.dialect;
+                table.add(field);
+            }
+            for mut field in fields {
+                if !matches!(&column.source, Some((_, source)) if source == "*") {
+                    field.field_design_title = column.name.clone();
+                }
+                field.column = None;
+                field.primary = false;
+                field.unique = false;
+                field.foreign = None;
+                field.default = None;
+                field.checks = Vec::new();
+                table.add(field);
+            }
+        }
+        table
+    }
 
-        // Sets the data type and related fields
-        if descriptor.as_str() == "int" {
-            field.datatype = if line.contains("unsigned") { DataType::Unsigned64 } else { DataType::Signed64 };
-            field.increment = line.contains("auto_increment");
-            if field.increment {
-                field.generated = true;
+    /// Finds the fields of the tables a view's column selects, which may be null if their table is outer joined.
+    fn view_fields(&self, view: &CreateView, column: &ViewColumn) -> Vec<FieldDesign> {
+        let (qualifier, name) = match &column.source {
+            Some(source) => source,
+            None => return Vec::new(),
+        };
+        let tables = view.tables.iter().filter(|table| match qualifier {
+            Some(qualifier) => table.alias.as_ref().unwrap_or(&table.name) == qualifier,
+            None => true,
+        });
+        let mut fields = Vec::new();
+        for source in tables {
+            let table = match self.db.table(&source.name) {
+                Some(table) => table,
+                None => continue,
+            };
+            let selected = table.fields.values()
+                .filter(|field| name == "*" || field.column_name().eq_ignore_ascii_case(name))
+                .map(|field| FieldDesign { required: field.required && !source.nullable, ..field.clone() });
+            fields.extend(selected);
+            if name != "*" && !fields.is_empty() {
+                break;
             }
-            field.bytes = Some(64);
-        } else if descriptor.starts_with("varchar(") {
-            // Pulls the size out of the varchar wrap and converts it to an integer
-            field.datatype = DataType::String;
-            let index = match tokens[1].next_index_of(")", 7) {
-                Some(val) => val,
+        }
+        fields
+    }
+
+    /// Applies the changes of an `ALTER TABLE` statement to a table that was already created.
+    fn alter_table(&mut self, alter: &AlterTable) -> Result<(), RustractError> {
+        let mut name = alter.name.clone();
+        for action in &alter.actions {
+            // Reads new column definitions before the table is borrowed
+            let definition = match action {
+                AlterAction::AddColumn(column) | AlterAction::ModifyColumn { definition: column, .. } => Some(self.read_field(&name, column)?),
+                AlterAction::SetType { column, data_type } => Some(self.read_field(&name, &ColumnDef {
+                    name: column.clone(),
+                    data_type: data_type.clone(),
+                    nullable: None,
+                    auto_increment: false,
+                    primary: false,
+                    unique: false,
+                    checks: Vec::new(),
+                    references: None,
+                    default: None,
+                    comment: None,
+                    charset: None,
+                    line: 0,
+                    column: 0
+                })?),
+                _ => None,
+            };
+
+            match action {
+                AlterAction::RenameTable(new_name) => {
+                    self.rename_table(&name, new_name)?;
+                    name = new_name.clone();
+                    continue;
+                },
+                AlterAction::RenameColumn { column, name: new_name } => {
+                    self.rename_column(&name, column, new_name)?;
+                    continue;
+                },
+                AlterAction::ModifyColumn { column, definition: column_def } if *column != column_def.name => {
+                    self.rename_column(&name, column, &column_def.name)?;
+                },
+                AlterAction::AddColumn(column) => self.name_column_constraints(&name, column),
+                AlterAction::AddConstraint(constraint) => self.name_constraint(&name, constraint),
+                _ => (),
+            }
+
+            let table = match self.db.table_mut(&name) {
+                Some(table) => table,
                 None => return Err(RustractError::DB(GenericError {
-                    message: format!("schema line {} has invalid characters in varchar", line),
-                }))
+                    message: format!("failed to alter table {}, it has not been created", name)
+                })),
             };
-            field.characters = Some(tokens[1][8..index].parse()?);
-        } else if descriptor.starts_with("enum(") {
-            field.datatype = DataType::Enum;
-            // Counts all of the elements in the comma-separated enum
-            field.enum_set = Some(extract_csl(&descriptor)?);
-        } else if descriptor.starts_with("set(") {
-            field.datatype = DataType::Set;
-            field.set = Some(extract_csl(&descriptor)?.into_set());
-        } else if descriptor.contains("tinyint") {
-            field.datatype = DataType::Byte;
-        } else if descriptor.contains("json") {
-            field.datatype = DataType::Json;
-        } else {
+            match action {
+                AlterAction::AddColumn(column) => {
+                    if table.field(&column.name).is_some() {
+                        return Err(RustractError::DB(GenericError {
+                            message: format!("failed to add column {}, it already exists in table {}", column.name, name)
+                        }));
+                    }
+                    table.add(definition.unwrap_or_else(|| FieldDesign::new(&column.name)));
+                    add_column_constraints(table, column)?;
+                },
+                AlterAction::DropColumn(column) => {
+                    alter_field(table, column)?;
+                    table.fields.remove(column);
+                    for index in &mut table.indexes {
+                        index.fields.retain(|field| field != column);
+                    }
+                    table.indexes.retain(|index| !index.fields.is_empty());
+                },
+                AlterAction::ModifyColumn { definition: column, .. } => {
+                    // Keys and foreign keys are separate from the column's definition, so they are kept
+                    let old = alter_field(table, &column.name)?.clone();
+                    let mut field = definition.unwrap_or_else(|| FieldDesign::new(&column.name));
+                    field.primary |= old.primary;
+                    field.unique |= old.unique;
+                    field.required |= old.primary;
+                    field.foreign = old.foreign;
+                    field.checks = old.checks;
+                    table.add(field);
+                    add_column_constraints(table, column)?;
+                },
+                AlterAction::SetType { column, .. } => {
+                    let field = alter_field(table, column)?;
+                    if let Some(typed) = definition {
+                        field.datatype = typed.datatype;
+                        field.bytes = typed.bytes;
+                        field.characters = typed.characters;
+                        field.decimals = typed.decimals;
+                        field.regex = typed.regex;
+                        field.enum_set = typed.enum_set;
+                        field.set = typed.set;
+                        field.array = typed.array;
+                        field.fixed = typed.fixed;
+                    }
+                },
+                AlterAction::SetNullable { column, nullable } => {
+                    let field = alter_field(table, column)?;
+                    field.required = !nullable || field.primary;
+                },
+                AlterAction::RenameTable(_) | AlterAction::RenameColumn { .. } => (),
+                AlterAction::DropPrimaryKey => match table.indexes.iter().position(|index| index.primary) {
+                    Some(position) => remove_index(table, position),
+                    None => return Err(RustractError::DB(GenericError {
+                        message: format!("failed to drop the primary key of table {}, it does not have one", name)
+                    })),
+                },
+                AlterAction::DropIndex(index) => match find_index(table, index, self.dialect) {
+                    Some(position) => remove_index(table, position),
+                    None => return Err(RustractError::DB(GenericError {
+                        message: format!("failed to drop index {}, it does not exist in table {}", index, name)
+                    })),
+                },
+                AlterAction::DropForeignKey(key) => {
+                    let named = (name.clone(), key.to_ascii_lowercase());
+                    match self.constraints.get(&named) {
+                        Some(NamedConstraint::ForeignKey(columns)) => remove_foreign_key(table, columns),
+                        _ => return Err(RustractError::DB(GenericError {
+                            message: format!("failed to drop foreign key {}, it does not exist in table {}", key, name)
+                        })),
+                    }
+                    self.constraints.remove(&named);
+                },
+                AlterAction::DropConstraint(constraint) => {
+                    // Unknown constraints (such as checks that could not be read) have nothing to remove
+                    if let Some(position) = find_index(table, constraint, self.dialect) {
+                        remove_index(table, position);
+                    }
+                    match self.constraints.remove(&(name.clone(), constraint.to_ascii_lowercase())) {
+                        Some(NamedConstraint::ForeignKey(columns)) => remove_foreign_key(table, &columns),
+                        Some(NamedConstraint::Check(expression)) => remove_check(table, &expression),
+                        None => (),
+                    }
+                },
+                AlterAction::AddConstraint(constraint) => add_constraint(table, constraint)?,
+                AlterAction::SetIncrement(title) => {
+                    let field = alter_field(table, title)?;
+                    field.increment = true;
+                    field.generated = true;
+                },
+                AlterAction::SetDefault { column, default } => {
+                    let field = alter_field(table, column)?;
+                    field.default = default.as_ref().map(|default| read_default(field, default));
+                },
+                AlterAction::SetComment { column, comment } => {
+                    let field = alter_field(table, column)?;
+                    if let Some(comment) = comment {
+                        read_annotations(field, comment)?;
+                    }
+                },
+            }
+        }
+        Ok(())
+    }
+
+    /// Keeps the name of a foreign key or check, so that it can be dropped by name.
+    ///
+    /// Foreign keys that were not named are given the name the database would give them.
+    fn name_constraint(&mut self, table: &str, constraint: &TableConstraint) {
+        let (name, named) = match constraint {
+            TableConstraint::ForeignKey { name, columns, .. } => {
+                let name = name.clone().unwrap_or_else(|| match self.dialect {
+                    Dialect::MySql => {
+                        let prefix = format!("{}_ibfk_", table.to_ascii_lowercase());
+                        let count = self.constraints.keys().filter(|(title, name)| title == table && name.starts_with(&prefix)).count();
+                        format!("{}{}", prefix, count + 1)
+                    },
+                    _ => format!("{}_{}_fkey", table, columns.join("_")),
+                });
+                (name, NamedConstraint::ForeignKey(columns.clone()))
+            },
+            TableConstraint::Check { name: Some(name), expression } => (name.clone(), NamedConstraint::Check(expression.clone())),
+            _ => return,
+        };
+        self.constraints.insert((table.to_string(), name.to_ascii_lowercase()), named);
+    }
+
+    /// Keeps the names of the foreign key and checks declared with a column.
+    fn name_column_constraints(&mut self, table: &str, column: &ColumnDef) {
+        if let Some(reference) = &column.references {
+            self.name_constraint(table, &TableConstraint::ForeignKey {
+                name: None,
+                columns: vec![column.name.clone()],
+                reference: reference.clone()
+            });
+        }
+    }
+
+    /// Renames a table, updating the foreign keys that refer to it.
+    fn rename_table(&mut self, name: &str, new_name: &str) -> Result<(), RustractError> {
+        if self.db.table(new_name).is_some() {
             return Err(RustractError::DB(GenericError {
-                message: format!("failed to read schema, {} is not a valid token", descriptor),
+                message: format!("failed to rename table {} to {}, which already exists", name, new_name)
             }));
         }
+        let mut table = match self.db.tables.remove(name) {
+            Some(table) => table,
+            None => return Err(RustractError::DB(GenericError {
+                message: format!("failed to rename table {}, it does not exist", name)
+            })),
+        };
+        table.table_design_title = new_name.to_string();
+        self.db.add(table);
+        if let Some(charset) = self.charsets.remove(name) {
+            self.charsets.insert(new_name.to_string(), charset);
+        }
+        self.constraints = self.constraints.drain()
+            .map(|((table, key), constraint)| ((if table == name { new_name.to_string() } else { table }, key), constraint))
+            .collect();
+        for key in self.db.tables.values_mut().flat_map(|table| table.fields.values_mut()).filter_map(|field| field.foreign.as_mut()) {
+            if key.table == name {
+                key.table = new_name.to_string();
+            }
+        }
+        Ok(())
+    }
 
-        // Sets whether the field is null 
-        field.required = line.contains("not null");
+    /// Renames a column of a table, updating the indexes and foreign keys that use it.
+    fn rename_column(&mut self, table_name: &str, column: &str, name: &str) -> Result<(), RustractError> {
+        let table = match self.db.table_mut(table_name) {
+            Some(table) => table,
+            None => return Err(RustractError::DB(GenericError {
+                message: format!("failed to alter table {}, it has not been created", table_name)
+            })),
+        };
+        let mut field = alter_field(table, column)?.clone();
+        if table.field(name).is_some() {
+            return Err(RustractError::DB(GenericError {
+                message: format!("failed to rename column {} to {}, which already exists in table {}", column, name, table_name)
+            }));
+        }
+        table.fields.remove(column);
+        field.field_design_title = name.to_string();
         table.add(field);
+        for title in table.indexes.iter_mut().flat_map(|index| index.fields.iter_mut()) {
+            if title == column {
+                *title = name.to_string();
+            }
+        }
+        for key in self.db.tables.values_mut().flat_map(|table| table.fields.values_mut()).filter_map(|field| field.foreign.as_mut()) {
+            if key.table == table_name && key.field == column {
+                key.field = name.to_string();
+            }
+        }
+        for (_, constraint) in self.constraints.iter_mut().filter(|((table, _), _)| table == table_name) {
+            if let NamedConstraint::ForeignKey(columns) = constraint {
+                for title in columns.iter_mut().filter(|title| *title == column) {
+                    *title = name.to_string();
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Creates a field design from the parsed column definition of the table.
+    fn read_field(&self, table: &str, column: &ColumnDef) -> Result<FieldDesign, RustractError> {
+        let mut field = FieldDesign::new(&column.name);
+        field.dialect = self.dialect;
+        let data_type = &column.data_type;
+        let postgres = self.dialect == Dialect::PostgreSql;
+        let mut serial = false;
+
+        // Columns without a character set use their table's (character sets that are not supported are measured as UTF-8)
+        let charset = column.charset.as_ref()
+            .or_else(|| self.charsets.get(table))
+            .and_then(|name| name.parse::<Charset>().ok())
+            .unwrap_or_default();
+
+        // Sets the data type and related fields
+        match data_type.name.as_str() {
+            _ if self.dialect == Dialect::Sqlite => read_affinity(&mut field, column)?,
+            "tinyint" | "smallint" | "mediumint" | "int" | "integer" | "bigint" | "int2" | "int4" | "int8" => {
+                read_integer(&mut field, column, false);
+            },
+            "smallserial" | "serial" | "bigserial" | "serial2" | "serial4" | "serial8" => {
+                // In MySQL, serial is an alias for bigint unsigned not null auto_increment unique
+                serial = true;
+                field.unique = !postgres;
+                read_integer(&mut field, column, !postgres);
+            },
+            "decimal" | "dec" | "numeric" | "fixed" => {
+                // MySQL decimals default to a precision of 10 and a scale of 0, while PostgreSQL's are unlimited
+                field.datatype = DataType::Decimal;
+                if !data_type.args.is_empty() || !postgres {
+                    field.characters = Some(if data_type.args.is_empty() { 10 } else { read_size(column, 0)? });
+                    field.decimals = Some(if data_type.args.len() > 1 { read_size(column, 1)? } else { 0 });
+                }
+            },
+            "float" => {
+                // A float with a precision over 24 is stored as a double
+                field.datatype = DataType::Float32;
+                if data_type.args.len() == 1 && read_size(column, 0)? > 24 {
+                    field.datatype = DataType::Float64;
+                }
+                read_float_size(&mut field, column)?;
+            },
+            "float4" => field.datatype = DataType::Float32,
+            "real" if postgres => field.datatype = DataType::Float32,
+            "double" | "real" | "float8" => {
+                field.datatype = DataType::Float64;
+                read_float_size(&mut field, column)?;
+            },
+            "bit" => {
+                // The characters of a bit field are its number of bits
+                let bits = if data_type.args.is_empty() { 1 } else { read_size(column, 0)? };
+                if !(1..=64).contains(&bits) {
+                    return Err(column_error(column, &format!("bit({}) must hold between 1 and 64 bits", bits)));
+                }
+                field.datatype = DataType::Bit;
+                field.characters = Some(bits);
+            },
+            "bool" | "boolean" => {
+                field.datatype = DataType::Boolean;
+                field.bytes = Some(1);
+            },
+            "date" => field.datatype = DataType::Date,
+            "time" | "timetz" => read_temporal(&mut field, column, DataType::Time)?,
+            "datetime" => read_temporal(&mut field, column, DataType::DateTime)?,
+            "timestamp" if !postgres => read_temporal(&mut field, column, DataType::Timestamp)?,
+            "timestamp" | "timestamptz" => read_temporal(&mut field, column, DataType::DateTime)?,
+            "year" => field.datatype = DataType::Year,
+            "varchar" => {
+                // PostgreSQL allows varchar without a length limit
+                field.datatype = DataType::String;
+                if !data_type.args.is_empty() || !postgres {
+                    field.characters = Some(read_size(column, 0)?);
+                }
+            },
+            "char" | "character" | "nchar" | "bpchar" => {
+                // Fixed length strings are padded to their length, which is also their limit
+                field.datatype = DataType::String;
+                field.characters = Some(if data_type.args.is_empty() { 1 } else { read_size(column, 0)? });
+                field.fixed = true;
+            },
+            "text" if postgres => field.datatype = DataType::String,
+            "tinytext" | "text" | "mediumtext" | "longtext" | "tinyblob" | "blob" | "mediumblob" | "longblob" => {
+                // MySQL limits text and blobs by their length in bytes, where text(M) is the smallest type that holds M characters
+                let text = data_type.name.ends_with("text");
+                field.datatype = if text { DataType::String } else { DataType::ByteString };
+                field.bytes = Some(match data_type.name.trim_end_matches("text").trim_end_matches("blob") {
+                    _ if !data_type.args.is_empty() && text => text_bytes(read_size(column, 0)? * charset.max_bytes()),
+                    _ if !data_type.args.is_empty() => text_bytes(read_size(column, 0)?),
+                    "tiny" => TEXT_BYTES[0],
+                    "" => TEXT_BYTES[1],
+                    "medium" => TEXT_BYTES[2],
+                    _ => TEXT_BYTES[3],
+                });
+            },
+            "binary" => {
+                // Fixed length binary strings are padded with zero bytes to their length
+                field.datatype = DataType::ByteString;
+                field.bytes = Some(if data_type.args.is_empty() { 1 } else { read_size(column, 0)? });
+                field.fixed = true;
+            },
+            "varbinary" => {
+                field.datatype = DataType::ByteString;
+                field.bytes = Some(read_size(column, 0)?);
+            },
+            "uuid" => {
+                field.datatype = DataType::String;
+                field.characters = Some(36);
+                field.regex = Some(Format::Uuid.pattern().to_string());
+            },
+            "bytea" => field.datatype = DataType::ByteString,
+            "enum" => {
+                field.datatype = DataType::Enum;
+                field.enum_set = Some(data_type.args.clone());
+            },
+            "set" => {
+                field.datatype = DataType::Set;
+                field.set = Some(data_type.args.clone());
+            },
+            "json" | "jsonb" => field.datatype = DataType::Json,
+            name => match self.enums.get(name) {
+                // Enum types are declared before the tables that use them
+                Some(values) => {
+                    field.datatype = DataType::Enum;
+                    field.enum_set = Some(values.clone());
+                },
+                None => return Err(column_error(column, &format!("{} is not a supported data type", name))),
+            },
+        }
+
+        if field.datatype == DataType::String && charset != Charset::default() {
+            field.charset = Some(charset);
+        }
+        field.array = data_type.array;
+        field.increment = column.auto_increment || serial;
+        field.generated = field.increment;
+        field.primary = column.primary;
+
+        // Incrementing fields default to the next value of their sequence instead
+        if !field.increment {
+            field.default = column.default.as_ref().map(|default| read_default(&field, default));
+        }
+
+        // Sets whether the field is null (primary keys can never be null)
+        field.required = column.nullable == Some(false) || column.primary || serial;
+
+        if let Some(comment) = &column.comment {
+            read_annotations(&mut field, comment)?;
+        }
+        Ok(field)
     }
+}
+
+/// The byte limits of MySQL's tiny, regular, medium and long text and blob types.
+pub(crate) const TEXT_BYTES: [isize; 4] = [255, 65_535, 16_777_215, 4_294_967_295];
 
-    // Unsupported lines like index declarations are ignored for compatibility
+/// Gets the byte limit of the smallest MySQL text or blob type that holds the number of bytes.
+fn text_bytes(bytes: isize) -> isize {
+    TEXT_BYTES.iter().copied().find(|limit| *limit >= bytes).unwrap_or(TEXT_BYTES[3])
+}
+
+/// Adds the keys, checks and references declared with a column that is added or changed by an `ALTER TABLE` statement.
+fn add_column_constraints(table: &mut TableDesign, column: &ColumnDef) -> Result<(), RustractError> {
+    let columns = vec![column.name.clone()];
+    if column.primary && table.primary_key().is_none() {
+        add_constraint(table, &TableConstraint::PrimaryKey { name: None, columns })?;
+    } else if column.unique {
+        add_constraint(table, &TableConstraint::Index { name: None, columns, unique: true })?;
+    }
+    for expression in &column.checks {
+        add_constraint(table, &TableConstraint::Check { name: None, expression: expression.clone() })?;
+    }
+    if let Some(reference) = &column.references {
+        add_foreign_key(table, std::slice::from_ref(&column.name), reference)?;
+    }
     Ok(())
 }
 
-/// Pulls a value out of a sql string-wrapped slice.
-fn unwrap_str(str: &str) -> Result<String, RustractError> {
-    match str.len() > 1 && str.contains('`') {
-        true => {
-            // This first unwrap should be safe since it contains this character
-            let pos_1 = str.index_of("`").unwrap();
-            let pos_2 = str.next_index_of("`", pos_1+1);
-            if pos_2.is_none() {
-                return Err(RustractError::DB(GenericError {
-                    message: format!("string {} does not have two instances of `'s", str),
-                }));
+/// Adds a `CHECK` constraint to the field it refers to, or to the table if it refers to several fields.
+///
+/// Checks that cannot be read (such as those using subqueries) are left for the database to enforce.
+fn add_check(table: &mut TableDesign, expression: &str) {
+    let check = match Check::parse(expression, table.dialect) {
+        Ok(check) => check,
+        Err(_) => return,
+    };
+    if let [column] = &check.columns()[..] {
+        let title = table.fields.keys().find(|title| title.eq_ignore_ascii_case(column)).cloned();
+        if let Some(field) = title.and_then(|title| table.field_mut(&title)) {
+            field.checks.push(expression.to_string());
+            return;
+        }
+    }
+    table.checks.push(expression.to_string());
+}
+
+/// Reads the validation annotations in a column's comment into its field design.
+///
+/// Annotations start a word of the comment, such as `@regex(^[a-z]+$)`, `@min(3)`, `@max(10)`,
+/// `@format(email)` or `@hidden`. The rest of the comment is ignored.
+fn read_annotations(field: &mut FieldDesign, comment: &str) -> Result<(), RustractError> {
+    let chars: Vec<char> = comment.chars().collect();
+    let mut index = 0;
+    while index < chars.len() {
+        // Email addresses and other words containing @ are not annotations
+        if chars[index] != '@' || (index > 0 && !chars[index - 1].is_whitespace()) {
+            index += 1;
+            continue;
+        }
+        let start = index + 1;
+        index = start;
+        while index < chars.len() && (chars[index].is_alphanumeric() || chars[index] == '_') {
+            index += 1;
+        }
+        let name: String = chars[start..index].iter().collect();
+
+        // The argument ends at its matching parenthesis, skipping those escaped by a backslash
+        let mut argument = None;
+        if chars.get(index) == Some(&'(') {
+            let mut depth = 0;
+            let argument_start = index + 1;
+            loop {
+                match chars.get(index) {
+                    None => return Err(annotation_error(field, &name, "is missing a closing parenthesis")),
+                    Some('\\') => index += 1,
+                    Some('(') => depth += 1,
+                    Some(')') => {
+                        depth -= 1;
+                        if depth == 0 {
+                            break;
+                        }
+                    },
+                    _ => (),
+                }
+                index += 1;
             }
+            argument = Some(chars[argument_start..index].iter().collect::<String>());
+            index += 1;
+        }
+
+        match (name.as_str(), argument) {
+            ("regex", Some(pattern)) => {
+                if let Err(e) = regex::Regex::new(&pattern) {
+                    return Err(annotation_error(field, &name, &e.to_string()));
+                }
+                field.regex = Some(pattern);
+            },
+            ("min" | "max", Some(number)) => {
+                let number = match number.trim().parse::<f64>() {
+                    Ok(number) => number,
+                    Err(_) => return Err(annotation_error(field, &name, &format!("expects a number, but found {}", number))),
+                };
+                if name == "min" {
+                    field.min = Some(number);
+                } else {
+                    field.max = Some(number);
+                }
+            },
+            ("format", Some(format)) => match format.trim().parse() {
+                Ok(format) => field.format = Some(format),
+                Err(e) => return Err(annotation_error(field, &name, &e.message())),
+            },
+            ("hidden", None) => field.hidden = true,
+            ("regex" | "min" | "max" | "format", None) => return Err(annotation_error(field, &name, "expects an argument")),
+            ("hidden", Some(_)) => return Err(annotation_error(field, &name, "does not take an argument")),
+            _ => return Err(annotation_error(field, &name, "is not a supported annotation")),
+        }
+    }
+    Ok(())
+}
+
+/// Creates an error for an invalid annotation in a column's comment.
+fn annotation_error(field: &FieldDesign, name: &str, message: &str) -> RustractError {
+    RustractError::Field(GenericError {
+        message: format!("the annotation @{} of field {} {}", name, field.field_design_title, message)
+    })
+}
 
-            // This is a string slice of a &str, the unwrap is safe due to the previous check
-            Ok(str[pos_1+1..pos_2.unwrap()].to_string())
+/// Gets the field changed by an `ALTER TABLE` statement, failing if it does not exist.
+fn alter_field<'a>(table: &'a mut TableDesign, title: &str) -> Result<&'a mut FieldDesign, RustractError> {
+    let table_title = table.table_design_title.clone();
+    table.field_mut(title).ok_or_else(|| RustractError::DB(GenericError {
+        message: format!("failed to alter column {}, it does not exist in table {}", title, table_title)
+    }))
+}
+
+/// Reads a column's default into a value of the field's type.
+///
+/// Literals are written as the database stores them (such as '1' for a number or an enum's label),
+/// so each form the value could take is tested against the field's design.
+/// Literals that do not fit the design (such as MySQL's zero dates) are kept as expressions.
+fn read_default(field: &FieldDesign, default: &DefaultExpr) -> DefaultValue {
+    let (text, sql) = match default {
+        DefaultExpr::Null => return DefaultValue::Null,
+        DefaultExpr::CurrentTimestamp => return DefaultValue::CurrentTimestamp,
+        DefaultExpr::Expression(expression) => return DefaultValue::Expression(expression.clone()),
+        DefaultExpr::Boolean(value) => (value.to_string(), value.to_string().to_ascii_uppercase()),
+        DefaultExpr::Number(number) => (number.clone(), number.clone()),
+        DefaultExpr::String(string) => (string.clone(), format!("'{}'", string.replace('\'', "''"))),
+    };
+
+    let mut candidates = vec![Value::String(text.clone())];
+    candidates.extend(serde_json::from_str::<Value>(&text).ok());
+    match field.datatype {
+        DataType::Boolean if text == "0" || text == "1" => candidates.push(Value::Bool(text == "1")),
+        _ => (),
+    }
+    if field.array && field.dialect == Dialect::PostgreSql {
+        candidates.extend(array_members(&text).map(Value::from));
+    }
+    match candidates.iter().find_map(|candidate| field.validate(candidate).ok()) {
+        Some(value) => DefaultValue::Value(value),
+        None => DefaultValue::Expression(sql),
+    }
+}
+
+/// Reads the members of a PostgreSQL array literal, such as `{a,"b c"}`.
+fn array_members(text: &str) -> Option<Vec<String>> {
+    let inner = text.strip_prefix('{')?.strip_suffix('}')?;
+    let mut members = Vec::new();
+    let mut chars = inner.chars().peekable();
+    while chars.peek().is_some() {
+        let mut member = String::new();
+        if chars.next_if_eq(&'"').is_some() {
+            loop {
+                match chars.next()? {
+                    '"' => break,
+                    '\\' => member.push(chars.next()?),
+                    c => member.push(c),
+                }
+            }
+        } else {
+            while let Some(c) = chars.next_if(|c| *c != ',') {
+                member.push(c);
+            }
+            member = member.trim().to_string();
+        }
+        members.push(member);
+        match chars.next() {
+            Some(',') | None => (),
+            Some(_) => return None,
+        }
+    }
+    Some(members)
+}
+
+/// Adds a constraint to the table design, failing if it refers to a missing field.
+fn add_constraint(table: &mut TableDesign, constraint: &TableConstraint) -> Result<(), RustractError> {
+    match constraint {
+        TableConstraint::Check { expression, .. } => add_check(table, expression),
+        TableConstraint::ForeignKey { columns, reference, .. } => add_foreign_key(table, columns, reference)?,
+        TableConstraint::PrimaryKey { name, columns } => {
+            for title in columns {
+                match table.field_mut(title) {
+                    Some(field) => {
+                        field.primary = true;
+                        field.required = true;
+                    },
+                    None => return Err(missing_key_field("primary key", title, table)),
+                }
+            }
+            table.add_index(Index {
+                name: name.clone(),
+                fields: columns.clone(),
+                unique: true,
+                primary: true
+            });
+        },
+        TableConstraint::Index { name, columns, unique } => {
+            if let Some(title) = columns.iter().find(|title| table.field(title).is_none()) {
+                return Err(missing_key_field("index", title, table));
+            }
+            table.add_index(Index {
+                name: name.clone(),
+                fields: columns.clone(),
+                unique: *unique,
+                primary: false
+            });
         },
-        false => Err(RustractError::DB(GenericError {
-            message: format!("string slice does not match the format `val`: {}", str),
-        }))
     }
+    Ok(())
 }
 
-/// Unwraps parenthesis to get the contents
-fn unwrap_parenthesis(line: &str) -> Result<String, RustractError> {
-    // Get the start and end positions of the parenthesis
-    let start = match line.index_of("(") {
-        Some(index) => index + 1,
-        None => return Err(RustractError::DB(GenericError {
+/// Finds the position of the table's index with the name, where indexes that were not named have the name the database gives them.
+fn find_index(table: &TableDesign, name: &str, dialect: Dialect) -> Option<usize> {
+    table.indexes.iter().position(|index| {
+        let index_name = match (&index.name, dialect, index.primary) {
+            (Some(index_name), _, _) => index_name.clone(),
+            (None, Dialect::MySql, true) => "PRIMARY".to_string(),
+            (None, Dialect::MySql, false) => index.fields.first().cloned().unwrap_or_default(),
+            (None, _, true) => format!("{}_pkey", table.table_design_title),
+            (None, _, false) => crate::ddl::index_name(table, index),
+        };
+        index_name.eq_ignore_ascii_case(name)
+    })
+}
+
+/// Removes the table's index at the position, along with the primary or unique marks it gave its fields.
+fn remove_index(table: &mut TableDesign, position: usize) {
+    let index = table.indexes.remove(position);
+    for title in &index.fields {
+        let unique = table.indexes.iter().any(|other| other.unique && other.fields == [title.as_str()]);
+        if let Some(field) = table.field_mut(title) {
+            field.primary &= !index.primary;
+            field.unique &= unique || index.fields.len() > 1;
+        }
+    }
+}
+
+/// Removes the foreign key of each of the columns.
+fn remove_foreign_key(table: &mut TableDesign, columns: &[String]) {
+    for title in columns {
+        if let Some(field) = table.field_mut(title) {
+            field.foreign = None;
+        }
+    }
+}
+
+/// Removes a check from the field or table that holds it.
+fn remove_check(table: &mut TableDesign, expression: &str) {
+    for checks in table.fields.values_mut().map(|field| &mut field.checks).chain(std::iter::once(&mut table.checks)) {
+        if let Some(position) = checks.iter().position(|check| check == expression) {
+            checks.remove(position);
+            return;
+        }
+    }
+}
+
+/// Creates an error for a key or index that refers to a field the table does not have.
+fn missing_key_field(kind: &str, title: &str, table: &TableDesign) -> RustractError {
+    RustractError::DB(GenericError {
+        message: format!(
+            "corrupt {} formation: {} does not exist in table {}",
+            kind,
+            title,
+            table.table_design_title
+        )
+    })
+}
+
+/// Marks the column as a foreign key to the referenced column.
+///
+/// Keys made of several columns only match a row when every column does,
+/// which a field's single `foreign` setting cannot describe, so they fail to load.
+fn add_foreign_key(table: &mut TableDesign, columns: &[String], reference: &Reference) -> Result<(), RustractError> {
+    let title = match columns {
+        [title] if reference.columns.len() <= 1 => title,
+        _ => return Err(RustractError::DB(GenericError {
             message: format!(
-                "could not unwrap parenthesis, line {} had no start",
-                line
+                "unsupported foreign key formation: columns {} of table {} reference table {} together, \
+                but only foreign keys of a single column are supported",
+                columns.join(", "),
+                table.table_design_title,
+                reference.table
             )
-        }))
+        })),
     };
-    let end = match line.index_of(")") {
-        Some(index) => index,
+
+    let table_title = table.table_design_title.clone();
+    match table.field_mut(title) {
+        Some(field) => field.foreign = Some(ForeignKey {
+            table: reference.table.clone(),
+            // An empty field refers to the primary key, which is found once every table is read
+            field: reference.columns.first().cloned().unwrap_or_default(),
+            on_delete: reference.on_delete,
+            on_update: reference.on_update
+        }),
         None => return Err(RustractError::DB(GenericError {
             message: format!(
-                "could not unwrap parenthesis, line {} had no end",
-                line
+                "corrupt foreign key formation: {} does not exist in table {}",
+                title,
+                table_title
             )
-        }))
-    };
+        })),
+    }
+    Ok(())
+}
 
-    // Catch )( errors
-    if start > end || start >= line.len() {
-        return Err(RustractError::DB(GenericError {
-            message: format!(
-                "could not unwrap parenthesis, line {} has invalid parenthesis format",
-                line
-            )
-        }));
+/// Sets the type of a SQLite field using SQLite's type affinity rules.
+///
+/// Type names that SQLite stores as numbers or text but that imply a format (such as `DATE` or `BOOLEAN`) are read as those types.
+fn read_affinity(field: &mut FieldDesign, column: &ColumnDef) -> Result<(), RustractError> {
+    let name = column.data_type.name.as_str();
+    let args = &column.data_type.args;
+    match name {
+        "bool" | "boolean" => {
+            field.datatype = DataType::Boolean;
+            field.bytes = Some(1);
+        },
+        "date" => field.datatype = DataType::Date,
+        "time" => field.datatype = DataType::Time,
+        "datetime" | "timestamp" => field.datatype = DataType::DateTime,
+        "json" => field.datatype = DataType::Json,
+        _ if name.contains("int") => {
+            // Every SQLite integer can hold 8 bytes
+            field.datatype = DataType::Signed64;
+            field.bytes = Some(8);
+        },
+        _ if name.contains("char") || name.contains("clob") || name.contains("text") => {
+            // SQLite does not enforce lengths, but they are kept as the intended limit
+            field.datatype = DataType::String;
+            if !args.is_empty() {
+                field.characters = Some(read_size(column, 0)?);
+            }
+        },
+        _ if name.is_empty() || name.contains("blob") => field.datatype = DataType::ByteString,
+        _ if name.contains("real") || name.contains("floa") || name.contains("doub") => field.datatype = DataType::Float64,
+        _ => {
+            // Any other type has numeric affinity
+            field.datatype = DataType::Decimal;
+            if !args.is_empty() {
+                field.characters = Some(read_size(column, 0)?);
+                field.decimals = Some(if args.len() > 1 { read_size(column, 1)? } else { 0 });
+            }
+        },
     }
+    Ok(())
+}
 
-    Ok(line.to_ascii_lowercase()[start..end].to_string())
+/// Returns true if the file at the specified path starts with the SQLite database header.
+#[cfg(feature = "sqlite")]
+fn is_sqlite_file(path: &str) -> bool {
+    use std::io::Read;
+    let mut header = [0; 16];
+    match std::fs::File::open(path) {
+        Ok(mut file) => file.read_exact(&mut header).is_ok() && &header == b"SQLite format 3\0",
+        Err(_) => false,
+    }
 }
 
-/// Extracts a comma separated list as a vector.
-fn extract_csl(line_src: &str) -> Result<Vec<String>, RustractError> {
-    // Get line segment containing the CSL
-    let mut csl = vec![];
-    let line = unwrap_parenthesis(line_src)?;
+/// Sets the integer type and byte size of a field from its column's type.
+///
+/// Display widths like `int(11)` do not limit the value, so they are ignored.
+fn read_integer(field: &mut FieldDesign, column: &ColumnDef, unsigned: bool) {
+    let unsigned = unsigned || column.data_type.unsigned;
+    let bytes = match column.data_type.name.as_str() {
+        "tinyint" => 1,
+        "smallint" | "int2" | "smallserial" | "serial2" => 2,
+        "mediumint" => 3,
+        "bigint" | "int8" | "bigserial" | "serial8" => 8,
+        "serial" if unsigned => 8,
+        _ => 4,
+    };
+    field.datatype = match (bytes, unsigned) {
+        (1, true) => DataType::Byte,
+        (1, false) | (2, false) => DataType::Signed16,
+        (2, true) => DataType::Unsigned16,
+        (8, true) => DataType::Unsigned64,
+        (8, false) => DataType::Signed64,
+        (_, true) => DataType::Unsigned32,
+        (_, false) => DataType::Signed32,
+    };
+    field.bytes = Some(bytes);
+}
 
-    for token in line.split(',') {
-        csl.push(token.trim().to_string());
+/// Sets the precision and scale of a float from the `(M,D)` arguments of its column's type.
+fn read_float_size(field: &mut FieldDesign, column: &ColumnDef) -> Result<(), RustractError> {
+    if column.data_type.args.len() == 2 {
+        field.characters = Some(read_size(column, 0)?);
+        field.decimals = Some(read_size(column, 1)?);
     }
+    Ok(())
+}
 
-    Ok(csl)
+/// Sets a temporal type, storing any fractional seconds precision (such as `datetime(3)`) as the decimals.
+fn read_temporal(field: &mut FieldDesign, column: &ColumnDef, datatype: DataType) -> Result<(), RustractError> {
+    field.datatype = datatype;
+    if !column.data_type.args.is_empty() {
+        field.decimals = Some(read_size(column, 0)?);
+    }
+    Ok(())
+}
+
+/// Reads the size argument at the specified index of a column's type.
+fn read_size(column: &ColumnDef, index: usize) -> Result<isize, RustractError> {
+    match column.data_type.args.get(index) {
+        Some(arg) => match arg.parse() {
+            Ok(size) => Ok(size),
+            Err(_) => Err(column_error(column, &format!("{} is not a valid size for type {}", arg, column.data_type.name))),
+        },
+        None => Err(column_error(column, &format!("type {} requires a size", column.data_type.name))),
+    }
+}
+
+/// Creates an error for the specified column, including where it was declared.
+fn column_error(column: &ColumnDef, message: &str) -> RustractError {
+    RustractError::DB(GenericError {
+        message: format!(
+            "failed to read column {}, {} (line {}, column {})",
+            column.name,
+            message,
+            column.line,
+            column.column
+        ),
+    })
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-
-    /// Tests pulling values out of SQL string-wrapped slices.
-    #[test]
-    fn unwrap_test() {
-        let unwrap_me = unwrap_str("I wrapped (`this`)...").expect("failed to unwrap str");
-        assert_eq!(unwrap_me, "this".to_string());
-
-        // Tests empty strings
-        assert_eq!("", unwrap_str("``").unwrap());
-
-        // Tests bounds
-        assert_eq!("e", unwrap_str("`e`").unwrap());
-    }
+    use crate::field::ReferentialAction;
+    use crate::types::DataTypeValue;
+    use crate::report::Rule;
 
     /// Tests the Database extraction code to ensure it obtains the data from the dump.
     #[test]
@@ -340,7 +1348,7 @@ mod test {
         let table_ref: &TableDesign = db.table("user").unwrap();
         let field_ref: &FieldDesign = table_ref.field("registered").unwrap();
 
-        // The good date is below the character limit of 10 (for ISO Strings)
+        // The good date is a valid ISO date, the bad date has too many digits
         let good = serde_json::json!({"registered": "2021-01-01"});
         let bad = serde_json::json!({"registered": "2021-01-001"});
 
@@ -348,6 +1356,613 @@ mod test {
         assert!(field_ref.extract(&bad["registered"]).is_err());
     }
 
+    /// Tests reading statements that line-based reading could not handle.
+    #[test]
+    fn sql_test() {
+        let db = Database::from_sql("
+            -- a comment mentioning CREATE TABLE `fake` (
+            CREATE TABLE `post` (
+              `id` int unsigned
+                NOT NULL AUTO_INCREMENT,
+              `title` varchar(45) COMMENT 'not null, unless (empty)',
+              `kind` enum('Long form','Short, form') NOT NULL,
+              PRIMARY KEY (`id`)
+            );
+        ").unwrap();
+        assert_eq!(db.tables.len(), 1);
+        let table = db.table("post").unwrap();
+        let id = table.field("id").unwrap();
+        assert!(id.required && id.primary && id.increment);
+        assert!(!table.field("title").unwrap().required);
+        assert_eq!(
+            table.field("kind").unwrap().enum_set,
+            Some(vec!["Long form".to_string(), "Short, form".to_string()])
+        );
+
+        // Errors report where the problem was found
+        let error = Database::from_sql("CREATE TABLE `a` (\n  `b` geometry\n);").unwrap_err();
+        assert_eq!(error.message(), "failed to read column b, geometry is not a supported data type (line 2, column 3)");
+    }
+
+    /// Tests that numeric columns are read with the limits MySQL uses.
+    #[test]
+    fn numeric_test() {
+        let db = Database::from_sql("
+            CREATE TABLE `numbers` (
+              `tiny` tinyint NOT NULL,
+              `utiny` tinyint unsigned,
+              `small` smallint(6),
+              `medium` mediumint unsigned,
+              `normal` int(11) unsigned zerofill,
+              `big` bigint NOT NULL AUTO_INCREMENT,
+              `price` decimal(10,2),
+              `ratio` float,
+              `wide` float(30),
+              `exact` double(8,3),
+              `flags` bit(12),
+              `active` boolean
+            );
+        ").unwrap();
+        let table = db.table("numbers").unwrap();
+        let read = |title: &str| {
+            let field = table.field(title).unwrap();
+            (field.datatype.clone(), field.bytes, field.characters, field.decimals)
+        };
+        assert_eq!(read("tiny"), (DataType::Signed16, Some(1), None, None));
+        assert_eq!(read("utiny"), (DataType::Byte, Some(1), None, None));
+        assert_eq!(read("small"), (DataType::Signed16, Some(2), None, None));
+        assert_eq!(read("medium"), (DataType::Unsigned32, Some(3), None, None));
+        assert_eq!(read("normal"), (DataType::Unsigned32, Some(4), None, None));
+        assert_eq!(read("big"), (DataType::Signed64, Some(8), None, None));
+        assert_eq!(read("price"), (DataType::Decimal, None, Some(10), Some(2)));
+        assert_eq!(read("ratio"), (DataType::Float32, None, None, None));
+        assert_eq!(read("wide"), (DataType::Float64, None, None, None));
+        assert_eq!(read("exact"), (DataType::Float64, None, Some(8), Some(3)));
+        assert_eq!(read("flags"), (DataType::Bit, None, Some(12), None));
+        assert_eq!(read("active"), (DataType::Boolean, Some(1), None, None));
+        assert!(table.field("big").unwrap().generated);
+
+        // The range checks match the columns
+        let tiny = table.field("tiny").unwrap();
+        assert!(tiny.extract(&serde_json::json!(127)).is_ok());
+        assert!(tiny.extract(&serde_json::json!(128)).is_err());
+        let price = table.field("price").unwrap();
+        assert!(price.extract(&serde_json::json!("12345678.99")).is_ok());
+        assert!(price.extract(&serde_json::json!("123456789")).is_err());
+        assert!(price.extract(&serde_json::json!("1.001")).is_err());
+    }
+
+    /// Tests reading the character and byte limits of text, char and blob columns, and writing them back.
+    #[test]
+    fn text_test() {
+        let db = Database::from_sql("
+            CREATE TABLE `file` (
+              `id` char(36) NOT NULL,
+              `flag` char,
+              `title` tinytext,
+              `summary` text(1000),
+              `body` text,
+              `draft` mediumtext,
+              `archive` longtext,
+              `hash` binary(16),
+              `key` varbinary(255),
+              `thumbnail` blob,
+              `data` longblob
+            );
+        ").unwrap();
+        let table = db.table("file").unwrap();
+        let limits = |title: &str| {
+            let field = table.field(title).unwrap();
+            (field.datatype.clone(), field.characters, field.bytes)
+        };
+        assert_eq!(limits("id"), (DataType::String, Some(36), None));
+        assert_eq!(limits("flag"), (DataType::String, Some(1), None));
+        assert_eq!(limits("title"), (DataType::String, None, Some(255)));
+        assert_eq!(limits("summary"), (DataType::String, None, Some(65_535)));
+        assert_eq!(limits("body"), (DataType::String, None, Some(65_535)));
+        assert_eq!(limits("draft"), (DataType::String, None, Some(16_777_215)));
+        assert_eq!(limits("archive"), (DataType::String, None, Some(4_294_967_295)));
+        assert_eq!(limits("hash"), (DataType::ByteString, None, Some(16)));
+        assert_eq!(limits("key"), (DataType::ByteString, None, Some(255)));
+        assert_eq!(limits("thumbnail"), (DataType::ByteString, None, Some(65_535)));
+        assert_eq!(limits("data"), (DataType::ByteString, None, Some(4_294_967_295)));
+
+        // Values MySQL would truncate or reject fail extraction
+        let id = table.field("id").unwrap();
+        assert!(id.extract(&serde_json::json!("123e4567-e89b-12d3-a456-426614174000")).is_ok());
+        assert!(id.extract(&serde_json::json!("123e4567-e89b-12d3-a456-4266141740001")).is_err());
+        assert!(table.field("title").unwrap().extract(&serde_json::json!("a".repeat(256))).is_err());
+        assert!(table.field("hash").unwrap().extract(&serde_json::json!(vec![0; 17])).is_err());
+
+        // The types are written back as the smallest that holds their limits, keeping fixed lengths
+        assert!(table.field("id").unwrap().fixed && table.field("hash").unwrap().fixed && !table.field("key").unwrap().fixed);
+        let sql = db.to_sql(Dialect::MySql).unwrap();
+        for column in [
+            "`id` CHAR(36)", "`flag` CHAR(1)", "`title` TINYTEXT", "`body` TEXT", "`draft` MEDIUMTEXT",
+            "`hash` BINARY(16)", "`key` VARBINARY(255)", "`thumbnail` BLOB", "`data` LONGBLOB"
+        ] {
+            assert!(sql.contains(column), "{} was not written in {}", column, sql);
+        }
+        assert!(db.to_sql(Dialect::PostgreSql).unwrap().contains("\"id\" CHAR(36)"));
+    }
+
+    #[test]
+    fn charset_test() {
+        let db = Database::from_sql("
+            CREATE TABLE `person` (
+              `name` varchar(45) NOT NULL,
+              `bio` tinytext,
+              `notes` text(100),
+              `tag` varchar(10) CHARACTER SET utf8mb4,
+              `code` char(3) COLLATE ascii_bin
+            ) ENGINE=InnoDB DEFAULT CHARSET=latin1 COLLATE=latin1_swedish_ci;
+
+            CREATE TABLE `post` (
+              `title` varchar(45) NOT NULL,
+              `summary` text(100)
+            ) ENGINE=InnoDB DEFAULT CHARSET=utf8mb3;
+        ").unwrap();
+        let person = db.table("person").unwrap();
+        assert_eq!(person.field("name").unwrap().charset, Some(Charset::Latin1));
+        assert_eq!(person.field("tag").unwrap().charset, None);
+        assert_eq!(person.field("code").unwrap().charset, Some(Charset::Ascii));
+        assert_eq!(person.field("notes").unwrap().bytes, Some(255));
+        assert_eq!(db.table("post").unwrap().field("summary").unwrap().bytes, Some(65_535));
+
+        // Characters are counted rather than bytes, while bytes are measured in the character set
+        let post = db.table("post").unwrap();
+        assert!(post.field("title").unwrap().extract(&serde_json::json!("ü".repeat(45))).is_ok());
+        assert!(post.field("title").unwrap().extract(&serde_json::json!("ü".repeat(46))).is_err());
+        assert_eq!(post.field("title").unwrap().validate(&serde_json::json!("Hello 👋")).unwrap_err().rule, Rule::Charset);
+        assert!(person.field("bio").unwrap().extract(&serde_json::json!("ü".repeat(255))).is_ok());
+        assert!(person.field("bio").unwrap().extract(&serde_json::json!("ü".repeat(256))).is_err());
+
+        let sql = db.to_sql(Dialect::MySql).unwrap();
+        assert!(sql.contains("`name` VARCHAR(45) CHARACTER SET latin1"), "{}", sql);
+    }
+
+    #[test]
+    fn temporal_test() {
+        let db = Database::from_sql("
+            CREATE TABLE `events` (
+              `day` date NOT NULL,
+              `starts` time,
+              `created` datetime(3),
+              `updated` timestamp,
+              `season` year(4)
+            );
+        ").unwrap();
+        let table = db.table("events").unwrap();
+        assert_eq!(table.field("day").unwrap().datatype, DataType::Date);
+        assert_eq!(table.field("starts").unwrap().datatype, DataType::Time);
+        assert_eq!(table.field("created").unwrap().datatype, DataType::DateTime);
+        assert_eq!(table.field("created").unwrap().decimals, Some(3));
+        assert_eq!(table.field("updated").unwrap().datatype, DataType::Timestamp);
+        assert_eq!(table.field("season").unwrap().datatype, DataType::Year);
+    }
+
+    /// Tests reading a PostgreSQL schema dump.
+    #[test]
+    fn postgres_test() {
+        let db = Database::from_sql_dialect("
+            SET statement_timeout = 0;
+            SELECT pg_catalog.set_config('search_path', '', false);
+            CREATE TYPE public.\"Status\" AS ENUM ('active', 'banned');
+            CREATE TABLE public.account (
+                id integer NOT NULL,
+                handle character varying(30) NOT NULL,
+                bio text,
+                balance numeric,
+                status public.\"Status\" DEFAULT 'active'::public.\"Status\" NOT NULL,
+                token uuid,
+                settings jsonb,
+                avatar bytea,
+                tags text[],
+                created timestamp with time zone DEFAULT now() NOT NULL
+            );
+            CREATE SEQUENCE public.account_id_seq AS integer START WITH 1 INCREMENT BY 1;
+            ALTER SEQUENCE public.account_id_seq OWNED BY public.account.id;
+            CREATE TABLE public.post (
+                id bigserial PRIMARY KEY,
+                visible boolean,
+                score real
+            );
+            ALTER TABLE ONLY public.account ALTER COLUMN id SET DEFAULT nextval('public.account_id_seq'::regclass);
+            ALTER TABLE ONLY public.account
+                ADD CONSTRAINT account_pkey PRIMARY KEY (id);
+        ", Dialect::PostgreSql).unwrap();
+
+        let account = db.table("account").unwrap();
+        let id = account.field("id").unwrap();
+        assert!(id.primary && id.increment && id.generated);
+        assert_eq!(id.datatype, DataType::Signed32);
+        assert_eq!(account.field("handle").unwrap().characters, Some(30));
+        assert_eq!(account.field("bio").unwrap().characters, None);
+        assert_eq!(account.field("balance").unwrap().datatype, DataType::Decimal);
+        assert_eq!(account.field("balance").unwrap().characters, None);
+        assert_eq!(account.field("status").unwrap().enum_set, Some(vec!["active".to_string(), "banned".to_string()]));
+        assert_eq!(account.field("settings").unwrap().datatype, DataType::Json);
+        assert_eq!(account.field("avatar").unwrap().datatype, DataType::ByteString);
+        assert_eq!(account.field("created").unwrap().datatype, DataType::DateTime);
+        assert!(account.field("tags").unwrap().array);
+
+        // UUIDs are strings checked against their format
+        let token = account.field("token").unwrap();
+        assert!(token.extract(&serde_json::json!("123e4567-e89b-12d3-a456-426614174000")).is_ok());
+        assert!(token.extract(&serde_json::json!("123e4567")).is_err());
+
+        let post = db.table("post").unwrap();
+        let id = post.field("id").unwrap();
+        assert!(id.primary && id.increment && id.required);
+        assert_eq!(id.datatype, DataType::Signed64);
+        assert_eq!(post.field("score").unwrap().datatype, DataType::Float32);
+
+        // Unknown types are reported with their position
+        let error = Database::from_sql_dialect("CREATE TABLE a (\n  b public.missing\n);", Dialect::PostgreSql).unwrap_err();
+        assert_eq!(error.message(), "failed to read column b, missing is not a supported data type (line 2, column 3)");
+    }
+
+    /// Tests that keys and indexes are read into the table designs.
+    #[test]
+    fn index_test() {
+        let db = Database::from_schema("./tests/schema.sql").unwrap();
+        let user = db.table("user").unwrap();
+        assert!(user.field("email").unwrap().unique);
+        assert!(!user.field("name").unwrap().unique);
+        assert_eq!(user.primary_key().unwrap().fields, vec!["id"]);
+        let names: Vec<Option<&str>> = user.indexes.iter().map(|index| index.name.as_deref()).collect();
+        assert_eq!(names, vec![None, Some("id_UNIQUE"), Some("email_UNIQUE")]);
+        assert!(!db.table("post").unwrap().field("author").unwrap().unique);
+
+        // Composite keys are kept together without making each field unique
+        let db = Database::from_sql_dialect("
+            CREATE TABLE membership (
+                member integer NOT NULL,
+                team integer NOT NULL,
+                email text UNIQUE,
+                PRIMARY KEY (member, team)
+            );
+            CREATE UNIQUE INDEX membership_team ON membership (team, email);
+            ALTER TABLE ONLY membership ADD CONSTRAINT membership_member UNIQUE (member);
+        ", Dialect::PostgreSql).unwrap();
+        let membership = db.table("membership").unwrap();
+        assert_eq!(membership.primary_key().unwrap().fields, vec!["member", "team"]);
+        assert!(membership.field("email").unwrap().unique);
+        assert!(!membership.field("team").unwrap().unique);
+        assert!(membership.field("member").unwrap().unique);
+        assert_eq!(membership.indexes.len(), 4);
+
+        let error = Database::from_sql("CREATE TABLE `a` (`b` int, UNIQUE KEY `c` (`b`, `d`));").unwrap_err();
+        assert_eq!(error.message(), "corrupt index formation: d does not exist in table a");
+    }
+
+    /// Tests that foreign keys are read and checked against the fields they reference.
+    #[test]
+    fn foreign_key_test() {
+        let db = Database::from_schema("./tests/schema.sql").unwrap();
+        let author = db.table("post").unwrap().field("author").unwrap();
+        assert_eq!(author.foreign, Some(ForeignKey {
+            table: "user".to_string(),
+            field: "id".to_string(),
+            on_delete: Some(ReferentialAction::Cascade),
+            on_update: Some(ReferentialAction::NoAction)
+        }));
+
+        // Unnamed columns refer to the primary key, even in tables read later
+        let db = Database::from_sql_dialect("
+            CREATE TABLE post (author INT REFERENCES person);
+            CREATE TABLE person (id INTEGER PRIMARY KEY);
+        ", Dialect::Sqlite).unwrap();
+        assert_eq!(db.table("post").unwrap().field("author").unwrap().foreign.as_ref().unwrap().to_string(), "person.id");
+
+        // Mismatched types fail to load
+        let error = Database::from_sql("
+            CREATE TABLE `person` (`id` int unsigned NOT NULL, PRIMARY KEY (`id`));
+            CREATE TABLE `post` (`author` int, FOREIGN KEY (`author`) REFERENCES `person` (`id`));
+        ").unwrap_err();
+        assert_eq!(
+            error.message(),
+            "foreign key post.author is a Signed 32-bit Integer, but references person.id which is a Unsigned 32-bit Integer"
+        );
+        assert!(Database::from_sql("CREATE TABLE `post` (`author` int, FOREIGN KEY (`author`) REFERENCES `person` (`id`));").is_err());
+
+        // Keys of several columns are rejected rather than split into unrelated keys
+        let error = Database::from_sql("
+            CREATE TABLE `a` (`x` int NOT NULL, `y` int NOT NULL, PRIMARY KEY (`x`, `y`));
+            CREATE TABLE `b` (`ax` int, `ay` int, FOREIGN KEY (`ax`, `ay`) REFERENCES `a` (`x`, `y`));
+        ").unwrap_err();
+        assert_eq!(
+            error.message(),
+            "unsupported foreign key formation: columns ax, ay of table b reference table a together, \
+            but only foreign keys of a single column are supported"
+        );
+        assert!(Database::from_sql("
+            CREATE TABLE `a` (`x` int NOT NULL, `y` int NOT NULL, PRIMARY KEY (`x`, `y`));
+            CREATE TABLE `b` (`ax` int, `ay` int, FOREIGN KEY (`ax`, `ay`) REFERENCES `a`);
+        ").is_err());
+    }
+
+    /// Tests reading a SQLite schema using its type affinity rules.
+    #[test]
+    fn sqlite_test() {
+        let db = Database::from_sql_dialect("
+            CREATE TABLE item (
+                id INTEGER PRIMARY KEY,
+                name VARCHAR(20) NOT NULL CHECK (length(name) > 0),
+                price NUMERIC(6, 2),
+                weight FLOATING POINT,
+                stock DOUBLE,
+                added DATE,
+                active BOOLEAN,
+                data BLOB,
+                extra
+            );
+            CREATE TABLE tag (
+                item INT,
+                label TEXT,
+                PRIMARY KEY (item, label),
+                CHECK (label <> '')
+            );
+            CREATE TABLE note (
+                id INT PRIMARY KEY
+            );
+        ", Dialect::Sqlite).unwrap();
+
+        let item = db.table("item").unwrap();
+        let read = |title: &str| {
+            let field = item.field(title).unwrap();
+            (field.datatype.clone(), field.characters, field.decimals)
+        };
+        assert_eq!(read("name"), (DataType::String, Some(20), None));
+        assert_eq!(read("price"), (DataType::Decimal, Some(6), Some(2)));
+        assert_eq!(read("weight"), (DataType::Signed64, None, None));
+        assert_eq!(read("stock"), (DataType::Float64, None, None));
+        assert_eq!(read("added"), (DataType::Date, None, None));
+        assert_eq!(read("active"), (DataType::Boolean, None, None));
+        assert_eq!(read("data"), (DataType::ByteString, None, None));
+        assert_eq!(read("extra"), (DataType::ByteString, None, None));
+        assert_eq!(item.field("name").unwrap().checks, vec!["length(name) > 0"]);
+        assert!(item.extract(&serde_json::json!({ "name": "" })).is_err());
+
+        // Only a single INTEGER primary key is generated
+        assert!(item.field("id").unwrap().generated);
+        let tag = db.table("tag").unwrap();
+        assert!(tag.field("item").unwrap().primary && !tag.field("item").unwrap().generated);
+        assert_eq!(tag.field("label").unwrap().checks, vec!["label <> ''"]);
+        assert!(!db.table("note").unwrap().field("id").unwrap().generated);
+    }
+
+    /// Tests reading CHECK constraints onto the fields and tables they refer to.
+    #[test]
+    fn check_test() {
+        let db = Database::from_sql("
+            CREATE TABLE `person` (
+              `age` int DEFAULT NULL,
+              `role` enum('admin','user') NOT NULL,
+              `start` date DEFAULT NULL,
+              `end` date DEFAULT NULL,
+              CONSTRAINT `person_chk_1` CHECK (((`age` >= 0) and (`age` < 150))),
+              CONSTRAINT `person_chk_2` CHECK ((`start` <= `end`)),
+              CONSTRAINT `person_chk_3` CHECK (((`role` <> _utf8mb4'admin') or (`age` >= 18))),
+              CONSTRAINT `person_chk_4` CHECK ((`age` > 200)) /*!80016 NOT ENFORCED */
+            );
+        ").unwrap();
+        let table = db.table("person").unwrap();
+        assert_eq!(table.field("age").unwrap().checks, vec!["((\"age\" >= 0) and (\"age\" < 150))"]);
+        assert_eq!(table.checks.len(), 2);
+
+        assert!(table.extract(&serde_json::json!({ "role": 0, "age": 30 })).is_ok());
+        assert!(table.extract(&serde_json::json!({ "role": 1, "age": 200 })).is_err());
+        assert!(table.extract(&serde_json::json!({ "role": 0, "age": 12 })).is_err());
+        assert!(table.extract(&serde_json::json!({ "role": 1, "start": "2021-02-01", "end": "2021-01-01" })).is_err());
+        assert!(table.extract(&serde_json::json!({ "role": 1, "start": "2021-02-01" })).is_ok());
+    }
+
+    /// Tests reading validation annotations from column comments.
+    #[test]
+    fn annotation_test() {
+        let db = Database::from_sql("
+            CREATE TABLE `user` (
+              `name` varchar(45) NOT NULL COMMENT 'Display name @min(3) @regex(^[a-z]+\\\\(?[0-9]*\\\\)?$)',
+              `email` varchar(45) NOT NULL COMMENT 'Sent to support@test.com @format(email)',
+              `hash` varchar(60) NOT NULL COMMENT '@hidden',
+              `age` int DEFAULT NULL COMMENT '@max(150)'
+            );
+        ").unwrap();
+        let table = db.table("user").unwrap();
+        let name = table.field("name").unwrap();
+        assert_eq!(name.min, Some(3.0));
+        assert_eq!(name.regex, Some("^[a-z]+\\(?[0-9]*\\)?$".to_string()));
+        assert_eq!(table.field("email").unwrap().format, Some(Format::Email));
+        assert!(table.field("hash").unwrap().hidden);
+        assert_eq!(table.field("age").unwrap().max, Some(150.0));
+
+        assert!(table.extract(&serde_json::json!({ "name": "bob(1)", "email": "bob@test.com", "hash": "x" })).is_ok());
+        assert!(table.extract(&serde_json::json!({ "name": "bo", "email": "bob@test.com", "hash": "x" })).is_err());
+        assert!(table.extract(&serde_json::json!({ "name": "bob", "email": "bob", "hash": "x" })).is_err());
+
+        let error = Database::from_sql("CREATE TABLE `a` (`b` int COMMENT '@size(3)');").unwrap_err();
+        assert_eq!(error.message(), "the annotation @size of field b is not a supported annotation");
+        assert!(Database::from_sql("CREATE TABLE `a` (`b` int COMMENT '@min(three)');").is_err());
+    }
+
+    /// Tests reading column defaults as values of each field's type.
+    #[test]
+    fn default_test() {
+        let db = Database::from_sql("
+            CREATE TABLE `account` (
+              `id` int unsigned NOT NULL AUTO_INCREMENT,
+              `credit` int NOT NULL DEFAULT '0',
+              `rate` decimal(4,2) NOT NULL DEFAULT '1.50',
+              `role` enum('Admin','Basic') NOT NULL DEFAULT 'Basic',
+              `verified` boolean NOT NULL DEFAULT '1',
+              `flags` bit(3) DEFAULT b'101',
+              `note` varchar(45) DEFAULT NULL,
+              `created` timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP,
+              `expires` datetime NOT NULL DEFAULT '0000-00-00 00:00:00',
+              PRIMARY KEY (`id`)
+            );
+        ").unwrap();
+        let table = db.table("account").unwrap();
+        let default = |title: &str| table.field(title).unwrap().default.clone();
+        assert_eq!(default("id"), None);
+        assert_eq!(default("credit"), Some(DefaultValue::Value(DataTypeValue::Signed32(0))));
+        assert_eq!(default("rate"), Some(DefaultValue::Value(DataTypeValue::Decimal("1.5".parse().unwrap()))));
+        assert_eq!(default("role"), Some(DefaultValue::Value(DataTypeValue::Enum(1, "Basic".to_string()))));
+        assert_eq!(default("verified"), Some(DefaultValue::Value(DataTypeValue::Boolean(true))));
+        assert_eq!(default("flags"), Some(DefaultValue::Value(DataTypeValue::Bit(5))));
+        assert_eq!(default("note"), Some(DefaultValue::Null));
+        assert_eq!(default("created"), Some(DefaultValue::CurrentTimestamp));
+
+        // Zero dates are not valid values, so they are kept as written
+        assert_eq!(default("expires"), Some(DefaultValue::Expression("'0000-00-00 00:00:00'".to_string())));
+
+        // Required fields with a default are optional input
+        let credit = table.field("credit").unwrap();
+        assert_eq!(credit.export(true, None), "  credit?: number,\n");
+        assert_eq!(credit.export(false, None), "  credit: number,\n");
+        let row = table.extract(&serde_json::json!({ "expires": "2030-01-01 00:00:00" })).unwrap();
+        assert_eq!(row.len(), 1);
+    }
+
+    #[test]
+    fn dump_test() {
+        let db = Database::from_sql("
+            DROP TABLE IF EXISTS `post`;
+            CREATE TABLE `user` (`id` int unsigned NOT NULL, `email` varchar(60) NOT NULL, `age` int, `temp` int);
+            CREATE TABLE IF NOT EXISTS `user` (`id` int);
+            CREATE TABLE `post` (
+              `id` int unsigned NOT NULL,
+              `author` int unsigned NOT NULL,
+              CONSTRAINT `post_ibfk_1` FOREIGN KEY (`author`) REFERENCES `user` (`id`),
+              CONSTRAINT `adult` CHECK (`id` > 0)
+            );
+            CREATE TABLE `log` (`id` int unsigned, `user` int unsigned, KEY (`user`), FOREIGN KEY (`user`) REFERENCES `user` (`id`));
+            ALTER TABLE `user` ADD PRIMARY KEY (`id`), ADD UNIQUE KEY `email_UNIQUE` (`email`), ADD KEY `by_age` (`age`);
+            ALTER TABLE `user` DROP KEY `by_age`, DROP COLUMN `temp`;
+            ALTER TABLE `post` ADD PRIMARY KEY (`id`), DROP CHECK `adult`;
+            ALTER TABLE `log` DROP FOREIGN KEY `log_ibfk_1`, DROP INDEX `user`;
+            DROP INDEX `email_UNIQUE` ON `user`;
+        ").unwrap();
+
+        // The second creation of the user table is skipped
+        let user = db.table("user").unwrap();
+        assert_eq!(user.fields.len(), 3);
+        assert_eq!(user.primary_key().unwrap().fields, vec!["id"]);
+        assert_eq!(user.indexes.len(), 1);
+        assert!(!user.field("email").unwrap().unique);
+
+        let post = db.table("post").unwrap();
+        assert_eq!(post.field("author").unwrap().foreign.as_ref().unwrap().to_string(), "user.id");
+        assert!(post.field("id").unwrap().checks.is_empty());
+        let log = db.table("log").unwrap();
+        assert!(log.field("user").unwrap().foreign.is_none() && log.indexes.is_empty());
+
+        // PostgreSQL names keys after their table and columns
+        let db = Database::from_sql_dialect("
+            CREATE TABLE person (id integer PRIMARY KEY, email text UNIQUE, parent integer REFERENCES person);
+            ALTER TABLE person DROP CONSTRAINT person_parent_fkey, DROP CONSTRAINT person_email_key;
+            ALTER TABLE ONLY person DROP CONSTRAINT person_pkey;
+        ", Dialect::PostgreSql).unwrap();
+        let person = db.table("person").unwrap();
+        assert!(person.indexes.is_empty() && person.field("parent").unwrap().foreign.is_none());
+        assert!(!person.field("id").unwrap().primary);
+
+        assert!(Database::from_sql("CREATE TABLE `a` (`id` int); CREATE TABLE `a` (`id` int);").is_err());
+        assert!(Database::from_sql("CREATE TABLE `a` (`id` int); ALTER TABLE `a` DROP INDEX `id`;").is_err());
+        assert!(Database::from_sql("CREATE TABLE `a` (`id` int); ALTER TABLE `a` DROP FOREIGN KEY `a_ibfk_1`;").is_err());
+    }
+
+    #[test]
+    fn view_test() {
+        let db = Database::from_sql("
+            CREATE TABLE `user` (`id` int unsigned NOT NULL AUTO_INCREMENT, `name` varchar(45) NOT NULL, PRIMARY KEY (`id`));
+            CREATE TABLE `post` (`id` int unsigned NOT NULL, `author` int unsigned NOT NULL, `title` varchar(100) NOT NULL,
+                FOREIGN KEY (`author`) REFERENCES `user` (`id`));
+
+            DROP TABLE IF EXISTS `user_posts`;
+            /*!50001 DROP VIEW IF EXISTS `user_posts`*/;
+            SET @saved_cs_client     = @@character_set_client;
+            /*!50001 CREATE VIEW `user_posts` AS SELECT
+             1 AS `id`,
+             1 AS `title`,
+             1 AS `total`*/;
+            SET character_set_client = @saved_cs_client;
+
+            /*!50001 DROP VIEW IF EXISTS `user_posts`*/;
+            /*!50001 CREATE ALGORITHM=UNDEFINED */
+            /*!50013 DEFINER=`root`@`localhost` SQL SECURITY DEFINER */
+            /*!50001 VIEW `user_posts` AS select `u`.`id` AS `id`,`p`.`title` AS `title`,count(`p`.`id`) AS `total`
+                from (`user` `u` left join `post` `p` on((`p`.`author` = `u`.`id`))) group by `u`.`id` */;
+            CREATE VIEW `everything` AS SELECT * FROM `post`;
+        ").unwrap();
+
+        let view = db.table("user_posts").unwrap();
+        assert!(view.read_only);
+        let id = view.field("id").unwrap();
+        assert!(id.datatype == DataType::Unsigned32 && id.required && !id.primary);
+
+        // Columns of outer joined tables may be null, and expressions have no known type
+        let title = view.field("title").unwrap();
+        assert!(title.characters == Some(100) && !title.required);
+        assert_eq!(view.field("total").unwrap().datatype, DataType::Json);
+
+        let everything = db.table("everything").unwrap();
+        assert_eq!(everything.fields.keys().collect::<Vec<&String>>(), vec!["author", "id", "title"]);
+        assert!(everything.field("author").unwrap().foreign.is_none());
+
+        // Views have no input type, and are not created as tables
+        crate::filesystem::check_path("./types/").unwrap();
+        view.export("./types/").unwrap();
+        let typescript = std::fs::read_to_string("./types/user_posts.ts").unwrap();
+        assert!(typescript.contains("export interface User_posts {") && !typescript.contains("Input"));
+        assert!(!db.to_sql(Dialect::MySql).unwrap().contains("user_posts"));
+        assert!(Database::from_sql("CREATE VIEW `a` AS SELECT 1; CREATE VIEW `a` AS SELECT 2;").is_err());
+    }
+
+    #[test]
+    fn migrations_test() {
+        let folder = "./migrations_read_test";
+        let _ = std::fs::remove_dir_all(folder);
+        std::fs::create_dir(folder).unwrap();
+        let write = |name: &str, sql: &str| std::fs::write(format!("{}/{}", folder, name), sql).unwrap();
+        write("V1__create.sql", "
+            CREATE TABLE `user` (`id` int unsigned NOT NULL AUTO_INCREMENT, `name` varchar(45), `note` varchar(45), PRIMARY KEY (`id`));
+            CREATE TABLE `post` (`id` int unsigned NOT NULL, `author` int unsigned, FOREIGN KEY (`author`) REFERENCES `user` (`id`));
+            CREATE TABLE `draft` (`id` int);
+        ");
+        write("V2__alter.sql", "
+            ALTER TABLE `user` ADD COLUMN `email` varchar(60) NOT NULL UNIQUE, DROP COLUMN `note`, MODIFY `name` varchar(20) NOT NULL;
+            ALTER TABLE `user` CHANGE `id` `user_id` int unsigned NOT NULL AUTO_INCREMENT, RENAME TO `account`;
+        ");
+        write("V10__drop.sql", "DROP TABLE `draft`; RENAME TABLE `post` TO `article`;");
+        write("V10__drop.down.sql", "this is not read");
+
+        let db = Database::from_migrations(folder, Dialect::MySql).unwrap();
+        assert_eq!(db.tables.keys().collect::<Vec<&String>>(), vec!["account", "article"]);
+        let account = db.table("account").unwrap();
+        assert!(account.field("note").is_none());
+        assert_eq!(account.field("name").unwrap().characters, Some(20));
+        assert!(account.field("name").unwrap().required);
+        assert!(account.field("email").unwrap().unique);
+        assert_eq!(account.primary_key().unwrap().fields, vec!["user_id"]);
+        assert_eq!(db.table("article").unwrap().field("author").unwrap().foreign.as_ref().unwrap().to_string(), "account.user_id");
+
+        // Errors name the migration that failed
+        write("V11__fail.sql", "ALTER TABLE `account` DROP COLUMN `note`;");
+        assert_eq!(
+            Database::from_migrations(folder, Dialect::MySql).unwrap_err().message(),
+            "failed to apply migration V11__fail.sql, failed to alter column note, it does not exist in table account"
+        );
+        std::fs::remove_file(format!("{}/V11__fail.sql", folder)).unwrap();
+        write("seed.sql", "DROP TABLE `account`;");
+        assert_eq!(
+            Database::from_migrations(folder, Dialect::MySql).unwrap_err().message(),
+            "failed to read migration seed.sql, its name does not start with a version number"
+        );
+        std::fs::remove_dir_all(folder).unwrap();
+    }
+
     /// Creates a test export of the types extracted from the Database dump.
     /// 
     /// These TypeScript types should be compiled manually to complete the test.
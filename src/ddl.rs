@@ -0,0 +1,591 @@
+use std::collections::HashSet;
+use crate::db::{Database, TEXT_BYTES};
+use crate::error::{RustractError, GenericError};
+use crate::field::{DefaultValue, FieldDesign, ReferentialAction};
+use crate::table::{Index, TableDesign};
+use crate::types::{DataType, DataTypeValue, Dialect};
+
+/// Writes the statements that create the database design, in the specified dialect.
+///
+/// Tables are created after the tables their foreign keys refer to.
+/// Foreign keys that form a cycle are added once every table is created (except in SQLite, which does not check them until use).
+/// Views are left out, since their queries are not kept.
+pub(crate) fn create_database(db: &Database, dialect: Dialect) -> Result<String, RustractError> {
+    let tables = db.tables.values().filter(|table| !table.read_only).collect();
+    Ok(create_tables(db, tables, dialect, HashSet::new())?.join("\n\n") + "\n")
+}
+
+/// Writes the statements that create the tables, where `created` holds the titles of the tables that already exist.
+pub(crate) fn create_tables<'a>(db: &'a Database, tables: Vec<&'a TableDesign>, dialect: Dialect, mut created: HashSet<&'a str>) -> Result<Vec<String>, RustractError> {
+    let mut statements = Vec::new();
+    let mut deferred = Vec::new();
+    for table in creation_order(db, tables) {
+        created.insert(table.table_design_title.as_str());
+        let sql = create_table(db, table, dialect, &created, &table.table_design_title)?;
+        statements.extend(sql.types);
+        statements.push(sql.create);
+        statements.extend(sql.indexes);
+        deferred.extend(sql.foreign_keys);
+    }
+    statements.extend(deferred);
+    Ok(statements)
+}
+
+/// Orders the tables so each is after the tables its foreign keys refer to, where possible.
+pub(crate) fn creation_order<'a>(db: &'a Database, tables: Vec<&'a TableDesign>) -> Vec<&'a TableDesign> {
+    let mut order = Vec::new();
+    for table in &tables {
+        visit(db, table, &mut order, &mut HashSet::new());
+    }
+    order.retain(|table| tables.contains(table));
+    order
+}
+
+/// The statements that create a single table.
+pub(crate) struct TableSql {
+    /// The types the table uses, which are created before it.
+    pub types: Vec<String>,
+    pub create: String,
+    /// The indexes that are created after the table.
+    pub indexes: Vec<String>,
+    /// The foreign keys that are added once the tables they refer to are created.
+    pub foreign_keys: Vec<String>
+}
+
+/// Adds the table to the creation order after the tables it refers to.
+fn visit<'a>(db: &'a Database, table: &'a TableDesign, order: &mut Vec<&'a TableDesign>, visiting: &mut HashSet<&'a str>) {
+    let title = table.table_design_title.as_str();
+    if order.iter().any(|created| created.table_design_title == title) || !visiting.insert(title) {
+        return;
+    }
+    for key in table.fields.values().filter_map(|field| field.foreign.as_ref()) {
+        if let Some(referenced) = db.table(&key.table) {
+            visit(db, referenced, order, visiting);
+        }
+    }
+    order.push(table);
+}
+
+/// Writes the statements that create a single table under the specified name (such as a temporary name when SQLite rebuilds a table).
+///
+/// `created` holds the titles of the tables that already exist, including this one.
+pub(crate) fn create_table(db: &Database, table: &TableDesign, dialect: Dialect, created: &HashSet<&str>, name: &str) -> Result<TableSql, RustractError> {
+    let title = &table.table_design_title;
+    let mut before = Vec::new();
+    let mut lines = Vec::new();
+    let mut after = Vec::new();
+    let mut deferred = Vec::new();
+
+    // Primary key fields are written first
+    let mut fields: Vec<&FieldDesign> = table.fields.values().collect();
+    fields.sort_by_key(|field| !field.primary);
+    for field in fields {
+        // PostgreSQL enums are types of their own
+        before.extend(create_type(table, field, dialect));
+        lines.push(column_definition(table, field, dialect)?);
+    }
+
+    if let Some(primary) = table.primary_key().filter(|_| inline_primary_key(table, dialect).is_none()) {
+        lines.push(format!("PRIMARY KEY ({})", columns(table, &primary.fields, dialect)));
+    }
+    for index in table.indexes.iter().filter(|index| !index.primary) {
+        let name = index_name(table, index);
+        match (dialect, index.unique) {
+            (Dialect::MySql, unique) => lines.push(format!(
+                "{}KEY {} ({})",
+                if unique { "UNIQUE " } else { "" },
+                quote(&name, dialect),
+                columns(table, &index.fields, dialect)
+            )),
+            (_, true) => lines.push(format!(
+                "CONSTRAINT {} UNIQUE ({})",
+                quote(&name, dialect),
+                columns(table, &index.fields, dialect)
+            )),
+            (_, false) => after.push(format!(
+                "CREATE INDEX {} ON {} ({});",
+                quote(&name, dialect),
+                quote(title, dialect),
+                columns(table, &index.fields, dialect)
+            )),
+        }
+    }
+
+    for field in table.fields.values() {
+        let key = match &field.foreign {
+            Some(key) => key,
+            None => continue,
+        };
+        let constraint = foreign_key(db, table, field, dialect)?;
+        if created.contains(key.table.as_str()) || dialect == Dialect::Sqlite {
+            lines.push(constraint);
+        } else {
+            deferred.push(format!("ALTER TABLE {} ADD {};", quote(title, dialect), constraint));
+        }
+    }
+
+    // SQLite stores enums as text, so their values are kept by a check
+    if dialect == Dialect::Sqlite {
+        for field in table.fields.values().filter(|field| field.datatype == DataType::Enum) {
+            lines.push(format!(
+                "CHECK ({} IN ({}))",
+                quote(field.column_name(), dialect),
+                values(field).iter().map(|value| string(value)).collect::<Vec<String>>().join(", ")
+            ));
+        }
+    }
+    for check in field_checks(table).chain(table.checks.iter()) {
+        lines.push(format!("CHECK ({})", check_sql(check, dialect)));
+    }
+
+    Ok(TableSql {
+        types: before,
+        create: format!("CREATE TABLE {} (\n  {}\n);", quote(name, dialect), lines.join(",\n  ")),
+        indexes: after,
+        foreign_keys: deferred
+    })
+}
+
+/// Writes the statement that creates the PostgreSQL enum type of a field, if it needs one.
+pub(crate) fn create_type(table: &TableDesign, field: &FieldDesign, dialect: Dialect) -> Option<String> {
+    if dialect != Dialect::PostgreSql || !matches!(field.datatype, DataType::Enum | DataType::Set) {
+        return None;
+    }
+    Some(format!(
+        "CREATE TYPE {} AS ENUM ({});",
+        enum_type(table, field),
+        values(field).iter().map(|value| string(value)).collect::<Vec<String>>().join(", ")
+    ))
+}
+
+/// Writes the constraint of a field's foreign key, which is named after its table and column.
+pub(crate) fn foreign_key(db: &Database, table: &TableDesign, field: &FieldDesign, dialect: Dialect) -> Result<String, RustractError> {
+    let key = match &field.foreign {
+        Some(key) => key,
+        None => return Err(RustractError::Field(GenericError {
+            message: format!("field {}.{} is not a foreign key", table.table_design_title, field.field_design_title)
+        })),
+    };
+    let referenced = db.table(&key.table).and_then(|table| table.field(&key.field)).ok_or_else(|| RustractError::DB(GenericError {
+        message: format!(
+            "foreign key {}.{} references {}, which does not exist",
+            table.table_design_title,
+            field.field_design_title,
+            key
+        )
+    }))?;
+    let mut constraint = format!(
+        "CONSTRAINT {} FOREIGN KEY ({}) REFERENCES {} ({})",
+        quote(&foreign_key_name(table, field), dialect),
+        quote(field.column_name(), dialect),
+        quote(&key.table, dialect),
+        quote(referenced.column_name(), dialect)
+    );
+    if let Some(action) = key.on_delete {
+        constraint += &format!(" ON DELETE {}", referential_action(action));
+    }
+    if let Some(action) = key.on_update {
+        constraint += &format!(" ON UPDATE {}", referential_action(action));
+    }
+    Ok(constraint)
+}
+
+/// Gets the name of a field's foreign key constraint.
+pub(crate) fn foreign_key_name(table: &TableDesign, field: &FieldDesign) -> String {
+    format!("{}_{}_fkey", table.table_design_title, field.column_name())
+}
+
+/// Writes the definition of a field's column, as used in `CREATE TABLE` or `ALTER TABLE ... ADD COLUMN`.
+pub(crate) fn column_definition(table: &TableDesign, field: &FieldDesign, dialect: Dialect) -> Result<String, RustractError> {
+    let mut definition = format!("{} {}", quote(field.column_name(), dialect), sql_type(table, field, dialect)?);
+    if field.required {
+        definition += " NOT NULL";
+    }
+    if field.increment {
+        definition += match dialect {
+            Dialect::MySql => " AUTO_INCREMENT",
+            Dialect::PostgreSql => " GENERATED BY DEFAULT AS IDENTITY",
+            // SQLite only increments a single integer primary key, which must be declared with its column
+            Dialect::Sqlite if inline_primary_key(table, dialect) == Some(field) => " PRIMARY KEY AUTOINCREMENT",
+            Dialect::Sqlite => "",
+        };
+    } else if let Some(default) = &field.default {
+        definition += &format!(" DEFAULT {}", default_sql(field, default, dialect)?);
+    }
+    Ok(definition)
+}
+
+/// Gets the SQLite field that is declared as the table's incrementing primary key with its column.
+fn inline_primary_key(table: &TableDesign, dialect: Dialect) -> Option<&FieldDesign> {
+    match &table.primary_key()?.fields[..] {
+        [title] if dialect == Dialect::Sqlite => table.field(title).filter(|field| field.increment),
+        _ => None,
+    }
+}
+
+/// Gets the SQL type of a field's column in the specified dialect.
+pub(crate) fn sql_type(table: &TableDesign, field: &FieldDesign, dialect: Dialect) -> Result<String, RustractError> {
+    if field.array && dialect != Dialect::PostgreSql {
+        return Err(unsupported(table, field, "holds an array", dialect));
+    }
+    let size = |name: &str, size: Option<isize>| match size {
+        Some(size) => format!("{}({})", name, size),
+        None => name.to_string(),
+    };
+    let precision = |name: &str| match (field.characters, field.decimals) {
+        (Some(characters), Some(decimals)) => format!("{}({},{})", name, characters, decimals),
+        (Some(characters), None) => format!("{}({})", name, characters),
+        _ => name.to_string(),
+    };
+    let text = || match field.characters {
+        Some(characters) if field.fixed => format!("CHAR({})", characters),
+        Some(characters) => format!("VARCHAR({})", characters),
+        None => "TEXT".to_string(),
+    };
+    let small = field.bytes == Some(1);
+    let medium = field.bytes == Some(3);
+
+    let name = match dialect {
+        Dialect::MySql => match field.datatype {
+            // Text and blobs without a length are written as the smallest type that holds their bytes
+            DataType::String => {
+                let text = match (field.characters, field.bytes) {
+                    (None, Some(bytes)) => format!("{}TEXT", text_size(bytes)),
+                    _ => text(),
+                };
+                match field.charset {
+                    Some(charset) => format!("{} CHARACTER SET {}", text, charset),
+                    None => text,
+                }
+            },
+            DataType::ByteString => match field.bytes {
+                Some(bytes) if TEXT_BYTES[1..].contains(&bytes) => format!("{}BLOB", text_size(bytes)),
+                Some(bytes) if field.fixed => format!("BINARY({})", bytes),
+                Some(bytes) => format!("VARBINARY({})", bytes),
+                None => "BLOB".to_string(),
+            },
+            DataType::Json => "JSON".to_string(),
+            DataType::Signed64 => "BIGINT".to_string(),
+            DataType::Unsigned64 => "BIGINT UNSIGNED".to_string(),
+            DataType::Signed32 if medium => "MEDIUMINT".to_string(),
+            DataType::Signed32 => "INT".to_string(),
+            DataType::Unsigned32 if medium => "MEDIUMINT UNSIGNED".to_string(),
+            DataType::Unsigned32 => "INT UNSIGNED".to_string(),
+            DataType::Signed16 if small => "TINYINT".to_string(),
+            DataType::Signed16 => "SMALLINT".to_string(),
+            DataType::Unsigned16 => "SMALLINT UNSIGNED".to_string(),
+            DataType::Byte => "TINYINT UNSIGNED".to_string(),
+            DataType::Float64 => precision("DOUBLE"),
+            DataType::Float32 => precision("FLOAT"),
+            DataType::Decimal => precision("DECIMAL"),
+            DataType::Date => "DATE".to_string(),
+            DataType::Time => size("TIME", field.decimals),
+            DataType::DateTime => size("DATETIME", field.decimals),
+            DataType::Timestamp => size("TIMESTAMP", field.decimals),
+            DataType::Year => "YEAR".to_string(),
+            DataType::Boolean => "BOOLEAN".to_string(),
+            DataType::Bit => size("BIT", field.characters),
+            DataType::Enum => format!("ENUM({})", values(field).iter().map(|value| string(value)).collect::<Vec<String>>().join(",")),
+            DataType::Set => format!("SET({})", values(field).iter().map(|value| string(value)).collect::<Vec<String>>().join(",")),
+        },
+        // PostgreSQL does not have unsigned integers, so they are stored in the next larger type
+        Dialect::PostgreSql => match field.datatype {
+            DataType::String => text(),
+            DataType::ByteString => "BYTEA".to_string(),
+            DataType::Json => "JSONB".to_string(),
+            DataType::Signed64 => "BIGINT".to_string(),
+            DataType::Unsigned64 => "NUMERIC(20)".to_string(),
+            DataType::Signed32 | DataType::Unsigned16 => "INTEGER".to_string(),
+            DataType::Unsigned32 => "BIGINT".to_string(),
+            DataType::Signed16 | DataType::Byte | DataType::Year => "SMALLINT".to_string(),
+            DataType::Float64 => "DOUBLE PRECISION".to_string(),
+            DataType::Float32 => "REAL".to_string(),
+            DataType::Decimal => precision("NUMERIC"),
+            DataType::Date => "DATE".to_string(),
+            DataType::Time => size("TIME", field.decimals),
+            DataType::DateTime => size("TIMESTAMP", field.decimals),
+            DataType::Timestamp => size("TIMESTAMPTZ", field.decimals),
+            DataType::Boolean => "BOOLEAN".to_string(),
+            DataType::Bit => size("BIT", field.characters),
+            DataType::Enum => enum_type(table, field),
+            // A set is stored as an array of its values
+            DataType::Set => format!("{}[]", enum_type(table, field)),
+        },
+        // SQLite keeps the declared type, but stores values by its affinity
+        Dialect::Sqlite => match field.datatype {
+            DataType::String | DataType::Enum | DataType::Set => text(),
+            DataType::ByteString => "BLOB".to_string(),
+            DataType::Json => "JSON".to_string(),
+            DataType::Signed64 | DataType::Unsigned64 | DataType::Signed32 | DataType::Unsigned32
+                | DataType::Signed16 | DataType::Unsigned16 | DataType::Byte | DataType::Year | DataType::Bit => "INTEGER".to_string(),
+            DataType::Float64 | DataType::Float32 => "REAL".to_string(),
+            DataType::Decimal => precision("NUMERIC"),
+            DataType::Date => "DATE".to_string(),
+            DataType::Time => "TIME".to_string(),
+            DataType::DateTime | DataType::Timestamp => "DATETIME".to_string(),
+            DataType::Boolean => "BOOLEAN".to_string(),
+        },
+    };
+    Ok(if field.array { format!("{}[]", name) } else { name })
+}
+
+/// Gets the prefix of the smallest MySQL text or blob type that holds the number of bytes, such as `MEDIUM`.
+fn text_size(bytes: isize) -> &'static str {
+    let position = TEXT_BYTES.iter().position(|limit| *limit >= bytes).unwrap_or(3);
+    ["TINY", "", "MEDIUM", "LONG"][position]
+}
+
+/// Writes the SQL of a field's default.
+pub(crate) fn default_sql(field: &FieldDesign, default: &DefaultValue, dialect: Dialect) -> Result<String, RustractError> {
+    Ok(match default {
+        DefaultValue::Null => "NULL".to_string(),
+        DefaultValue::CurrentTimestamp => match field.decimals {
+            Some(decimals) if decimals > 0 && dialect != Dialect::Sqlite => format!("CURRENT_TIMESTAMP({})", decimals),
+            _ => "CURRENT_TIMESTAMP".to_string(),
+        },
+        DefaultValue::Value(value) => literal(value, dialect)?,
+        DefaultValue::Expression(expression) => expression.clone(),
+    })
+}
+
+/// Writes a value as an SQL literal.
+fn literal(value: &DataTypeValue, dialect: Dialect) -> Result<String, RustractError> {
+    Ok(match value {
+        DataTypeValue::String(text) => string(text),
+        // PostgreSQL stores a set as an array, written as quoted members in braces
+        DataTypeValue::Set(members) if dialect == Dialect::PostgreSql => string(&format!(
+            "{{{}}}",
+            members.iter()
+                .map(|member| format!("\"{}\"", member.replace('\\', "\\\\").replace('"', "\\\"")))
+                .collect::<Vec<String>>()
+                .join(",")
+        )),
+        DataTypeValue::Set(members) => string(&members.join(",")),
+        DataTypeValue::Json(json) => match dialect {
+            // MySQL only allows JSON defaults written as expressions
+            Dialect::MySql => format!("({})", string(&serde_json::to_string(json)?)),
+            _ => string(&serde_json::to_string(json)?),
+        },
+        DataTypeValue::ByteString(bytes) => {
+            let hex: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+            match dialect {
+                Dialect::PostgreSql => format!("'\\x{}'", hex),
+                _ => format!("X'{}'", hex),
+            }
+        },
+        DataTypeValue::Date(_) | DataTypeValue::Time(_) | DataTypeValue::DateTime(_) | DataTypeValue::Timestamp(_) => string(&value.to_string()),
+        DataTypeValue::Decimal(_) if dialect == Dialect::MySql => string(&value.to_string()),
+        DataTypeValue::Boolean(value) => if *value { "TRUE" } else { "FALSE" }.to_string(),
+        DataTypeValue::Null => "NULL".to_string(),
+        DataTypeValue::Enum(_, label) => string(label),
+        DataTypeValue::Array(values) => format!(
+            "ARRAY[{}]",
+            values.iter().map(|value| literal(value, dialect)).collect::<Result<Vec<String>, RustractError>>()?.join(", ")
+        ),
+        _ => value.to_string(),
+    })
+}
+
+/// Gets the name of a PostgreSQL enum type created for a field.
+pub(crate) fn enum_type(table: &TableDesign, field: &FieldDesign) -> String {
+    format!("{}_{}", table.table_design_title, field.column_name())
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' })
+        .collect()
+}
+
+/// Gets the values of a field's enum or set, in order.
+pub(crate) fn values(field: &FieldDesign) -> Vec<String> {
+    match (&field.enum_set, &field.set) {
+        (Some(values), _) => values.clone(),
+        (None, Some(set)) => set.clone(),
+        (None, None) => Vec::new(),
+    }
+}
+
+/// Gets the checks of each field of the table.
+pub(crate) fn field_checks(table: &TableDesign) -> impl Iterator<Item = &String> {
+    table.fields.values().flat_map(|field| field.checks.iter())
+}
+
+/// Gets the name of an index, naming it after its table and fields if it has no name.
+pub(crate) fn index_name(table: &TableDesign, index: &Index) -> String {
+    index.name.clone().unwrap_or_else(|| format!(
+        "{}_{}_{}",
+        table.table_design_title,
+        index.fields.join("_"),
+        if index.unique { "key" } else { "idx" }
+    ))
+}
+
+/// Writes the quoted column names of the fields, in order.
+pub(crate) fn columns(table: &TableDesign, fields: &[String], dialect: Dialect) -> String {
+    fields.iter()
+        .map(|title| quote(table.field(title).map_or(title.as_str(), FieldDesign::column_name), dialect))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+/// Gets the SQL of a referential action.
+fn referential_action(action: ReferentialAction) -> &'static str {
+    match action {
+        ReferentialAction::Restrict => "RESTRICT",
+        ReferentialAction::Cascade => "CASCADE",
+        ReferentialAction::SetNull => "SET NULL",
+        ReferentialAction::SetDefault => "SET DEFAULT",
+        ReferentialAction::NoAction => "NO ACTION",
+    }
+}
+
+/// Quotes an identifier, such as a table or column name.
+pub(crate) fn quote(name: &str, dialect: Dialect) -> String {
+    match dialect {
+        Dialect::MySql => format!("`{}`", name.replace('`', "``")),
+        _ => format!("\"{}\"", name.replace('"', "\"\"")),
+    }
+}
+
+/// Writes a check, which is stored in standard SQL, in the specified dialect.
+///
+/// MySQL reads double quotes as strings, so quoted identifiers are quoted with backticks instead.
+pub(crate) fn check_sql(check: &str, dialect: Dialect) -> String {
+    if dialect != Dialect::MySql {
+        return check.to_string();
+    }
+    let mut sql = String::new();
+    let mut quote = None;
+    for c in check.chars() {
+        match (quote, c) {
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                sql.push(if c == '"' { '`' } else { c });
+            },
+            (Some(open), _) if c == open => {
+                quote = None;
+                sql.push(if c == '"' { '`' } else { c });
+            },
+            (Some('"'), '`') => sql.push_str("``"),
+            _ => sql.push(c),
+        }
+    }
+    sql
+}
+
+/// Quotes a string literal.
+pub(crate) fn string(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "''"))
+}
+
+/// Creates an error for a field that cannot be created in the specified dialect.
+fn unsupported(table: &TableDesign, field: &FieldDesign, message: &str, dialect: Dialect) -> RustractError {
+    RustractError::Field(GenericError {
+        message: format!(
+            "failed to write field {}.{}, it {}, which {} does not support",
+            table.table_design_title,
+            field.field_design_title,
+            message,
+            dialect
+        )
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn create_test() {
+        let db = Database::from_sql("CREATE TABLE `post` (
+            `id` INT UNSIGNED NOT NULL AUTO_INCREMENT,
+            `author` INT UNSIGNED NOT NULL,
+            `status` ENUM('Draft','Posted') NOT NULL DEFAULT 'Draft',
+            `title` VARCHAR(45) NOT NULL DEFAULT 'It''s new',
+            `posted` DATETIME(3) NULL DEFAULT CURRENT_TIMESTAMP(3),
+            `score` DECIMAL(5,2) NULL CHECK (`score` >= 0),
+            PRIMARY KEY (`id`),
+            KEY `author_idx` (`author`),
+            CONSTRAINT `fk_author` FOREIGN KEY (`author`) REFERENCES `user` (`id`) ON DELETE CASCADE
+        );
+        CREATE TABLE `user` (`id` INT UNSIGNED NOT NULL AUTO_INCREMENT, `email` VARCHAR(45) NOT NULL UNIQUE, PRIMARY KEY (`id`));").unwrap();
+
+        // Referenced tables are created first
+        assert_eq!(db.to_sql(Dialect::MySql).unwrap(), "CREATE TABLE `user` (
+  `id` INT UNSIGNED NOT NULL AUTO_INCREMENT,
+  `email` VARCHAR(45) NOT NULL,
+  PRIMARY KEY (`id`),
+  UNIQUE KEY `user_email_key` (`email`)
+);
+
+CREATE TABLE `post` (
+  `id` INT UNSIGNED NOT NULL AUTO_INCREMENT,
+  `author` INT UNSIGNED NOT NULL,
+  `posted` DATETIME(3) DEFAULT CURRENT_TIMESTAMP(3),
+  `score` DECIMAL(5,2),
+  `status` ENUM('Draft','Posted') NOT NULL DEFAULT 'Draft',
+  `title` VARCHAR(45) NOT NULL DEFAULT 'It''s new',
+  PRIMARY KEY (`id`),
+  KEY `author_idx` (`author`),
+  CONSTRAINT `post_author_fkey` FOREIGN KEY (`author`) REFERENCES `user` (`id`) ON DELETE CASCADE,
+  CHECK (`score` >= 0)
+);
+");
+
+        let postgres = db.to_sql(Dialect::PostgreSql).unwrap();
+        assert!(postgres.contains("CREATE TYPE post_status AS ENUM ('Draft', 'Posted');"));
+        assert!(postgres.contains("\"id\" BIGINT NOT NULL GENERATED BY DEFAULT AS IDENTITY"));
+        assert!(postgres.contains("CREATE INDEX \"author_idx\" ON \"post\" (\"author\");"));
+        let sqlite = db.to_sql(Dialect::Sqlite).unwrap();
+        assert!(sqlite.contains("\"id\" INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT"));
+        assert!(sqlite.contains("CHECK (\"status\" IN ('Draft', 'Posted'))"));
+
+        // PostgreSQL sets are arrays of an enum, so their defaults are array literals
+        let tags = Database::from_sql(r#"CREATE TABLE `post` (`tags` SET('news','long "read"') NOT NULL DEFAULT 'news,long "read"');"#).unwrap();
+        let postgres = tags.to_sql(Dialect::PostgreSql).unwrap();
+        assert!(postgres.contains(r#""tags" post_tags[] NOT NULL DEFAULT '{"news","long \"read\""}'"#), "{}", postgres);
+        let read = Database::from_sql_dialect(&postgres, Dialect::PostgreSql).unwrap();
+        assert_eq!(
+            read.table("post").unwrap().field("tags").unwrap().default,
+            Some(DefaultValue::Value(DataTypeValue::Array(vec![
+                DataTypeValue::Enum(0, "news".to_string()),
+                DataTypeValue::Enum(1, "long \"read\"".to_string())
+            ])))
+        );
+        assert!(tags.to_sql(Dialect::MySql).unwrap().contains(r#"DEFAULT 'news,long "read"'"#));
+
+        // Arrays can only be created in PostgreSQL
+        let tags = Database::from_sql_dialect("CREATE TABLE post (tags text[]);", Dialect::PostgreSql).unwrap();
+        assert!(tags.to_sql(Dialect::PostgreSql).unwrap().contains("\"tags\" TEXT[]"));
+        assert_eq!(
+            tags.to_sql(Dialect::MySql).unwrap_err().message(),
+            "failed to write field post.tags, it holds an array, which MySQL does not support"
+        );
+    }
+
+    #[test]
+    fn round_trip_test() {
+        let db = Database::from_schema("./tests/schema.sql").unwrap();
+        for dialect in [Dialect::MySql, Dialect::PostgreSql, Dialect::Sqlite] {
+            let read = Database::from_sql_dialect(&db.to_sql(dialect).unwrap(), dialect).unwrap();
+            for (title, table) in &db.tables {
+                let read_table = read.table(title).unwrap();
+                assert_eq!(read_table.indexes, table.indexes, "{} indexes of {}", dialect, title);
+                for (field_title, field) in &table.fields {
+                    let read_field = read_table.field(field_title).unwrap();
+                    assert_eq!(
+                        (read_field.required, read_field.increment, &read_field.foreign),
+                        (field.required, field.increment, &field.foreign),
+                        "{} field {}.{}", dialect, title, field_title
+                    );
+                    // SQLite enums are read back as text
+                    if dialect != Dialect::Sqlite {
+                        assert_eq!(read_field.enum_set, field.enum_set);
+                    }
+                }
+            }
+            // Only MySQL has every type of the design
+            if dialect == Dialect::MySql {
+                assert!(read.diff(&db).is_empty());
+            }
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use crate::error::{RustractError, GenericError};
+
+/// An exact decimal number, stored as its digits so that no precision is lost.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Decimal {
+    negative: bool,
+    /// The digits before the decimal point, without leading zeros.
+    integer: String,
+    /// The digits after the decimal point, without trailing zeros.
+    fraction: String
+}
+
+/// The most digits a decimal may have, which is MySQL's highest `DECIMAL` precision.
+const MAX_DIGITS: usize = 65;
+
+impl Decimal {
+    /// Parses a decimal number such as `-12.50` or `1.25e3`, returning `None` if it is invalid.
+    ///
+    /// Numbers with more than 65 digits (or an exponent over 65) are also invalid, since no column can hold them.
+    pub fn parse(text: &str) -> Option<Decimal> {
+        let (negative, text) = match text.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, text.strip_prefix('+').unwrap_or(text)),
+        };
+
+        // Splits off the exponent, if there is one
+        let (mantissa, exponent) = match text.find(['e', 'E']) {
+            Some(index) => (&text[..index], text[index + 1..].parse::<i32>().ok()?),
+            None => (text, 0),
+        };
+        let (integer, fraction) = match mantissa.find('.') {
+            Some(index) => (&mantissa[..index], &mantissa[index + 1..]),
+            None => (mantissa, ""),
+        };
+        if (integer.is_empty() && fraction.is_empty())
+            || !integer.chars().chain(fraction.chars()).all(|character| character.is_ascii_digit()) {
+            return None;
+        }
+
+        // Exponents are limited before the digits are padded, so a large one cannot allocate a huge string
+        if exponent.unsigned_abs() as usize > MAX_DIGITS {
+            return None;
+        }
+
+        // Moves the decimal point by the exponent
+        let digits = format!("{}{}", integer, fraction);
+        let point = integer.len() as i64 + i64::from(exponent);
+        let (integer, fraction) = if point <= 0 {
+            (String::new(), format!("{}{}", "0".repeat((-point) as usize), digits))
+        } else if point as usize >= digits.len() {
+            (format!("{}{}", digits, "0".repeat(point as usize - digits.len())), String::new())
+        } else {
+            (digits[..point as usize].to_string(), digits[point as usize..].to_string())
+        };
+
+        let integer = integer.trim_start_matches('0').to_string();
+        let fraction = fraction.trim_end_matches('0').to_string();
+        if integer.len() + fraction.len() > MAX_DIGITS {
+            return None;
+        }
+        Some(Decimal {
+            negative: negative && !(integer.is_empty() && fraction.is_empty()),
+            integer,
+            fraction
+        })
+    }
+
+    /// Returns true if this number is below zero.
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    /// Returns the number of significant digits before the decimal point.
+    pub fn integer_digits(&self) -> usize {
+        self.integer.len()
+    }
+
+    /// Returns the number of significant digits after the decimal point.
+    pub fn fraction_digits(&self) -> usize {
+        self.fraction.len()
+    }
+
+    /// Returns the number of significant digits, leaving out the zeros before the first and after the last nonzero digit.
+    pub fn significant_digits(&self) -> usize {
+        format!("{}{}", self.integer, self.fraction).trim_matches('0').len()
+    }
+
+    /// Converts this number to the nearest float, which may lose precision.
+    pub fn to_f64(&self) -> f64 {
+        self.to_string().parse().unwrap_or(f64::NAN)
+    }
+}
+
+impl Display for Decimal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.negative {
+            write!(f, "-")?;
+        }
+        write!(f, "{}", if self.integer.is_empty() { "0" } else { &self.integer })?;
+        if !self.fraction.is_empty() {
+            write!(f, ".{}", self.fraction)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Decimal {
+    type Err = RustractError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Decimal::parse(text).ok_or_else(|| RustractError::Generic(GenericError {
+            message: format!("{} is not a valid Decimal", text),
+        }))
+    }
+}
+
+impl Serialize for Decimal {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Decimal {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        text.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_test() {
+        let decimal = Decimal::parse("-0012.3400").unwrap();
+        assert!(decimal.is_negative());
+        assert_eq!((decimal.integer_digits(), decimal.fraction_digits()), (2, 2));
+        assert_eq!(decimal.to_string(), "-12.34");
+        assert_eq!(Decimal::parse("1200.0034").unwrap().significant_digits(), 8);
+
+        // Exponents move the decimal point without rounding
+        assert_eq!(Decimal::parse("1.25e3").unwrap().to_string(), "1250");
+        assert_eq!(Decimal::parse("125E-4").unwrap().to_string(), "0.0125");
+        assert_eq!(Decimal::parse("-0.0").unwrap().to_string(), "0");
+
+        // Digits beyond a float's precision are kept
+        assert_eq!(Decimal::parse("12345678901234567890.123456789").unwrap().to_string(), "12345678901234567890.123456789");
+
+        assert_eq!(Decimal::parse("1.2.3"), None);
+        assert_eq!(Decimal::parse("."), None);
+        assert_eq!(Decimal::parse("1e"), None);
+
+        // Numbers no column can hold are rejected before their digits are written out
+        assert_eq!(Decimal::parse("1e2000000000"), None);
+        assert_eq!(Decimal::parse("1e-2000000000"), None);
+        assert_eq!(Decimal::parse(&"9".repeat(66)), None);
+        assert_eq!(Decimal::parse("1e64").unwrap().integer_digits(), 65);
+    }
+}
@@ -0,0 +1,394 @@
+use std::fmt::{Display, Formatter};
+use serde::{Serialize,Deserialize};
+use crate::db::Database;
+use crate::error::RustractError;
+use crate::field::FieldDesign;
+use crate::table::TableDesign;
+use crate::types::DataType;
+
+/// Describes the kind of change made to a table or field between two database designs.
+#[derive(Deserialize, Serialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    TableAdded,
+    TableRemoved,
+    FieldAdded,
+    FieldRemoved,
+    /// The field's type changed, including whether it holds an array.
+    Type,
+    /// The field became required or optional.
+    Nullability,
+    /// The most characters or bytes the field allows changed.
+    Length,
+    /// Values were added to or removed from the field's enum or set.
+    Enum
+}
+
+impl Display for ChangeKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", match self {
+            ChangeKind::TableAdded => "table added",
+            ChangeKind::TableRemoved => "table removed",
+            ChangeKind::FieldAdded => "field added",
+            ChangeKind::FieldRemoved => "field removed",
+            ChangeKind::Type => "type",
+            ChangeKind::Nullability => "nullability",
+            ChangeKind::Length => "length",
+            ChangeKind::Enum => "enum"
+        })
+    }
+}
+
+/// Describes a single change between two database designs.
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
+pub struct Change {
+    pub table: String,
+    #[serde(skip_serializing_if="Option::is_none")]
+    pub field: Option<String>,
+    pub kind: ChangeKind,
+    #[serde(skip_serializing_if="Option::is_none")]
+    pub before: Option<String>,
+    #[serde(skip_serializing_if="Option::is_none")]
+    pub after: Option<String>,
+    /// Whether the change can break clients or data written for the old design,
+    /// such as by removing a field or narrowing its length.
+    pub breaking: bool,
+    pub message: String
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+/// Collects every change from one database design to another.
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone, Default)]
+pub struct SchemaDiff {
+    pub changes: Vec<Change>
+}
+
+impl Display for SchemaDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty() {
+            return write!(f, "no changes");
+        }
+        write!(f, "{} change(s), {} breaking", self.len(), self.breaking().len())?;
+        for change in &self.changes {
+            let marker = if change.breaking { "!" } else { " " };
+            write!(f, "\n{} {}", marker, change)?;
+        }
+        Ok(())
+    }
+}
+
+impl SchemaDiff {
+    /// Finds the changes made from the old design to the new one.
+    ///
+    /// Tables and fields are matched by title, so a renamed one is reported as removed and added.
+    pub fn between(old: &Database, new: &Database) -> Self {
+        let mut diff = SchemaDiff::default();
+        for (title, table) in &old.tables {
+            match new.table(title) {
+                Some(new_table) => diff.tables(table, new_table),
+                None => diff.add(title, None, ChangeKind::TableRemoved, None, None, true,
+                    format!("removed table {}", title)),
+            }
+        }
+        for title in new.tables.keys().filter(|title| old.table(title).is_none()) {
+            diff.add(title, None, ChangeKind::TableAdded, None, None, false,
+                format!("added table {}", title));
+        }
+        diff
+    }
+
+    /// Returns true if the designs are the same.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Returns the number of changes found.
+    pub fn len(&self) -> usize {
+        self.changes.len()
+    }
+
+    /// Returns true if any change is breaking.
+    pub fn is_breaking(&self) -> bool {
+        self.changes.iter().any(|change| change.breaking)
+    }
+
+    /// Gets the breaking changes.
+    pub fn breaking(&self) -> Vec<&Change> {
+        self.changes.iter().filter(|change| change.breaking).collect()
+    }
+
+    /// Gets the changes made to the specified table and its fields.
+    pub fn table(&self, title: &str) -> Vec<&Change> {
+        self.changes.iter().filter(|change| change.table == title).collect()
+    }
+
+    /// Renders the changes as JSON.
+    pub fn to_json(&self) -> Result<String, RustractError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Adds a change to this diff.
+    #[allow(clippy::too_many_arguments)]
+    fn add(
+        &mut self,
+        table: &str,
+        field: Option<&str>,
+        kind: ChangeKind,
+        before: Option<String>,
+        after: Option<String>,
+        breaking: bool,
+        message: String
+    ) {
+        self.changes.push(Change {
+            table: table.to_string(),
+            field: field.map(str::to_string),
+            kind,
+            before,
+            after,
+            breaking,
+            message
+        });
+    }
+
+    /// Finds the changes made to the fields of a table.
+    fn tables(&mut self, old: &TableDesign, new: &TableDesign) {
+        let table = &old.table_design_title;
+        for (title, field) in &old.fields {
+            match new.field(title) {
+                Some(new_field) => self.fields(table, field, new_field),
+                None => self.add(table, Some(title), ChangeKind::FieldRemoved, None, None, true,
+                    format!("removed field {}.{}", table, title)),
+            }
+        }
+        for (title, field) in new.fields.iter().filter(|(title, _)| old.field(title).is_none()) {
+            // Inserts written for the old design leave the field out, which fails if the database cannot fill it
+            let breaking = field.required && !field.generated && !field.has_default();
+            self.add(table, Some(title), ChangeKind::FieldAdded, None, None, breaking,
+                format!("added {} field {}.{}", if field.required { "required" } else { "optional" }, table, title));
+        }
+    }
+
+    /// Finds the changes made to a field.
+    fn fields(&mut self, table: &str, old: &FieldDesign, new: &FieldDesign) {
+        let title = old.field_design_title.as_str();
+        let name = format!("{}.{}", table, title);
+
+        if old.datatype != new.datatype || old.array != new.array {
+            let (before, after) = (type_name(old), type_name(new));
+            let breaking = old.array != new.array || !widens(&old.datatype, &new.datatype);
+            self.add(table, Some(title), ChangeKind::Type, Some(before.clone()), Some(after.clone()), breaking,
+                format!("changed type of {} from {} to {}", name, before, after));
+        } else {
+            let limits = [
+                ("characters", old.characters, new.characters),
+                ("bytes", old.bytes, new.bytes),
+                ("decimals", old.decimals, new.decimals)
+            ];
+            for (unit, before, after) in limits {
+                if before == after {
+                    continue;
+                }
+                // A missing limit allows any length
+                let mut narrowed = match (before, after) {
+                    (Some(before), Some(after)) => after < before,
+                    (_, after) => after.is_some(),
+                };
+                // Adding decimals without adding digits leaves fewer digits before the decimal point
+                if let (Some(old_digits), Some(old_decimals), Some(new_digits), Some(new_decimals)) =
+                    (old.characters, old.decimals, new.characters, new.decimals) {
+                    narrowed |= unit == "decimals" && new_digits - new_decimals < old_digits - old_decimals;
+                }
+                let (before, after) = (limit(before), limit(after));
+                self.add(table, Some(title), ChangeKind::Length, Some(before.clone()), Some(after.clone()), narrowed,
+                    format!("{} {} of {} from {} to {}", if narrowed { "narrowed" } else { "widened" }, unit, name, before, after));
+            }
+        }
+
+        if old.required != new.required {
+            let nullability = |required: bool| if required { "required" } else { "optional" }.to_string();
+            self.add(table, Some(title), ChangeKind::Nullability, Some(nullability(old.required)), Some(nullability(new.required)),
+                new.required,
+                format!("made {} {}", name, nullability(new.required)));
+        }
+
+        let old_values = values(old);
+        let new_values = values(new);
+        let removed: Vec<&String> = old_values.iter().filter(|value| !new_values.contains(value)).collect();
+        let added: Vec<&String> = new_values.iter().filter(|value| !old_values.contains(value)).collect();
+        if !removed.is_empty() || !added.is_empty() {
+            let mut changes = Vec::new();
+            if !added.is_empty() {
+                changes.push(format!("added {}", join(&added)));
+            }
+            if !removed.is_empty() {
+                changes.push(format!("removed {}", join(&removed)));
+            }
+            self.add(table, Some(title), ChangeKind::Enum, Some(join(&old_values)), Some(join(&new_values)),
+                !removed.is_empty(),
+                format!("{} values of {}", changes.join(" and "), name));
+        }
+    }
+}
+
+/// Gets the name of a field's type, marking array fields.
+fn type_name(field: &FieldDesign) -> String {
+    if field.array {
+        format!("{}[]", field.datatype)
+    } else {
+        field.datatype.to_string()
+    }
+}
+
+/// Gets the text of a length limit.
+fn limit(limit: Option<isize>) -> String {
+    limit.map_or_else(|| "unlimited".to_string(), |limit| limit.to_string())
+}
+
+/// Gets the values of a field's enum or set, in order.
+fn values(field: &FieldDesign) -> Vec<String> {
+    let mut values = field.enum_set.clone().unwrap_or_default();
+    values.extend(field.set.iter().flatten().cloned());
+    values
+}
+
+/// Joins values into a list for a message.
+fn join<T: AsRef<str>>(values: &[T]) -> String {
+    values.iter().map(AsRef::as_ref).collect::<Vec<&str>>().join(", ")
+}
+
+/// Returns true if every value of the old type can be stored in the new type.
+fn widens(old: &DataType, new: &DataType) -> bool {
+    let integer = |datatype: &DataType| match datatype {
+        DataType::Byte => Some((false, 8)),
+        DataType::Signed16 => Some((true, 16)),
+        DataType::Signed32 => Some((true, 32)),
+        DataType::Signed64 => Some((true, 64)),
+        DataType::Unsigned16 => Some((false, 16)),
+        DataType::Unsigned32 => Some((false, 32)),
+        DataType::Unsigned64 => Some((false, 64)),
+        _ => None,
+    };
+    match (old, new) {
+        (DataType::Float32, DataType::Float64) => true,
+        (old, new) => match (integer(old), integer(new)) {
+            (Some((old_signed, old_bits)), Some((new_signed, new_bits))) => match (old_signed, new_signed) {
+                (true, false) => false,
+                (false, true) => new_bits > old_bits,
+                _ => new_bits >= old_bits,
+            },
+            _ => false,
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const OLD: &str = "CREATE TABLE `user` (
+        `id` INT UNSIGNED NOT NULL AUTO_INCREMENT,
+        `name` VARCHAR(45) NOT NULL,
+        `bio` VARCHAR(280) NULL,
+        `email` VARCHAR(45) NOT NULL,
+        `type` ENUM('Admin','Mod','Basic') NOT NULL,
+        `score` SMALLINT NOT NULL,
+        PRIMARY KEY (`id`)
+    );
+    CREATE TABLE `session` (`id` INT NOT NULL, PRIMARY KEY (`id`));";
+
+    const NEW: &str = "CREATE TABLE `user` (
+        `id` BIGINT UNSIGNED NOT NULL AUTO_INCREMENT,
+        `name` VARCHAR(30) NOT NULL,
+        `bio` VARCHAR(500) NOT NULL,
+        `type` ENUM('Admin','Basic','Guest') NOT NULL,
+        `score` VARCHAR(10) NOT NULL,
+        `nickname` VARCHAR(20) NULL,
+        `joined` DATE NOT NULL,
+        PRIMARY KEY (`id`)
+    );
+    CREATE TABLE `post` (`id` INT NOT NULL, PRIMARY KEY (`id`));";
+
+    #[test]
+    fn diff_test() {
+        let old = Database::from_sql(OLD).unwrap();
+        let new = Database::from_sql(NEW).unwrap();
+        let diff = old.diff(&new);
+        let change = |field: &str, kind: ChangeKind| diff.changes.iter()
+            .find(|change| change.field.as_deref() == Some(field) && change.kind == kind)
+            .unwrap_or_else(|| panic!("missing {} change of {}", kind, field));
+
+        assert!(diff.table("session").iter().any(|change| change.kind == ChangeKind::TableRemoved && change.breaking));
+        assert!(diff.table("post").iter().any(|change| change.kind == ChangeKind::TableAdded && !change.breaking));
+
+        assert!(!change("id", ChangeKind::Type).breaking);
+        assert!(change("score", ChangeKind::Type).breaking);
+        let name = change("name", ChangeKind::Length);
+        assert!(name.breaking);
+        assert_eq!((name.before.as_deref(), name.after.as_deref()), (Some("45"), Some("30")));
+        assert!(!change("bio", ChangeKind::Length).breaking);
+        assert!(change("bio", ChangeKind::Nullability).breaking);
+        assert!(change("email", ChangeKind::FieldRemoved).breaking);
+        assert!(!change("nickname", ChangeKind::FieldAdded).breaking);
+        assert!(change("joined", ChangeKind::FieldAdded).breaking);
+
+        let values = change("type", ChangeKind::Enum);
+        assert!(values.breaking);
+        assert_eq!(values.message, "added Guest and removed Mod values of user.type");
+
+        assert!(diff.is_breaking());
+        assert!(old.diff(&old).is_empty());
+        assert_eq!(old.diff(&old).to_string(), "no changes");
+    }
+
+    #[test]
+    fn render_test() {
+        let old = Database::from_sql("CREATE TABLE `user` (`name` VARCHAR(20) NULL, `bio` VARCHAR(280) NULL);").unwrap();
+        let new = Database::from_sql("CREATE TABLE `user` (`name` VARCHAR(45) NULL);").unwrap();
+        let diff = old.diff(&new);
+        assert_eq!(
+            diff.to_string(),
+            "2 change(s), 1 breaking\n! removed field user.bio\n  widened characters of user.name from 20 to 45"
+        );
+
+        let json: serde_json::Value = serde_json::from_str(&diff.to_json().unwrap()).unwrap();
+        assert_eq!(json["changes"][1]["kind"], "length");
+        assert_eq!(json["changes"][1]["breaking"], false);
+        assert_eq!(serde_json::from_value::<SchemaDiff>(json).unwrap(), diff);
+    }
+
+    #[test]
+    fn precision_test() {
+        let old = Database::from_sql("CREATE TABLE `item` (`price` DECIMAL(10,2), `cost` DECIMAL(10,2), `weight` FLOAT(7,4));").unwrap();
+        let new = Database::from_sql("CREATE TABLE `item` (`price` DECIMAL(10,4), `cost` DECIMAL(12,4), `weight` FLOAT(7,2));").unwrap();
+        let diff = old.diff(&new);
+        let change = |field: &str| diff.changes.iter()
+            .find(|change| change.field.as_deref() == Some(field) && change.message.contains("decimals"))
+            .unwrap_or_else(|| panic!("missing decimals change of {}", field));
+
+        // Moving digits to the scale leaves fewer for the integer part, while adding digits with them does not
+        let price = change("price");
+        assert!(price.breaking);
+        assert_eq!((price.before.as_deref(), price.after.as_deref()), (Some("2"), Some("4")));
+        assert!(!change("cost").breaking);
+        assert!(change("weight").breaking);
+    }
+
+    #[test]
+    fn widen_test() {
+        let old = Database::from_sql("CREATE TABLE `item` (`count` TINYINT UNSIGNED, `stock` TINYINT UNSIGNED, `rank` SMALLINT UNSIGNED);").unwrap();
+        let new = Database::from_sql("CREATE TABLE `item` (`count` SMALLINT UNSIGNED, `stock` SMALLINT, `rank` TINYINT UNSIGNED);").unwrap();
+        let diff = old.diff(&new);
+        let breaking = |field: &str| diff.changes.iter()
+            .find(|change| change.field.as_deref() == Some(field) && change.kind == ChangeKind::Type)
+            .unwrap_or_else(|| panic!("missing type change of {}", field))
+            .breaking;
+        assert!(!breaking("count"));
+        assert!(!breaking("stock"));
+        assert!(breaking("rank"));
+    }
+}
@@ -1,5 +1,7 @@
 use std::{fmt::{Display, Formatter, Result}, num::ParseIntError};
 
+use crate::report::{FieldFailure, ValidationReport};
+
 #[derive(Debug)]
 pub enum RustractError {
     DB(GenericError),
@@ -7,6 +9,8 @@ pub enum RustractError {
     Field(GenericError),
     Filesystem(GenericError),
     Generic(GenericError),
+    Validation(ValidationReport),
+    Syntax(SyntaxError),
     ParseInt(ParseIntError),
     IO(std::io::Error),
     JSON(serde_json::Error),
@@ -21,6 +25,8 @@ impl RustractError {
             RustractError::Field(e) => e.message.clone(),
             RustractError::Filesystem(e) => e.message.clone(),
             RustractError::Generic(e) => e.message.clone(),
+            RustractError::Validation(e) => e.to_string(),
+            RustractError::Syntax(e) => e.to_string(),
             RustractError::ParseInt(e) => e.to_string(),
             RustractError::IO(e) => e.to_string(),
             RustractError::JSON(e) => e.to_string(),
@@ -66,6 +72,16 @@ impl From<regex::Error> for RustractError {
     }
 }
 
+/// Allows SQLite errors to be converted into RustractError's.
+#[cfg(feature = "sqlite")]
+impl From<rusqlite::Error> for RustractError {
+    fn from(e: rusqlite::Error) -> Self {
+        RustractError::DB(GenericError {
+            message: e.to_string(),
+        })
+    }
+}
+
 /// Allows GenericError's to be converted into RustractError's.
 impl From<GenericError> for RustractError {
     fn from(e: GenericError) -> Self {
@@ -73,6 +89,22 @@ impl From<GenericError> for RustractError {
     }
 }
 
+/// Allows field failures to be converted into RustractError's.
+impl From<FieldFailure> for RustractError {
+    fn from(e: FieldFailure) -> Self {
+        RustractError::Field(GenericError {
+            message: e.message,
+        })
+    }
+}
+
+/// Allows validation reports to be converted into RustractError's.
+impl From<ValidationReport> for RustractError {
+    fn from(e: ValidationReport) -> Self {
+        RustractError::Validation(e)
+    }
+}
+
 /// A generic error for removing context from other errors.
 /// 
 /// Context removal is useful for certain lifetime restrictions.
@@ -81,6 +113,23 @@ pub struct GenericError {
     pub message: String,
 }
 
+/// An error found while reading SQL, along with where it was found.
+#[derive(Debug, Clone)]
+pub struct SyntaxError {
+    pub message: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Adds the Error trait to SyntaxError.
+impl std::error::Error for SyntaxError {}
+
+impl Display for SyntaxError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "{} (line {}, column {})", self.message, self.line, self.column)
+    }
+}
+
 /// Allows parse int errors to be converted into GenericError's.
 impl From<std::num::ParseIntError> for GenericError {
     fn from(e: std::num::ParseIntError) -> Self {
@@ -1,10 +1,15 @@
-use std::collections::HashSet;
 use std::fmt::{Display, Formatter};
 use regex::Regex;
 use serde_json::{Map, Value};
 use serde::{Serialize,Deserialize};
 use crate::error::{RustractError, GenericError};
-use crate::types::{DataType, DataTypeValue, HasBytes, HasLength, capitalize};
+use crate::report::{FieldFailure, Rule};
+use crate::sql::{Check, Scalar};
+use crate::decimal::Decimal;
+use crate::format::Format;
+use crate::charset::Charset;
+use crate::temporal::{Date, DateTime, Time};
+use crate::types::{DataType, DataTypeValue, Dialect, HasLength, capitalize};
 
 /// Describes a database table field's design.
 #[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
@@ -23,13 +28,90 @@ pub struct FieldDesign {
     pub unique: bool,
     pub required: bool,
     #[serde(skip_serializing_if="Option::is_none")]
-    pub foreign: Option<String>,
+    pub foreign: Option<ForeignKey>,
     pub increment: bool,
     pub generated: bool,
     #[serde(skip_serializing_if="Option::is_none")]
     pub enum_set: Option<Vec<String>>,
+    /// The members of a set, in the order they were declared.
     #[serde(skip_serializing_if="Option::is_none")]
-    pub set: Option<HashSet<String>>
+    pub set: Option<Vec<String>>,
+    /// Whether the field holds an array of values (such as a PostgreSQL `text[]` column).
+    #[serde(default)]
+    pub array: bool,
+    /// Whether the field is padded to a fixed length, as in a `CHAR(n)` or `BINARY(n)` column.
+    #[serde(default)]
+    pub fixed: bool,
+    /// The value the database gives this field when it is left out of an insert.
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    pub default: Option<DefaultValue>,
+    /// The conditions of the `CHECK` constraints that only refer to this field, written in standard SQL.
+    #[serde(default, skip_serializing_if="Vec::is_empty")]
+    pub checks: Vec<String>,
+    /// The lowest value of a number, or the fewest characters (or bytes) of a string.
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    pub min: Option<f64>,
+    /// The highest value of a number, or the most characters (or bytes) of a string.
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    pub max: Option<f64>,
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    pub format: Option<Format>,
+    /// Whether the field is left out of the exported TypeScript types, such as for a password hash.
+    #[serde(default)]
+    pub hidden: bool,
+    /// The name of the field's database column, if the field was given a different title.
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    pub column: Option<String>,
+    /// The character set the field's text is stored in, which its byte limit is measured in (`utf8mb4` if not set).
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    pub charset: Option<Charset>,
+    /// Whether enum and set labels must be given in the same case, as in a column with a binary collation.
+    #[serde(default)]
+    pub case_sensitive: bool,
+    /// The dialect of the schema the field was read from, which its checks are evaluated in.
+    #[serde(default)]
+    pub dialect: Dialect
+}
+
+/// A field's default value, read from its column's `DEFAULT` clause.
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum DefaultValue {
+    Null,
+    /// The time the row is inserted, such as `CURRENT_TIMESTAMP`.
+    CurrentTimestamp,
+    /// A literal value, checked against the field's design.
+    Value(DataTypeValue),
+    /// An expression the database evaluates, written in SQL.
+    Expression(String)
+}
+
+/// Describes the field of another table that a foreign key field refers to.
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
+pub struct ForeignKey {
+    pub table: String,
+    pub field: String,
+    #[serde(skip_serializing_if="Option::is_none")]
+    pub on_delete: Option<ReferentialAction>,
+    #[serde(skip_serializing_if="Option::is_none")]
+    pub on_update: Option<ReferentialAction>
+}
+
+impl Display for ForeignKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.table, self.field)
+    }
+}
+
+/// The action the database takes on a foreign key when its referenced row is deleted or updated.
+#[derive(Deserialize, Serialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum ReferentialAction {
+    Restrict,
+    Cascade,
+    SetNull,
+    SetDefault,
+    NoAction
 }
 
 impl Display for FieldDesign {
@@ -55,58 +137,123 @@ impl FieldDesign {
             increment: false,
             generated: false,
             enum_set: None,
-            set: None
+            set: None,
+            array: false,
+            fixed: false,
+            default: None,
+            checks: Vec::new(),
+            min: None,
+            max: None,
+            format: None,
+            hidden: false,
+            column: None,
+            charset: None,
+            case_sensitive: false,
+            dialect: Dialect::MySql
         }
     }
 
     /// Tests the provided JSON value against this field's design and returns the data if valid.
     pub fn extract(&self, json: &Value) -> Result<DataTypeValue, RustractError> {
+        Ok(self.validate(json)?)
+    }
+
+    /// Tests the provided JSON value against this field's design, describing the failed rule if invalid.
+    pub fn validate(&self, json: &Value) -> Result<DataTypeValue, FieldFailure> {
+        // Null is kept apart from a missing field, since it clears the column's value
+        if json.is_null() {
+            if self.required {
+                return Err(self.failure(
+                    Rule::Required,
+                    None,
+                    Some(json.to_string()),
+                    format!("field {} may not be null", self.field_design_title)
+                ));
+            }
+            return Ok(DataTypeValue::Null);
+        }
+        let value = if self.array {
+            // Each element of an array field is tested against the rest of the design
+            let mut values = Vec::new();
+            for element in self.test_type(json.as_array(), json)? {
+                values.push(self.validate_element(element)?);
+            }
+            DataTypeValue::Array(values)
+        } else {
+            self.validate_element(json)?
+        };
+        self.test_checks(&value)?;
+        Ok(value)
+    }
+
+    /// Tests a single JSON value against this field's type, limits and format.
+    fn validate_element(&self, json: &Value) -> Result<DataTypeValue, FieldFailure> {
+        let value = self.validate_value(json)?;
+        self.test_min_max(&value)?;
+        if let (Some(format), DataTypeValue::String(text)) = (self.format, &value) {
+            if !format.is_match(text) {
+                return Err(self.failure(
+                    Rule::Format,
+                    Some(format.to_string()),
+                    Some(text.clone()),
+                    format!("field {} is not a valid {} (value: {})", self.field_design_title, format, text)
+                ));
+            }
+        }
+        Ok(value)
+    }
+
+    /// Tests a single JSON value against this field's type and limits.
+    fn validate_value(&self, json: &Value) -> Result<DataTypeValue, FieldFailure> {
         // This match results in duplicated code, but is needed due to limitations of serde_json
         match self.datatype {
             DataType::String => {
-                let json_string = String::from(self.test_type(json.as_str())?);
+                let json_string = String::from(self.test_type(json.as_str(), json)?);
                 self.test_length::<String>(&json_string)?;
-                self.test_byte_length::<String>(&json_string)?;
+                self.test_byte_length(&json_string)?;
                 self.test_regex(&json_string)?;
                 Ok(DataTypeValue::String(json_string))
             },
             DataType::ByteString => {
-                let json_array = self.test_type(json.as_array())?;
+                let json_array = self.test_type(json.as_array(), json)?;
                 let mut byte_string = vec![];
                 for value in json_array.iter() {
-                    byte_string.push(self.downsize::<u8, u64>(self.test_type(value.as_u64())?)?);
+                    byte_string.push(self.downsize::<u8, u64>(self.test_type(value.as_u64(), value)?)?);
                 }
                 if let Some(bytes) = self.bytes {
                     if byte_string.len() > bytes as usize {
-                        return Err(RustractError::Field(GenericError {
-                            message: format!(
+                        return Err(self.failure(
+                            Rule::Bytes,
+                            Some(bytes.to_string()),
+                            Some(byte_string.len().to_string()),
+                            format!(
                                 "bytestring {} is {} bytes long, but max size is {} bytes",
                                 self.field_design_title,
                                 byte_string.len(),
                                 bytes
-                            ),
-                        }));
+                            )
+                        ));
                     }
                 }
                 Ok(DataTypeValue::ByteString(byte_string))
             },
             DataType::Json => {
-                let json_object: Map<String, Value> = self.test_type(json.as_object())?.clone();
+                let json_object: Map<String, Value> = self.test_type(json.as_object(), json)?.clone();
                 Ok(DataTypeValue::Json(json_object))
             },
             DataType::Signed64 => {
-                let json_int = self.test_type(json.as_i64())?;
+                let json_int = self.test_signed_range(self.test_type(json.as_i64(), json)?)?;
                 self.test_length::<i64>(&json_int)?;
                 Ok(DataTypeValue::Signed64(json_int))
             },
             DataType::Unsigned64 => {
-                let json_int = self.test_type(json.as_u64())?;
+                let json_int = self.test_unsigned_range(self.test_type(json.as_u64(), json)?)?;
                 self.test_length::<u64>(&json_int)?;
                 Ok(DataTypeValue::Unsigned64(json_int))
             },
             DataType::Signed32 => {
                 let json_int = self.downsize::<i32,i64>(
-                    self.test_type(json.as_i64())?
+                    self.test_signed_range(self.test_type(json.as_i64(), json)?)?
                 )?;
                 self.test_length::<i32>(
                     &json_int
@@ -115,7 +262,7 @@ impl FieldDesign {
             },
             DataType::Unsigned32 => {
                 let json_int = self.downsize::<u32,u64>(
-                    self.test_type(json.as_u64())?
+                    self.test_unsigned_range(self.test_type(json.as_u64(), json)?)?
                 )?;
                 self.test_length::<u32>(
                     &json_int
@@ -124,7 +271,7 @@ impl FieldDesign {
             },
             DataType::Signed16 => {
                 let json_int = self.downsize::<i16,i64>(
-                    self.test_type(json.as_i64())?
+                    self.test_signed_range(self.test_type(json.as_i64(), json)?)?
                 )?;
                 self.test_length::<i16>(
                     &json_int
@@ -133,7 +280,7 @@ impl FieldDesign {
             },
             DataType::Unsigned16 => {
                 let json_int = self.downsize::<u16,u64>(
-                    self.test_type(json.as_u64())?
+                    self.test_unsigned_range(self.test_type(json.as_u64(), json)?)?
                 )?;
                 self.test_length::<u16>(
                     &json_int
@@ -141,39 +288,122 @@ impl FieldDesign {
                 Ok(DataTypeValue::Unsigned16(json_int))
             },
             DataType::Float64 => {
-                let json_float = self.test_type(json.as_f64())?;
-                self.test_length::<f64>(&json_float)?;
+                let json_float = self.test_type(json.as_f64(), json)?;
+                self.test_precision::<f64>(&json_float)?;
                 Ok(DataTypeValue::Float64(json_float))
             },
             DataType::Float32 => {
-                let json_float = self.test_type(json.as_f64())?;
-                self.test_length::<f32>(
+                let json_float = self.test_type(json.as_f64(), json)?;
+                if json_float.abs() > f64::from(f32::MAX) {
+                    return Err(self.failure(
+                        Rule::Range,
+                        Some(f32::MAX.to_string()),
+                        Some(json_float.to_string()),
+                        format!(
+                            "field {} is outside of the range of a 32-bit float (number: {})",
+                            self.field_design_title,
+                            json_float
+                        )
+                    ));
+                }
+                self.test_precision::<f32>(
                     &(json_float as f32)
                 )?;
                 Ok(DataTypeValue::Float32(json_float as f32))
             },
+            DataType::Decimal => {
+                // Numbers are read from their shortest exact form, strings are read as written
+                let text = match json {
+                    Value::Number(number) => {
+                        // JSON numbers are read as floats, which only hold 15 significant digits exactly
+                        let text = number.to_string();
+                        if number.is_f64() && Decimal::parse(&text).is_some_and(|decimal| decimal.significant_digits() > 15) {
+                            return Err(self.failure(
+                                Rule::Type,
+                                Some(self.datatype.to_string()),
+                                Some(text),
+                                format!(
+                                    "field {} has more digits than a JSON number holds exactly, so it must be sent as a string",
+                                    self.field_design_title
+                                )
+                            ));
+                        }
+                        Some(text)
+                    },
+                    Value::String(string) => Some(string.clone()),
+                    _ => None,
+                };
+                let decimal = self.test_type(text.as_deref().and_then(Decimal::parse), json)?;
+                self.test_scale(&decimal)?;
+                Ok(DataTypeValue::Decimal(decimal))
+            },
+            DataType::Date => {
+                let date = self.test_type(json.as_str().and_then(Date::parse), json)?;
+                Ok(DataTypeValue::Date(self.test_bounds(date, Date::MIN, Date::MAX)?))
+            },
+            DataType::Time => {
+                let time = self.test_type(json.as_str().and_then(Time::parse), json)?;
+                if !time.in_range() {
+                    return Err(self.failure(
+                        Rule::Range,
+                        Some("-838:59:59..=838:59:59".to_string()),
+                        Some(time.to_string()),
+                        format!(
+                            "field {} is outside of the time range -838:59:59..=838:59:59 (time: {})",
+                            self.field_design_title,
+                            time
+                        )
+                    ));
+                }
+                Ok(DataTypeValue::Time(time))
+            },
+            DataType::DateTime => {
+                let date_time = self.test_type(json.as_str().and_then(DateTime::parse), json)?;
+                Ok(DataTypeValue::DateTime(self.test_bounds(date_time, DateTime::MIN, DateTime::MAX)?))
+            },
+            DataType::Timestamp => {
+                // Timestamps are assumed to be in UTC
+                let date_time = self.test_type(json.as_str().and_then(DateTime::parse), json)?;
+                Ok(DataTypeValue::Timestamp(self.test_bounds(
+                    date_time,
+                    DateTime::TIMESTAMP_MIN,
+                    DateTime::TIMESTAMP_MAX
+                )?))
+            },
+            DataType::Year => {
+                // MySQL also allows a year of zero
+                let year = self.downsize::<u16, u64>(self.test_type(json.as_u64(), json)?)?;
+                if year != 0 {
+                    self.test_bounds(year, 1901, 2155)?;
+                }
+                Ok(DataTypeValue::Year(year))
+            },
             DataType::Boolean => {
-                let json_bool = self.test_type(json.as_bool())?;
+                let json_bool = self.test_type(json.as_bool(), json)?;
                 Ok(DataTypeValue::Boolean(json_bool))
             },
             DataType::Bit => {
-                let json_bit = self.test_type(json.as_u64())?;
-                let size = crate::types::digits(&json_bit);
-                if size > 1 {
-                    return Err(RustractError::Field(GenericError {
-                        message: format!(
-                            "expected {} to be a bit, but size was {}, number: \"{}\"",
+                // The characters of a bit field are the number of bits it holds (1 by default)
+                let json_bit = self.test_type(json.as_u64(), json)?;
+                let width = self.characters.unwrap_or(1).clamp(1, 64) as u32;
+                if width < 64 && json_bit >> width > 0 {
+                    return Err(self.failure(
+                        Rule::Range,
+                        Some(width.to_string()),
+                        Some((64 - json_bit.leading_zeros()).to_string()),
+                        format!(
+                            "expected {} to fit in {} bit(s), number: \"{}\"",
                             self.field_design_title,
-                            size,
+                            width,
                             json_bit
-                        ),
-                    }));
+                        )
+                    ));
                 }
-                Ok(DataTypeValue::Bit(self.downsize::<u8, u64>(json_bit)?))
+                Ok(DataTypeValue::Bit(json_bit))
             },
             DataType::Byte => {
                 let json_int = self.downsize::<u8,u64>(
-                    self.test_type(json.as_u64())?
+                    self.test_type(json.as_u64(), json)?
                 )?;
                 self.test_length::<u8>(
                     &json_int
@@ -181,50 +411,142 @@ impl FieldDesign {
                 Ok(DataTypeValue::Byte(json_int))
             },
             DataType::Enum => {
-                let json_enum = self.downsize::<u32, u64>(self.test_type(json.as_u64())?)?;
                 if let Some(list) = &self.enum_set {
-                    if (json_enum as usize) < list.len() {
-                        Ok(DataTypeValue::Enum(json_enum))
-                    } else {
-                        Err(RustractError::Field(GenericError {
-                            message: format!(
+                    // Enums are given by their label, or by their index in the list
+                    let index = match json.as_str() {
+                        Some(label) => match list.iter().position(|element| self.label_matches(element, label)) {
+                            Some(index) => index,
+                            None => return Err(self.failure(
+                                Rule::Enum,
+                                Some(list.join(", ")),
+                                Some(label.to_string()),
+                                format!(
+                                    "value {} is not a label of the enum {}",
+                                    label,
+                                    self.field_design_title
+                                )
+                            )),
+                        },
+                        None => self.downsize::<u32, u64>(self.test_type(json.as_u64(), json)?)? as usize,
+                    };
+                    match list.get(index) {
+                        Some(label) => Ok(DataTypeValue::Enum(index as u32, label.clone())),
+                        None => Err(self.failure(
+                            Rule::Enum,
+                            Some(format!("0..{}", list.len())),
+                            Some(index.to_string()),
+                            format!(
                                 "expected {} to be within the enum range {}..{}",
-                                json_enum,
+                                index,
                                 0,
                                 list.len()
                             )
-                        }))
+                        )),
                     }
                 } else {
-                    Err(RustractError::Field(GenericError {
-                        message: "internal error, enum field has no enum attached".to_string()
-                    }))
+                    Err(self.failure(
+                        Rule::Design,
+                        None,
+                        None,
+                        "internal error, enum field has no enum attached".to_string()
+                    ))
                 }
             },
             DataType::Set => {
-                let json_string = self.test_type(json.as_str())?.to_ascii_lowercase();
                 if let Some(set) = &self.set {
-                    if set.contains(&json_string) {
-                        Ok(DataTypeValue::Set(json_string))
-                    } else {
-                        Err(RustractError::Field(GenericError {
-                            message: format!(
-                                "value {} is not an element of this set",
-                                json_string
-                            )
-                        }))
+                    // Sets are given as an array of members, or as a comma separated string like MySQL writes them
+                    let given: Vec<&str> = match json.as_str() {
+                        Some(text) if text.trim().is_empty() => Vec::new(),
+                        Some(text) => text.split(',').map(str::trim).collect(),
+                        None => {
+                            let mut given = Vec::new();
+                            for element in self.test_type(json.as_array(), json)? {
+                                given.push(self.test_type(element.as_str(), element)?);
+                            }
+                            given
+                        },
+                    };
+
+                    // Members are matched like enum labels, and kept as they were declared
+                    let mut members: Vec<String> = Vec::new();
+                    for member in given {
+                        let label = match set.iter().find(|label| self.label_matches(label, member)) {
+                            Some(label) => label,
+                            None => return Err(self.failure(
+                                Rule::Set,
+                                Some(set.join(", ")),
+                                Some(member.to_string()),
+                                format!(
+                                    "value {} is not an element of this set",
+                                    member
+                                )
+                            )),
+                        };
+                        if members.contains(label) {
+                            return Err(self.failure(
+                                Rule::Set,
+                                Some(set.join(", ")),
+                                Some(member.to_string()),
+                                format!(
+                                    "value {} is given more than once in set {}",
+                                    member,
+                                    self.field_design_title
+                                )
+                            ));
+                        }
+                        members.push(label.clone());
                     }
+                    Ok(DataTypeValue::Set(members))
                 } else {
-                    Err(RustractError::Field(GenericError {
-                        message: "internal error, set field has no set attached".to_string()
-                    }))
+                    Err(self.failure(
+                        Rule::Design,
+                        None,
+                        None,
+                        "internal error, set field has no set attached".to_string()
+                    ))
                 }
             }
         }
     }
 
+    /// Returns true if the database gives this field a value when it is left out of an insert.
+    pub fn has_default(&self) -> bool {
+        self.default.as_ref().is_some_and(|default| *default != DefaultValue::Null)
+    }
+
+    /// Gets the value the database would give this field if it was left out, if it is known.
+    ///
+    /// `CURRENT_TIMESTAMP` defaults use the current UTC time, while other expressions are left to the database.
+    pub fn default_value(&self) -> Option<DataTypeValue> {
+        match self.default.as_ref()? {
+            DefaultValue::Value(value) => Some(value.clone()),
+            DefaultValue::CurrentTimestamp => {
+                let mut now = DateTime::now();
+                let scale = 10_u32.pow(6 - self.decimals.unwrap_or(0).clamp(0, 6) as u32);
+                now.microsecond -= now.microsecond % scale;
+                match self.datatype {
+                    DataType::Date => Some(DataTypeValue::Date(now.date)),
+                    DataType::Time => Some(DataTypeValue::Time(Time {
+                        negative: false,
+                        hours: u16::from(now.hour),
+                        minutes: now.minute,
+                        seconds: now.second,
+                        microseconds: now.microsecond
+                    })),
+                    DataType::DateTime => Some(DataTypeValue::DateTime(now)),
+                    DataType::Timestamp => Some(DataTypeValue::Timestamp(now)),
+                    DataType::Year => Some(DataTypeValue::Year(now.date.year)),
+                    _ => None,
+                }
+            },
+            DefaultValue::Null | DefaultValue::Expression(_) => None,
+        }
+    }
+
     /// Creates an export type for this field's data to match against.
     ///
+    /// Enums are exported as string enums, whose values are the labels the database stores.
+    /// Labels that are not valid TypeScript names (such as those with spaces) are quoted.
     /// This will currently fail if this field is not an enum.
     pub fn export_type(&self, table_name: &str) -> Result<String, RustractError> {
         let mut output: String = String::new();
@@ -237,9 +559,13 @@ impl FieldDesign {
         if let DataType::Enum = self.datatype {
             // Add each enum element to the new type
             if let Some(set) = &self.enum_set {
+                let identifier = Regex::new("^[A-Za-z_$][A-Za-z0-9_$]*$")?;
                 for (index, element) in set.iter().enumerate() {
+                    let label = serde_json::to_string(element)?;
                     output += "  ";
-                    output += element;
+                    output += if identifier.is_match(element) { element } else { &label };
+                    output += " = ";
+                    output += &label;
                     if index < set.len() - 1 {
                         output += ",";
                     }
@@ -260,89 +586,360 @@ impl FieldDesign {
         Ok(output)
     }
 
+    /// Returns true if the given label matches an enum or set label, ignoring case unless this field is case sensitive.
+    fn label_matches(&self, label: &str, given: &str) -> bool {
+        if self.case_sensitive {
+            label == given
+        } else {
+            label.eq_ignore_ascii_case(given)
+        }
+    }
+
+    /// Creates a failure describing the rule this field's value broke.
+    fn failure(&self, rule: Rule, limit: Option<String>, actual: Option<String>, message: String) -> FieldFailure {
+        FieldFailure {
+            field: self.field_design_title.clone(),
+            rule,
+            limit,
+            actual,
+            message
+        }
+    }
+
     /// Unwraps the Option-wrapped Serde value along with a relevant error message.
-    fn test_type<T>(&self, value: Option<T>) -> Result<T, RustractError> {
+    fn test_type<T>(&self, value: Option<T>, json: &Value) -> Result<T, FieldFailure> {
         match value {
             Some(val) => Ok(val),
-            None => Err(RustractError::Field(GenericError {
-                message: format!(
+            None => Err(self.failure(
+                Rule::Type,
+                Some(self.datatype.to_string()),
+                Some(json.to_string()),
+                format!(
                     "field {} is not of type {} (JSON cast failed)",
                     self.field_design_title,
                     self.datatype
-                ),
-            })),
+                )
+            )),
         }
     }
 
     /// Tests the length (digits or chars) of the given struct against this field's limit.
-    fn test_length<T>(&self, value: &T) -> Result<(), RustractError>
+    fn test_length<T>(&self, value: &T) -> Result<(), FieldFailure>
     where T: HasLength
     {
         if let Some(max) = self.characters {
-            match value.length() > max {
-                true => return Err(RustractError::Field(GenericError {
-                    message: format!(
+            if value.length() > max {
+                return Err(self.failure(
+                    Rule::Characters,
+                    Some(max.to_string()),
+                    Some(value.length().to_string()),
+                    format!(
                         "field {} is over the size limit of {} (size: {})",
                         self.field_design_title,
                         max,
                         value.length()
-                    ),
-                })),
-                false => return Ok(())
+                    )
+                ));
             }
         }
         Ok(())
     }
 
-    /// Tests the byte length of the given struct against this field's limit.
-    fn test_byte_length<T>(&self, value: &T) -> Result<(), RustractError>
-    where T: HasBytes
+    /// Tests the integer digits of a float against this field's precision (characters) and scale (decimals).
+    ///
+    /// Extra fractional digits are not tested, since the database rounds them.
+    fn test_precision<T>(&self, value: &T) -> Result<(), FieldFailure>
+    where T: HasLength
     {
-        if self.bytes.is_some() && value.byte_length() > self.bytes.unwrap() {
-            return Err(RustractError::Field(GenericError {
-                message: format!(
-                    "field {} is over the byte limit of {} (bytes: {}).",
+        if let Some(precision) = self.characters {
+            let max = precision - self.decimals.unwrap_or(0);
+            if value.length() > max {
+                return Err(self.failure(
+                    Rule::Characters,
+                    Some(max.to_string()),
+                    Some(value.length().to_string()),
+                    format!(
+                        "field {} has {} integer digits, but the limit is {}",
+                        self.field_design_title,
+                        value.length(),
+                        max
+                    )
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Tests the digits of a decimal against this field's precision (characters) and scale (decimals).
+    fn test_scale(&self, value: &Decimal) -> Result<(), FieldFailure> {
+        let scale = self.decimals.unwrap_or(0);
+        if self.decimals.is_some() && value.fraction_digits() as isize > scale {
+            return Err(self.failure(
+                Rule::Characters,
+                Some(scale.to_string()),
+                Some(value.fraction_digits().to_string()),
+                format!(
+                    "field {} has {} fractional digits, but the limit is {}",
+                    self.field_design_title,
+                    value.fraction_digits(),
+                    scale
+                )
+            ));
+        }
+        if let Some(precision) = self.characters {
+            if value.integer_digits() as isize > precision - scale {
+                return Err(self.failure(
+                    Rule::Characters,
+                    Some((precision - scale).to_string()),
+                    Some(value.integer_digits().to_string()),
+                    format!(
+                        "field {} has {} integer digits, but the limit is {}",
+                        self.field_design_title,
+                        value.integer_digits(),
+                        precision - scale
+                    )
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Tests that the value is within the inclusive bounds of its type.
+    fn test_bounds<T>(&self, value: T, min: T, max: T) -> Result<T, FieldFailure>
+    where T: PartialOrd + Display
+    {
+        if value < min || value > max {
+            return Err(self.failure(
+                Rule::Range,
+                Some(format!("{}..={}", min, max)),
+                Some(value.to_string()),
+                format!(
+                    "field {} is outside of the range {}..={} (value: {})",
                     self.field_design_title,
-                    self.bytes.unwrap(),
-                    value.byte_length()
-                ),
-            }))
+                    min,
+                    max,
+                    value
+                )
+            ));
+        }
+        Ok(value)
+    }
+
+    /// Tests a signed integer against the range of this field's byte size (such as a 3 byte mediumint).
+    fn test_signed_range(&self, value: i64) -> Result<i64, FieldFailure> {
+        if let Some(bytes @ 1..=7) = self.bytes {
+            let bits = bytes as u32 * 8;
+            let (min, max) = (-(1_i64 << (bits - 1)), (1_i64 << (bits - 1)) - 1);
+            if value < min || value > max {
+                return Err(self.range_failure(&format!("{}..={}", min, max), &value.to_string(), bytes));
+            }
+        }
+        Ok(value)
+    }
+
+    /// Tests an unsigned integer against the range of this field's byte size (such as a 3 byte mediumint).
+    fn test_unsigned_range(&self, value: u64) -> Result<u64, FieldFailure> {
+        if let Some(bytes @ 1..=7) = self.bytes {
+            let max = (1_u64 << (bytes as u32 * 8)) - 1;
+            if value > max {
+                return Err(self.range_failure(&format!("0..={}", max), &value.to_string(), bytes));
+            }
+        }
+        Ok(value)
+    }
+
+    /// Creates a failure for an integer outside of its byte size's range.
+    fn range_failure(&self, range: &str, value: &str, bytes: isize) -> FieldFailure {
+        self.failure(
+            Rule::Range,
+            Some(range.to_string()),
+            Some(value.to_string()),
+            format!(
+                "field {} is outside of the range {} for a {} byte integer (number: {})",
+                self.field_design_title,
+                range,
+                bytes,
+                value
+            )
+        )
+    }
+
+    /// Tests the byte length of the given text, measured in this field's character set, against this field's limit.
+    fn test_byte_length(&self, value: &str) -> Result<(), FieldFailure> {
+        let charset = self.charset.unwrap_or_default();
+        let bytes = charset.byte_length(value).map_err(|character| self.failure(
+            Rule::Charset,
+            Some(charset.to_string()),
+            Some(character.to_string()),
+            format!(
+                "field {} holds the character {}, which {} cannot store.",
+                self.field_design_title,
+                character,
+                charset
+            )
+        ))?;
+        if let Some(max) = self.bytes {
+            if bytes > max {
+                return Err(self.failure(
+                    Rule::Bytes,
+                    Some(max.to_string()),
+                    Some(bytes.to_string()),
+                    format!(
+                        "field {} is over the byte limit of {} (bytes: {}).",
+                        self.field_design_title,
+                        max,
+                        bytes
+                    )
+                ))
+            }
         }
         Ok(())
     }
 
     /// Attempts to downsize the given number into the specified size.
-    fn downsize<T, E>(&self, value: E) -> Result<T, RustractError>
-    where E: Copy + std::convert::TryInto<T>
+    fn downsize<T, E>(&self, value: E) -> Result<T, FieldFailure>
+    where E: Copy + Display + std::convert::TryInto<T>
     {
         match value.try_into() {
             Ok(val) => Ok(val),
-            Err(_) => Err(RustractError::Field(GenericError {
-                message: format!(
+            Err(_) => Err(self.failure(
+                Rule::Range,
+                Some(self.datatype.to_string()),
+                Some(value.to_string()),
+                format!(
                     "field {} is over the byte limit for type {}",
                     self.field_design_title,
                     self.datatype
-                ),
-            })),
+                )
+            )),
+        }
+    }
+
+    /// Gets the name of the field's database column.
+    pub fn column_name(&self) -> &str {
+        self.column.as_deref().unwrap_or(&self.field_design_title)
+    }
+
+    /// Tests the value against this field's minimum and maximum.
+    ///
+    /// Strings are measured in characters and byte strings in bytes, while numbers are compared by value.
+    fn test_min_max(&self, value: &DataTypeValue) -> Result<(), FieldFailure> {
+        if self.min.is_none() && self.max.is_none() {
+            return Ok(());
+        }
+        let (measure, rule, name) = match value {
+            DataTypeValue::String(text) => (text.chars().count() as f64, Rule::Characters, "length"),
+            DataTypeValue::ByteString(bytes) => (bytes.len() as f64, Rule::Bytes, "length"),
+            _ => match self.check_value(value) {
+                Scalar::Number(number) => (number, Rule::Range, "value"),
+                _ => return Ok(()),
+            },
+        };
+        let min = self.min.unwrap_or(f64::NEG_INFINITY);
+        let max = self.max.unwrap_or(f64::INFINITY);
+        if measure < min || measure > max {
+            let range = match (self.min, self.max) {
+                (Some(min), Some(max)) => format!("{}..={}", min, max),
+                (Some(min), None) => format!("{}..", min),
+                (_, max) => format!("..={}", max.unwrap_or_default()),
+            };
+            return Err(self.failure(
+                rule,
+                Some(range.clone()),
+                Some(measure.to_string()),
+                format!(
+                    "field {} has a {} of {}, but must be within {}",
+                    self.field_design_title,
+                    name,
+                    measure,
+                    range
+                )
+            ));
+        }
+        Ok(())
+    }
+
+    /// Tests the value against this field's `CHECK` constraints, which only fail if their condition is false.
+    fn test_checks(&self, value: &DataTypeValue) -> Result<(), FieldFailure> {
+        for text in &self.checks {
+            let check = match Check::parse(text, self.dialect) {
+                Ok(check) => check,
+                Err(e) => return Err(self.failure(
+                    Rule::Design,
+                    Some(text.clone()),
+                    None,
+                    format!("field {} has an invalid check: {}", self.field_design_title, e.message())
+                )),
+            };
+            let passed = check.passes(|name| match name.eq_ignore_ascii_case(self.column_name()) {
+                true => self.check_value(value),
+                false => Scalar::Null,
+            });
+            if !passed {
+                return Err(self.failure(
+                    Rule::Check,
+                    Some(text.clone()),
+                    Some(value.to_string()),
+                    format!("field {} failed the check {} (value: {})", self.field_design_title, text, value)
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Converts an extracted value of this field to the value a check compares.
+    ///
+    /// Enums are compared by their label, while values that checks cannot compare (such as JSON) are null.
+    pub(crate) fn check_value(&self, value: &DataTypeValue) -> Scalar {
+        match value {
+            DataTypeValue::String(text) => Scalar::Text(text.clone()),
+            DataTypeValue::Set(members) => Scalar::Text(members.join(",")),
+            DataTypeValue::Signed64(number) => Scalar::Number(*number as f64),
+            DataTypeValue::Unsigned64(number) | DataTypeValue::Bit(number) => Scalar::Number(*number as f64),
+            DataTypeValue::Signed32(number) => Scalar::Number(f64::from(*number)),
+            DataTypeValue::Unsigned32(number) => Scalar::Number(f64::from(*number)),
+            DataTypeValue::Signed16(number) => Scalar::Number(f64::from(*number)),
+            DataTypeValue::Unsigned16(number) | DataTypeValue::Year(number) => Scalar::Number(f64::from(*number)),
+            DataTypeValue::Byte(number) => Scalar::Number(f64::from(*number)),
+            DataTypeValue::Float64(number) => Scalar::Number(*number),
+            DataTypeValue::Float32(number) => Scalar::Number(f64::from(*number)),
+            DataTypeValue::Decimal(number) => Scalar::Number(number.to_f64()),
+            DataTypeValue::Boolean(value) => Scalar::Boolean(*value),
+            DataTypeValue::Date(_) | DataTypeValue::Time(_) | DataTypeValue::DateTime(_) | DataTypeValue::Timestamp(_) => {
+                Scalar::Text(value.to_string())
+            },
+            DataTypeValue::Enum(_, label) => Scalar::Text(label.clone()),
+            DataTypeValue::ByteString(_) | DataTypeValue::Json(_) | DataTypeValue::Array(_) | DataTypeValue::Null => Scalar::Null,
         }
     }
 
     /// Tests the given struct against this field's regex restrictions.
-    fn test_regex<T>(&self, value: &T) -> Result<(), RustractError>
+    fn test_regex<T>(&self, value: &T) -> Result<(), FieldFailure>
     where T: AsRef<str>
     {
         if let Some(val) = &self.regex {
             // TODO: Implement Serialize/Deserialize traits for Regex to remove runtime cost.
-            let regex = Regex::new(val)?;
+            let regex = match Regex::new(val) {
+                Ok(regex) => regex,
+                Err(e) => return Err(self.failure(
+                    Rule::Design,
+                    Some(val.clone()),
+                    None,
+                    format!("field {} has an invalid regex: {}", self.field_design_title, e)
+                )),
+            };
 
             if !regex.is_match(value.as_ref()) {
-                return Err(RustractError::Field(GenericError {
-                    message: format!(
+                return Err(self.failure(
+                    Rule::Regex,
+                    Some(val.clone()),
+                    Some(value.as_ref().to_string()),
+                    format!(
                         "field {} failed to match the regex restriction of {}",
                         self.field_design_title,
-                        regex.to_string()
-                    ),
-                }));
+                        regex
+                    )
+                ));
             }
         }
 
@@ -352,17 +949,32 @@ impl FieldDesign {
     /// Exports this field to a String containing TypeScript.
     pub fn export(&self, input: bool, override_name: Option<&str>) -> String {
         // Set enums or other types to be of the correct type
-        let mut name: &str = &self.datatype.typescript();
+        let mut name = self.datatype.typescript();
+        if let (DataType::Set, Some(set)) = (&self.datatype, &self.set) {
+            // Sets are arrays of a union of their members, in the order they were declared
+            let members: Vec<String> = set.iter().map(|member| Value::from(member.as_str()).to_string()).collect();
+            name = format!("({})[]", members.join(" | "));
+        }
+        if input && self.datatype == DataType::Decimal {
+            // Decimals are output as strings, but may be sent as numbers if a float holds them exactly
+            name = format!("(number | {})", name);
+        }
         if let Some(new_name) = override_name {
-            name = new_name;
+            name = new_name.to_string();
         }
 
         let mut output = String::new();
         output += "  ";
         output += &self.field_design_title;
-        output += if (input && self.generated) || !self.required { "?" } else { "" };
+        output += if (input && (self.generated || self.has_default())) || !self.required { "?" } else { "" };
         output += ": ";
-        output += name;
+        output += &name;
+        if self.array {
+            output += "[]";
+        }
+        if !self.required {
+            output += " | null";
+        }
         output += ",\n";
         output
     }
@@ -379,7 +991,6 @@ pub(crate) fn enum_name(table_name: &str, field_name: &str) -> Result<String, Ru
 
 #[cfg(test)]
 mod test {
-    use crate::types::IntoHashSet;
     use super::*;
 
     #[test]
@@ -399,7 +1010,19 @@ mod test {
             increment: false,
             generated: true,
             enum_set: None,
-            set: None
+            set: None,
+            array: false,
+            fixed: false,
+            default: None,
+            checks: Vec::new(),
+            min: None,
+            max: None,
+            format: None,
+            hidden: false,
+            column: None,
+            charset: None,
+            case_sensitive: false,
+            dialect: Dialect::MySql
         };
         assert_eq!(field.extract(json.get("int").unwrap()).unwrap(), DataTypeValue::Signed32(-1_i32));
     }
@@ -421,7 +1044,19 @@ mod test {
             increment: false,
             generated: true,
             enum_set: None,
-            set: None
+            set: None,
+            array: false,
+            fixed: false,
+            default: None,
+            checks: Vec::new(),
+            min: None,
+            max: None,
+            format: None,
+            hidden: false,
+            column: None,
+            charset: None,
+            case_sensitive: false,
+            dialect: Dialect::MySql
         };
         assert_eq!(field.extract(json.get("int64").unwrap()).unwrap(), DataTypeValue::Signed64(-4294967297_i64));
     }
@@ -443,9 +1078,42 @@ mod test {
             increment: false,
             generated: true,
             enum_set: Some(vec!["Zero".to_string(),"One".to_string(),"Two".to_string(),"Three".to_string(),"Four".to_string(),"Five".to_string(),"Six".to_string(),"Seven".to_string()]),
-            set: None
+            set: None,
+            array: false,
+            fixed: false,
+            default: None,
+            checks: Vec::new(),
+            min: None,
+            max: None,
+            format: None,
+            hidden: false,
+            column: None,
+            charset: None,
+            case_sensitive: false,
+            dialect: Dialect::MySql
         };
-        assert_eq!(field.extract(json.get("enum").unwrap()).unwrap(), DataTypeValue::Enum(7_u32));
+        assert_eq!(field.extract(json.get("enum").unwrap()).unwrap(), DataTypeValue::Enum(7, "Seven".to_string()));
+    }
+
+    #[test]
+    fn test_enum_label() {
+        let mut field = FieldDesign::new("type");
+        field.datatype = DataType::Enum;
+        field.enum_set = Some(vec!["Admin".to_string(), "Basic".to_string(), "Read only".to_string()]);
+        assert_eq!(field.extract(&serde_json::json!("Admin")).unwrap(), DataTypeValue::Enum(0, "Admin".to_string()));
+        assert_eq!(field.extract(&serde_json::json!("basic")).unwrap(), DataTypeValue::Enum(1, "Basic".to_string()));
+        assert_eq!(field.extract(&serde_json::json!(2)).unwrap(), DataTypeValue::Enum(2, "Read only".to_string()));
+        assert_eq!(field.validate(&serde_json::json!("Owner")).unwrap_err().rule, Rule::Enum);
+        assert_eq!(field.validate(&serde_json::json!(3)).unwrap_err().rule, Rule::Enum);
+
+        field.case_sensitive = true;
+        assert_eq!(field.validate(&serde_json::json!("basic")).unwrap_err().rule, Rule::Enum);
+
+        // Labels are exported as the values of a string enum
+        assert_eq!(
+            field.export_type("user").unwrap(),
+            "export enum UserTypeEnum {\n  Admin = \"Admin\",\n  Basic = \"Basic\",\n  \"Read only\" = \"Read only\"\n}\n"
+        );
     }
 
     #[test]
@@ -465,9 +1133,43 @@ mod test {
             increment: false,
             generated: true,
             enum_set: None,
-            set: Some(vec!["test".to_string(),"set".to_string()].into_set())
+            set: Some(vec!["test".to_string(),"set".to_string()]),
+            array: false,
+            fixed: false,
+            default: None,
+            checks: Vec::new(),
+            min: None,
+            max: None,
+            format: None,
+            hidden: false,
+            column: None,
+            charset: None,
+            case_sensitive: false,
+            dialect: Dialect::MySql
         };
-        assert_eq!(field.extract(json.get("set").unwrap()).unwrap(), DataTypeValue::Set("test".to_string()));
+        assert_eq!(field.extract(json.get("set").unwrap()).unwrap(), DataTypeValue::Set(vec!["test".to_string()]));
+    }
+
+    #[test]
+    fn test_set_members() {
+        let mut field = FieldDesign::new("days");
+        field.datatype = DataType::Set;
+        field.set = Some(vec!["Mon".to_string(), "Tue".to_string(), "Wed".to_string()]);
+        let days = DataTypeValue::Set(vec!["Tue".to_string(), "Mon".to_string()]);
+        assert_eq!(field.extract(&serde_json::json!(["tue", "Mon"])).unwrap(), days);
+        assert_eq!(field.extract(&serde_json::json!("tue, MON")).unwrap(), days);
+        assert_eq!(field.extract(&serde_json::json!([])).unwrap(), DataTypeValue::Set(Vec::new()));
+        assert_eq!(field.extract(&serde_json::json!("")).unwrap(), DataTypeValue::Set(Vec::new()));
+
+        // Unknown and repeated members are rejected
+        assert_eq!(field.validate(&serde_json::json!(["mon", "sun"])).unwrap_err().rule, Rule::Set);
+        assert_eq!(field.validate(&serde_json::json!("mon,tue,mon")).unwrap_err().rule, Rule::Set);
+        assert_eq!(field.validate(&serde_json::json!([1])).unwrap_err().rule, Rule::Type);
+
+        assert_eq!(field.export(false, None), "  days?: (\"Mon\" | \"Tue\" | \"Wed\")[] | null,\n");
+
+        field.case_sensitive = true;
+        assert_eq!(field.validate(&serde_json::json!(["mon"])).unwrap_err().rule, Rule::Set);
     }
 
     #[test]
@@ -487,9 +1189,21 @@ mod test {
             increment: false,
             generated: true,
             enum_set: None,
-            set: None
+            set: None,
+            array: false,
+            fixed: false,
+            default: None,
+            checks: Vec::new(),
+            min: None,
+            max: None,
+            format: None,
+            hidden: false,
+            column: None,
+            charset: None,
+            case_sensitive: false,
+            dialect: Dialect::MySql
         };
-        assert_eq!(field.extract(json.get("bit").unwrap()).unwrap(), DataTypeValue::Bit(1_u8));
+        assert_eq!(field.extract(json.get("bit").unwrap()).unwrap(), DataTypeValue::Bit(1_u64));
     }
 
     #[test]
@@ -509,7 +1223,19 @@ mod test {
             increment: false,
             generated: true,
             enum_set: None,
-            set: None
+            set: None,
+            array: false,
+            fixed: false,
+            default: None,
+            checks: Vec::new(),
+            min: None,
+            max: None,
+            format: None,
+            hidden: false,
+            column: None,
+            charset: None,
+            case_sensitive: false,
+            dialect: Dialect::MySql
         };
         assert_eq!(field.extract(json.get("byte").unwrap()).unwrap(), DataTypeValue::Byte(0_u8));
     }
@@ -531,7 +1257,19 @@ mod test {
             increment: false,
             generated: true,
             enum_set: None,
-            set: None
+            set: None,
+            array: false,
+            fixed: false,
+            default: None,
+            checks: Vec::new(),
+            min: None,
+            max: None,
+            format: None,
+            hidden: false,
+            column: None,
+            charset: None,
+            case_sensitive: false,
+            dialect: Dialect::MySql
         };
         assert_eq!(field.extract(json.get("uint").unwrap()).unwrap(), DataTypeValue::Unsigned32(1_u32));
     }
@@ -553,7 +1291,19 @@ mod test {
             increment: false,
             generated: true,
             enum_set: None,
-            set: None
+            set: None,
+            array: false,
+            fixed: false,
+            default: None,
+            checks: Vec::new(),
+            min: None,
+            max: None,
+            format: None,
+            hidden: false,
+            column: None,
+            charset: None,
+            case_sensitive: false,
+            dialect: Dialect::MySql
         };
         assert_eq!(field.extract(json.get("uint64").unwrap()).unwrap(), DataTypeValue::Unsigned64(4294967297_u64));
     }
@@ -575,7 +1325,19 @@ mod test {
             increment: false,
             generated: true,
             enum_set: None,
-            set: None
+            set: None,
+            array: false,
+            fixed: false,
+            default: None,
+            checks: Vec::new(),
+            min: None,
+            max: None,
+            format: None,
+            hidden: false,
+            column: None,
+            charset: None,
+            case_sensitive: false,
+            dialect: Dialect::MySql
         };
         assert_eq!(field.extract(json.get("float").unwrap()).unwrap(), DataTypeValue::Float32(1.1_f32));
     }
@@ -597,7 +1359,19 @@ mod test {
             increment: false,
             generated: true,
             enum_set: None,
-            set: None
+            set: None,
+            array: false,
+            fixed: false,
+            default: None,
+            checks: Vec::new(),
+            min: None,
+            max: None,
+            format: None,
+            hidden: false,
+            column: None,
+            charset: None,
+            case_sensitive: false,
+            dialect: Dialect::MySql
         };
         assert_eq!(field.extract(json.get("float64").unwrap()).unwrap(), DataTypeValue::Float64(1.1_f64));
     }
@@ -619,7 +1393,19 @@ mod test {
             increment: false,
             generated: true,
             enum_set: None,
-            set: None
+            set: None,
+            array: false,
+            fixed: false,
+            default: None,
+            checks: Vec::new(),
+            min: None,
+            max: None,
+            format: None,
+            hidden: false,
+            column: None,
+            charset: None,
+            case_sensitive: false,
+            dialect: Dialect::MySql
         };
         assert_eq!(field.extract(json.get("string").unwrap()).unwrap(), DataTypeValue::String("test".to_string()));
     }
@@ -641,7 +1427,19 @@ mod test {
             increment: false,
             generated: true,
             enum_set: None,
-            set: None
+            set: None,
+            array: false,
+            fixed: false,
+            default: None,
+            checks: Vec::new(),
+            min: None,
+            max: None,
+            format: None,
+            hidden: false,
+            column: None,
+            charset: None,
+            case_sensitive: false,
+            dialect: Dialect::MySql
         };
         assert_eq!(field.extract(json.get("byte_string").unwrap()).unwrap(), DataTypeValue::ByteString([0_u8].to_vec()));
     }
@@ -663,7 +1461,19 @@ mod test {
             increment: false,
             generated: true,
             enum_set: None,
-            set: None
+            set: None,
+            array: false,
+            fixed: false,
+            default: None,
+            checks: Vec::new(),
+            min: None,
+            max: None,
+            format: None,
+            hidden: false,
+            column: None,
+            charset: None,
+            case_sensitive: false,
+            dialect: Dialect::MySql
         };
         assert_eq!(field.extract(json.get("boolean").unwrap()).unwrap(), DataTypeValue::Boolean(true));
     }
@@ -685,13 +1495,162 @@ mod test {
             increment: false,
             generated: true,
             enum_set: None,
-            set: None
+            set: None,
+            array: false,
+            fixed: false,
+            default: None,
+            checks: Vec::new(),
+            min: None,
+            max: None,
+            format: None,
+            hidden: false,
+            column: None,
+            charset: None,
+            case_sensitive: false,
+            dialect: Dialect::MySql
         };
         let mut map: Map<String, serde_json::Value> = Map::new();
         map.insert("field".to_string(), serde_json::json!("test"));
         assert_eq!(field.extract(json.get("json").unwrap()).unwrap(), DataTypeValue::Json(map));
     }
 
+    #[test]
+    fn test_ranges() {
+        // A signed tinyint is stored in a 16-bit integer, but limited to 1 byte
+        let mut field = FieldDesign::new("tinyint");
+        field.datatype = DataType::Signed16;
+        field.bytes = Some(1);
+        assert_eq!(field.extract(&serde_json::json!(-128)).unwrap(), DataTypeValue::Signed16(-128));
+        assert_eq!(field.validate(&serde_json::json!(128)).unwrap_err().rule, Rule::Range);
+
+        // An unsigned mediumint is limited to 3 bytes
+        field.datatype = DataType::Unsigned32;
+        field.bytes = Some(3);
+        assert!(field.extract(&serde_json::json!(16_777_215)).is_ok());
+        assert!(field.extract(&serde_json::json!(16_777_216)).is_err());
+
+        // A decimal(5,2) allows 3 integer digits
+        field.datatype = DataType::Float64;
+        field.bytes = None;
+        field.characters = Some(5);
+        field.decimals = Some(2);
+        assert!(field.extract(&serde_json::json!(-999.99)).is_ok());
+        assert!(field.extract(&serde_json::json!(0.5)).is_ok());
+        assert_eq!(field.validate(&serde_json::json!(1000.5)).unwrap_err().rule, Rule::Characters);
+
+        // A bit(4) holds values up to 15
+        field.datatype = DataType::Bit;
+        field.characters = Some(4);
+        field.decimals = None;
+        assert_eq!(field.extract(&serde_json::json!(15)).unwrap(), DataTypeValue::Bit(15));
+        assert!(field.extract(&serde_json::json!(16)).is_err());
+    }
+
+    #[test]
+    fn test_array() {
+        let mut field = FieldDesign::new("tags");
+        field.characters = Some(3);
+        field.array = true;
+        assert_eq!(
+            field.extract(&serde_json::json!(["a", "abc"])).unwrap(),
+            DataTypeValue::Array(vec![DataTypeValue::String("a".to_string()), DataTypeValue::String("abc".to_string())])
+        );
+        assert_eq!(field.validate(&serde_json::json!(["abcd"])).unwrap_err().rule, Rule::Characters);
+        assert_eq!(field.validate(&serde_json::json!("a")).unwrap_err().rule, Rule::Type);
+        assert_eq!(field.export(false, None), "  tags?: string[] | null,\n");
+    }
+
+    #[test]
+    fn test_null() {
+        let mut field = FieldDesign::new("registered");
+        field.datatype = DataType::Date;
+        assert_eq!(field.extract(&serde_json::json!(null)).unwrap(), DataTypeValue::Null);
+        assert_eq!(field.export(true, None), "  registered?: `${number}-${number}-${number}` | null,\n");
+
+        // Fields whose column is NOT NULL reject null, and export without it
+        field.required = true;
+        assert_eq!(field.validate(&serde_json::json!(null)).unwrap_err().rule, Rule::Required);
+        assert_eq!(field.export(true, None), "  registered: `${number}-${number}-${number}`,\n");
+    }
+
+    #[test]
+    fn test_decimal() {
+        let mut field = FieldDesign::new("price");
+        field.datatype = DataType::Decimal;
+        field.characters = Some(5);
+        field.decimals = Some(2);
+        assert_eq!(
+            field.extract(&serde_json::json!("-999.99")).unwrap(),
+            DataTypeValue::Decimal(Decimal::parse("-999.99").unwrap())
+        );
+        assert_eq!(field.extract(&serde_json::json!(0.1)).unwrap().to_string(), "0.1");
+        assert_eq!(field.extract(&serde_json::json!("1.500")).unwrap().to_string(), "1.5");
+        assert_eq!(field.validate(&serde_json::json!("1.005")).unwrap_err().limit, Some("2".to_string()));
+        assert_eq!(field.validate(&serde_json::json!(1000)).unwrap_err().limit, Some("3".to_string()));
+        assert_eq!(field.validate(&serde_json::json!("1,5")).unwrap_err().rule, Rule::Type);
+
+        // Numbers with more digits than a float holds would already be rounded, so they must be strings
+        field.characters = Some(30);
+        assert_eq!(field.validate(&serde_json::json!(12345678901234567890.12)).unwrap_err().rule, Rule::Type);
+        assert_eq!(
+            field.extract(&serde_json::json!("12345678901234567890.12")).unwrap().to_string(),
+            "12345678901234567890.12"
+        );
+        assert_eq!(field.extract(&serde_json::json!(12345678901234567890_u64)).unwrap().to_string(), "12345678901234567890");
+
+        // Decimals are always output as strings, but either form is accepted as input
+        assert_eq!(field.export(false, None), "  price?: `${number}` | null,\n");
+        assert_eq!(field.export(true, None), "  price?: (number | `${number}`) | null,\n");
+    }
+
+    #[test]
+    fn test_annotations() {
+        let mut field = FieldDesign::new("username");
+        field.min = Some(3.0);
+        field.max = Some(5.0);
+        assert!(field.extract(&serde_json::json!("üser")).is_ok());
+        let failure = field.validate(&serde_json::json!("ab")).unwrap_err();
+        assert_eq!((failure.rule, failure.limit), (Rule::Characters, Some("3..=5".to_string())));
+
+        field.min = None;
+        field.max = None;
+        field.format = Some(Format::Email);
+        assert!(field.extract(&serde_json::json!("test@test.com")).is_ok());
+        assert_eq!(field.validate(&serde_json::json!("test")).unwrap_err().rule, Rule::Format);
+
+        // Numbers are compared by value
+        field.datatype = DataType::Signed32;
+        field.format = None;
+        field.min = Some(-1.5);
+        assert!(field.extract(&serde_json::json!(-1)).is_ok());
+        let failure = field.validate(&serde_json::json!(-2)).unwrap_err();
+        assert_eq!((failure.rule, failure.limit), (Rule::Range, Some("-1.5..".to_string())));
+    }
+
+    #[test]
+    fn test_temporal() {
+        let mut field = FieldDesign::new("registered");
+        field.datatype = DataType::Date;
+        assert_eq!(
+            field.extract(&serde_json::json!("2021-09-01")).unwrap(),
+            DataTypeValue::Date(Date { year: 2021, month: 9, day: 1 })
+        );
+        assert_eq!(field.validate(&serde_json::json!("2021-09-01T00:00:00")).unwrap_err().rule, Rule::Type);
+        assert_eq!(field.validate(&serde_json::json!("0999-01-01")).unwrap_err().rule, Rule::Range);
+
+        field.datatype = DataType::Timestamp;
+        assert!(field.extract(&serde_json::json!("2021-09-01T12:00:00.000Z")).is_ok());
+        assert_eq!(field.validate(&serde_json::json!("2040-01-01 00:00:00")).unwrap_err().rule, Rule::Range);
+
+        field.datatype = DataType::Time;
+        assert!(field.extract(&serde_json::json!("-12:00:00")).is_ok());
+        assert!(field.extract(&serde_json::json!("900:00:00")).is_err());
+
+        field.datatype = DataType::Year;
+        assert_eq!(field.extract(&serde_json::json!(2021)).unwrap(), DataTypeValue::Year(2021));
+        assert!(field.extract(&serde_json::json!(1900)).is_err());
+    }
+
     fn json_init() -> Value {
         serde_json::json!({
             "int": -1_i32,
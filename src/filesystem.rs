@@ -16,7 +16,7 @@ pub(crate) fn read_file(path: &str) -> Result<String, RustractError> {
     let mut file = match File::open(path) {
         Ok(file) => file,
         Err(err) => return Err(RustractError::Filesystem(GenericError {
-            message: format!("failed to find file <{}>: {}", path, err.to_string())
+            message: format!("failed to find file <{}>: {}", path, err)
         }))
     };
     let mut s = String::new();
@@ -0,0 +1,80 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use regex::Regex;
+use serde::{Serialize,Deserialize};
+use crate::error::{RustractError, GenericError};
+
+/// A common string format that a field's values must follow, such as an email address.
+#[derive(Deserialize, Serialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    Email,
+    /// An absolute HTTP or HTTPS URL.
+    Url,
+    /// The hyphenated hexadecimal form of a UUID.
+    Uuid,
+    Ipv4
+}
+
+impl Format {
+    /// Gets the regex that values of this format match.
+    pub fn pattern(&self) -> &'static str {
+        match self {
+            Format::Email => r"^[^@\s]+@[^@\s]+\.[^@\s]+$",
+            Format::Url => r"^https?://[^\s/?#]+\.?[^\s/?#]*(?::\d+)?(?:[/?#]\S*)?$",
+            Format::Uuid => "^[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}$",
+            Format::Ipv4 => r"^(?:(?:25[0-5]|2[0-4][0-9]|1[0-9][0-9]|[1-9]?[0-9])\.){3}(?:25[0-5]|2[0-4][0-9]|1[0-9][0-9]|[1-9]?[0-9])$",
+        }
+    }
+
+    /// Returns true if the text follows this format.
+    pub fn is_match(&self, text: &str) -> bool {
+        Regex::new(self.pattern()).is_ok_and(|regex| regex.is_match(text))
+    }
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", match self {
+            Format::Email => "email",
+            Format::Url => "url",
+            Format::Uuid => "uuid",
+            Format::Ipv4 => "ipv4"
+        })
+    }
+}
+
+impl FromStr for Format {
+    type Err = RustractError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text.to_ascii_lowercase().as_str() {
+            "email" => Ok(Format::Email),
+            "url" => Ok(Format::Url),
+            "uuid" => Ok(Format::Uuid),
+            "ipv4" => Ok(Format::Ipv4),
+            _ => Err(RustractError::Generic(GenericError {
+                message: format!("{} is not a supported format", text),
+            })),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn format_test() {
+        assert!(Format::Email.is_match("test@test.com"));
+        assert!(!Format::Email.is_match("test@test"));
+        assert!(Format::Url.is_match("https://example.com:8080/path?query=1"));
+        assert!(!Format::Url.is_match("example.com"));
+        assert!(Format::Uuid.is_match("123e4567-e89b-12d3-a456-426614174000"));
+        assert!(Format::Ipv4.is_match("192.168.0.1"));
+        assert!(!Format::Ipv4.is_match("256.1.1.1"));
+
+        assert_eq!("Email".parse::<Format>().unwrap(), Format::Email);
+        assert!("phone".parse::<Format>().is_err());
+    }
+}
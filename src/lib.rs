@@ -4,25 +4,40 @@
 //! Date: 2021-09-01
 pub mod error;
 pub mod db;
+pub mod diff;
 pub mod table;
 pub mod field;
+pub mod row;
+pub mod report;
 pub mod types;
+pub mod temporal;
+pub mod decimal;
+pub mod format;
+pub mod charset;
+pub mod overrides;
+pub mod migration;
+mod ddl;
 mod filesystem;
+mod sql;
 use error::RustractError;
 use filesystem::get_config;
 
 use crate::db::Database;
+use crate::overrides::Overrides;
 
 /// Initializes a local library based on the input settings.
 /// 
 /// If this is the first time running the library, set `config_path` to `None`,
 /// and provide a `dump_path` leading to the mysql dump of the database.
+/// For other databases, set the `dialect` of the config (such as `"postgresql"` for a `pg_dump` schema).
 /// Also, set `reload_schema` to `true`.
 /// 
 /// If anything is not provided, this function will use defaults.
 /// This saves the `Config` and `Database` `json`'s to the working directory.
 /// 
 /// If the provided schema path differs from the saved one, it will be overwritten.
+///
+/// If the config has an `overrides_path`, the overrides are applied each time the schema is read.
 pub fn init(config_path: Option<&str>, schema_path: Option<&str>, reload_schema: bool) -> Result<Database, RustractError> {
     // Creates a config if none is provided, then sets up directory structure
     let config = if let Some(path) = config_path { get_config(path)? } else {
@@ -33,15 +48,15 @@ pub fn init(config_path: Option<&str>, schema_path: Option<&str>, reload_schema:
         c.save("./config.json")?;
         c
     };
-    let type_path = if config.type_path.is_some() { config.type_path.unwrap() } else { "./types/".to_string() };
+    let type_path = config.type_path.clone().unwrap_or_else(|| "./types/".to_string());
 
     // Loads the database from the path, or from the schema if no database is found
     let db: Database = if reload_schema {
-        Database::from_schema(&config.schema_path)?
+        read_schema(&config)?
     } else {
         match Database::from(&config.db_path) {
             Ok(file) => file,
-            Err(_) => Database::from_schema(&config.schema_path)?,
+            Err(_) => read_schema(&config)?,
         }
     };
 
@@ -54,6 +69,18 @@ pub fn init(config_path: Option<&str>, schema_path: Option<&str>, reload_schema:
     Ok(db)
 }
 
+/// Reads the database design from the config's schema (or migrations), then applies its overrides.
+fn read_schema(config: &types::Config) -> Result<Database, RustractError> {
+    let mut db = match &config.migrations_path {
+        Some(path) => Database::from_migrations(path, config.dialect)?,
+        None => Database::from_schema_dialect(&config.schema_path, config.dialect)?,
+    };
+    if let Some(path) = &config.overrides_path {
+        Overrides::from(path)?.apply(&mut db)?;
+    }
+    Ok(db)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -74,6 +101,7 @@ mod test {
         assert_eq!(&config.db_path, "./example_database.json");
         assert_eq!(&config.schema_path, "./tests/schema.sql");
         assert_eq!(config.type_path, None);
+        assert_eq!(config.dialect, types::Dialect::MySql);
         delete_config();
     }
 
@@ -0,0 +1,470 @@
+use crate::db::Database;
+use crate::ddl::{check_sql, column_definition, columns, create_table, create_tables, create_type, creation_order, default_sql, enum_type, field_checks, foreign_key, foreign_key_name, index_name, quote, sql_type, values};
+use crate::error::RustractError;
+use crate::field::{DefaultValue, FieldDesign};
+use crate::filesystem::check_path;
+use crate::table::{Index, TableDesign};
+use crate::temporal::DateTime;
+use crate::types::{DataType, Dialect};
+
+/// The statements that change a database from one design to another (up), and back again (down).
+#[derive(Debug, PartialEq, Clone)]
+pub struct Migration {
+    pub up: Vec<String>,
+    pub down: Vec<String>
+}
+
+impl Migration {
+    /// Creates the migration from the old database design to the new one, in the specified dialect.
+    ///
+    /// Tables and fields are matched by title, so a renamed table is dropped and created again.
+    /// Changes to `CHECK` constraints are only migrated in SQLite, where the table is rebuilt.
+    pub fn between(old: &Database, new: &Database, dialect: Dialect) -> Result<Self, RustractError> {
+        Ok(Migration {
+            up: statements(old, new, dialect)?,
+            down: statements(new, old, dialect)?
+        })
+    }
+
+    /// Returns true if the designs are the same.
+    pub fn is_empty(&self) -> bool {
+        self.up.is_empty() && self.down.is_empty()
+    }
+
+    /// Gets the SQL of the up migration.
+    pub fn up_sql(&self) -> String {
+        script(&self.up)
+    }
+
+    /// Gets the SQL of the down migration.
+    pub fn down_sql(&self) -> String {
+        script(&self.down)
+    }
+
+    /// Saves the migration to the folder as `{version}_{description}.up.sql` and `{version}_{description}.down.sql`,
+    /// where the version is the current UTC time as `YYYYMMDDHHMMSS` (the layout `sqlx migrate` reads).
+    ///
+    /// The version is moved past any migration already in the folder, so they stay in order.
+    /// Returns the name of the migration, without its extension.
+    pub fn save(&self, folder: &str, description: &str) -> Result<String, RustractError> {
+        check_path(folder)?;
+        let now = DateTime::now();
+        let mut version: u64 = format!(
+            "{:04}{:02}{:02}{:02}{:02}{:02}",
+            now.date.year,
+            now.date.month,
+            now.date.day,
+            now.hour,
+            now.minute,
+            now.second
+        ).parse()?;
+        for entry in std::fs::read_dir(folder)? {
+            let name = entry?.file_name().to_string_lossy().to_string();
+            let digits: String = name.chars().take_while(char::is_ascii_digit).collect();
+            if let Ok(existing) = digits.parse::<u64>() {
+                version = version.max(existing + 1);
+            }
+        }
+
+        let description: String = description.trim()
+            .chars()
+            .map(|c| if c.is_alphanumeric() { c.to_ascii_lowercase() } else { '_' })
+            .collect();
+        let name = format!("{}_{}", version, description);
+        let path = std::path::Path::new(folder);
+        std::fs::write(path.join(format!("{}.up.sql", name)), self.up_sql())?;
+        std::fs::write(path.join(format!("{}.down.sql", name)), self.down_sql())?;
+        Ok(name)
+    }
+}
+
+/// Joins statements into a script.
+fn script(statements: &[String]) -> String {
+    if statements.is_empty() {
+        String::new()
+    } else {
+        statements.join("\n\n") + "\n"
+    }
+}
+
+/// Writes the statements that change a database from one design to another.
+///
+/// Foreign keys and indexes are dropped first and added last, so the columns they use can change in between.
+fn statements<'a>(from: &'a Database, to: &'a Database, dialect: Dialect) -> Result<Vec<String>, RustractError> {
+    let mut drops = Vec::new();
+    let mut changes = Vec::new();
+    let mut adds = Vec::new();
+
+    // Views are left out, since their queries are not kept
+    let tables = |db: &'a Database| db.tables.values().filter(|table| !table.read_only);
+    let find = |db: &'a Database, title: &str| db.table(title).filter(|table| !table.read_only);
+
+    let kept: Vec<(&TableDesign, &TableDesign)> = tables(from)
+        .filter_map(|old| find(to, &old.table_design_title).map(|new| (old, new)))
+        .collect();
+    for (old, new) in &kept {
+        if dialect == Dialect::Sqlite && needs_rebuild(old, new) {
+            changes.extend(rebuild(to, old, new)?);
+            continue;
+        }
+        let table = AlterTable { to, old, new, dialect };
+        drops.extend(table.drop_keys());
+        changes.extend(table.change_fields()?);
+        adds.extend(table.add_keys()?);
+    }
+
+    // Added tables may refer to the kept ones, which already exist
+    let created = kept.iter().map(|(_, new)| new.table_design_title.as_str()).collect();
+    let added = tables(to).filter(|new| find(from, &new.table_design_title).is_none()).collect();
+    changes.extend(create_tables(to, added, dialect, created)?);
+
+    // Removed tables are dropped before the tables they refer to
+    let removed = tables(from).filter(|old| find(to, &old.table_design_title).is_none()).collect();
+    for table in creation_order(from, removed).into_iter().rev() {
+        changes.push(format!("DROP TABLE {};", quote(&table.table_design_title, dialect)));
+        if dialect == Dialect::PostgreSql {
+            for field in table.fields.values().filter(|field| is_enum(field)) {
+                changes.push(format!("DROP TYPE {};", enum_type(table, field)));
+            }
+        }
+    }
+
+    let mut statements = drops;
+    statements.extend(changes);
+    statements.extend(adds);
+    Ok(statements)
+}
+
+/// The changes to a table that is in both designs.
+struct AlterTable<'a> {
+    to: &'a Database,
+    old: &'a TableDesign,
+    new: &'a TableDesign,
+    dialect: Dialect
+}
+
+impl AlterTable<'_> {
+    /// Writes an `ALTER TABLE` statement of this table.
+    fn alter(&self, action: &str) -> String {
+        format!("ALTER TABLE {} {};", quote(&self.new.table_design_title, self.dialect), action)
+    }
+
+    /// Gets the foreign key fields of the table that are not in the other design with the same key.
+    fn changed_keys<'a>(table: &'a TableDesign, other: &'a TableDesign) -> impl Iterator<Item = &'a FieldDesign> {
+        table.fields.values().filter(move |field| field.foreign.is_some()
+            && other.field(&field.field_design_title).is_none_or(|other| other.foreign != field.foreign || other.column_name() != field.column_name()))
+    }
+
+    /// Gets the indexes of the table that are not in the other design.
+    fn changed_indexes<'a>(table: &'a TableDesign, other: &'a TableDesign) -> impl Iterator<Item = &'a Index> {
+        table.indexes.iter().filter(move |index| !other.indexes.contains(index))
+    }
+
+    /// Drops the foreign keys and indexes that are removed or changed.
+    fn drop_keys(&self) -> Vec<String> {
+        let dialect = self.dialect;
+        let mut statements = Vec::new();
+        for field in AlterTable::changed_keys(self.old, self.new) {
+            let name = quote(&foreign_key_name(self.old, field), dialect);
+            statements.push(match dialect {
+                Dialect::MySql => self.alter(&format!("DROP FOREIGN KEY {}", name)),
+                _ => self.alter(&format!("DROP CONSTRAINT {}", name)),
+            });
+        }
+        for index in AlterTable::changed_indexes(self.old, self.new) {
+            let name = match (&index.name, index.primary) {
+                (None, true) => format!("{}_pkey", self.old.table_design_title),
+                _ => index_name(self.old, index),
+            };
+            let name = quote(&name, dialect);
+            statements.push(match (dialect, index.primary, index.unique) {
+                (Dialect::MySql, true, _) => self.alter("DROP PRIMARY KEY"),
+                (Dialect::MySql, false, _) => self.alter(&format!("DROP INDEX {}", name)),
+                (_, _, true) => self.alter(&format!("DROP CONSTRAINT {}", name)),
+                (_, _, false) => format!("DROP INDEX {};", name),
+            });
+        }
+        statements
+    }
+
+    /// Adds the foreign keys and indexes that are new or changed.
+    fn add_keys(&self) -> Result<Vec<String>, RustractError> {
+        let dialect = self.dialect;
+        let mut statements = Vec::new();
+        for index in AlterTable::changed_indexes(self.new, self.old) {
+            let name = quote(&index_name(self.new, index), dialect);
+            let fields = columns(self.new, &index.fields, dialect);
+            statements.push(match (dialect, index.primary, index.unique) {
+                (_, true, _) => self.alter(&format!("ADD PRIMARY KEY ({})", fields)),
+                (Dialect::MySql, false, unique) => self.alter(&format!("ADD {}KEY {} ({})", if unique { "UNIQUE " } else { "" }, name, fields)),
+                (Dialect::PostgreSql, false, true) => self.alter(&format!("ADD CONSTRAINT {} UNIQUE ({})", name, fields)),
+                (_, false, unique) => format!(
+                    "CREATE {}INDEX {} ON {} ({});",
+                    if unique { "UNIQUE " } else { "" },
+                    name,
+                    quote(&self.new.table_design_title, dialect),
+                    fields
+                ),
+            });
+        }
+        for field in AlterTable::changed_keys(self.new, self.old) {
+            statements.push(self.alter(&format!("ADD {}", foreign_key(self.to, self.new, field, dialect)?)));
+        }
+        Ok(statements)
+    }
+
+    /// Adds, changes and drops the table's columns.
+    fn change_fields(&self) -> Result<Vec<String>, RustractError> {
+        let dialect = self.dialect;
+        let mut statements = Vec::new();
+        for field in self.new.fields.values() {
+            let old = match self.old.field(&field.field_design_title) {
+                Some(old) => old,
+                None => {
+                    statements.extend(create_type(self.new, field, dialect));
+                    statements.push(self.alter(&format!("ADD COLUMN {}", column_definition(self.new, field, dialect)?)));
+                    continue;
+                },
+            };
+            if old.column_name() != field.column_name() {
+                statements.push(self.alter(&format!(
+                    "RENAME COLUMN {} TO {}",
+                    quote(old.column_name(), dialect),
+                    quote(field.column_name(), dialect)
+                )));
+            }
+            match dialect {
+                Dialect::PostgreSql => statements.extend(self.change_postgres_field(old, field)?),
+                _ => if column_definition(self.old, old, dialect)? != column_definition(self.new, field, dialect)? {
+                    statements.push(self.alter(&format!("MODIFY COLUMN {}", column_definition(self.new, field, dialect)?)));
+                },
+            }
+        }
+        for field in self.old.fields.values().filter(|field| self.new.field(&field.field_design_title).is_none()) {
+            statements.push(self.alter(&format!("DROP COLUMN {}", quote(field.column_name(), dialect))));
+            if dialect == Dialect::PostgreSql && is_enum(field) {
+                statements.push(format!("DROP TYPE {};", enum_type(self.old, field)));
+            }
+        }
+        Ok(statements)
+    }
+
+    /// Changes a PostgreSQL column, which is altered one setting at a time.
+    fn change_postgres_field(&self, old: &FieldDesign, new: &FieldDesign) -> Result<Vec<String>, RustractError> {
+        let dialect = self.dialect;
+        let column = quote(new.column_name(), dialect);
+        let alter = |action: String| self.alter(&format!("ALTER COLUMN {} {}", column, action));
+        let mut statements = Vec::new();
+
+        let old_type = sql_type(self.old, old, dialect)?;
+        let new_type = sql_type(self.new, new, dialect)?;
+        let retyped = old_type != new_type || values(old) != values(new);
+        if retyped && old.default.is_some() {
+            statements.push(alter("DROP DEFAULT".to_string()));
+        }
+        if retyped && old_type == new_type {
+            // Enum values cannot be removed, so the type is created again and the column's values are cast to it
+            let name = enum_type(self.new, new);
+            let cast = if new.datatype == DataType::Set || new.array { "text[]" } else { "text" };
+            statements.push(format!("ALTER TYPE {} RENAME TO {}_old;", name, name));
+            statements.extend(create_type(self.new, new, dialect));
+            statements.push(alter(format!("TYPE {} USING {}::{}::{}", new_type, column, cast, new_type)));
+            statements.push(format!("DROP TYPE {}_old;", name));
+        } else if retyped {
+            statements.extend(create_type(self.new, new, dialect));
+            statements.push(alter(format!("TYPE {} USING {}::{}", new_type, column, new_type)));
+            if is_enum(old) {
+                statements.push(format!("DROP TYPE {};", enum_type(self.old, old)));
+            }
+        }
+
+        if old.increment != new.increment {
+            statements.push(alter(if new.increment { "ADD GENERATED BY DEFAULT AS IDENTITY" } else { "DROP IDENTITY" }.to_string()));
+        }
+        if old.required != new.required {
+            statements.push(alter(if new.required { "SET NOT NULL" } else { "DROP NOT NULL" }.to_string()));
+        }
+        if (retyped || old.default != new.default) && !new.increment {
+            match &new.default {
+                Some(default) => statements.push(alter(format!("SET DEFAULT {}", default_sql(new, default, dialect)?))),
+                None if !retyped && old.default.is_some() => statements.push(alter("DROP DEFAULT".to_string())),
+                None => (),
+            }
+        }
+        Ok(statements)
+    }
+}
+
+/// Returns true if a field uses its own PostgreSQL enum type.
+fn is_enum(field: &FieldDesign) -> bool {
+    matches!(field.datatype, DataType::Enum | DataType::Set)
+}
+
+/// Returns true if SQLite can only make the changes to a table by creating it again,
+/// since it can add columns and indexes but not change or drop constraints.
+fn needs_rebuild(old: &TableDesign, new: &TableDesign) -> bool {
+    let dialect = Dialect::Sqlite;
+    let definition = |table, field| column_definition(table, field, dialect).ok();
+    let checks = |table| field_checks(table).chain(table.checks.iter()).map(|check| check_sql(check, dialect)).collect::<Vec<String>>();
+
+    checks(old) != checks(new)
+        || AlterTable::changed_keys(old, new).next().is_some()
+        || AlterTable::changed_keys(new, old).next().is_some()
+        || AlterTable::changed_indexes(old, new).next().is_some()
+        || AlterTable::changed_indexes(new, old).any(|index| index.primary)
+        || old.fields.values().any(|field| match new.field(&field.field_design_title) {
+            Some(new_field) => definition(old, field) != definition(new, new_field),
+            None => true,
+        })
+        || new.fields.values().filter(|field| old.field(&field.field_design_title).is_none()).any(|field| {
+            // Added columns cannot be keys, and must have a constant default if they are required
+            let constant = matches!(&field.default, Some(default) if *default != DefaultValue::CurrentTimestamp && *default != DefaultValue::Null);
+            field.primary || field.increment || (field.required && !constant)
+        })
+}
+
+/// Creates a SQLite table again with its new design, copying the rows of the columns that are kept.
+fn rebuild(to: &Database, old: &TableDesign, new: &TableDesign) -> Result<Vec<String>, RustractError> {
+    let dialect = Dialect::Sqlite;
+    let title = quote(&new.table_design_title, dialect);
+    let temporary = format!("{}_new", new.table_design_title);
+    let created = to.tables.keys().map(String::as_str).collect();
+    let sql = create_table(to, new, dialect, &created, &temporary)?;
+
+    let kept: Vec<(&FieldDesign, &FieldDesign)> = old.fields.values()
+        .filter_map(|field| new.field(&field.field_design_title).map(|new_field| (field, new_field)))
+        .collect();
+    let names = |fields: Vec<&FieldDesign>| fields.iter().map(|field| quote(field.column_name(), dialect)).collect::<Vec<String>>().join(", ");
+
+    let mut statements = vec!["PRAGMA foreign_keys = OFF;".to_string(), sql.create];
+    if !kept.is_empty() {
+        statements.push(format!(
+            "INSERT INTO {} ({}) SELECT {} FROM {};",
+            quote(&temporary, dialect),
+            names(kept.iter().map(|(_, new)| *new).collect()),
+            names(kept.iter().map(|(old, _)| *old).collect()),
+            title
+        ));
+    }
+    statements.push(format!("DROP TABLE {};", title));
+    statements.push(format!("ALTER TABLE {} RENAME TO {};", quote(&temporary, dialect), title));
+    statements.extend(sql.indexes);
+    statements.push("PRAGMA foreign_keys = ON;".to_string());
+    Ok(statements)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const OLD: &str = "CREATE TABLE `user` (
+        `id` INT UNSIGNED NOT NULL AUTO_INCREMENT,
+        `name` VARCHAR(45) NOT NULL,
+        `email` VARCHAR(45) NOT NULL,
+        `type` ENUM('Admin','Mod','Basic') NOT NULL,
+        PRIMARY KEY (`id`)
+    );
+    CREATE TABLE `session` (`id` INT UNSIGNED NOT NULL, `user` INT UNSIGNED NOT NULL, PRIMARY KEY (`id`), FOREIGN KEY (`user`) REFERENCES `user` (`id`));";
+
+    const NEW: &str = "CREATE TABLE `user` (
+        `id` INT UNSIGNED NOT NULL AUTO_INCREMENT,
+        `name` VARCHAR(60) NOT NULL,
+        `type` ENUM('Admin','Basic','Guest') NOT NULL DEFAULT 'Basic',
+        `nickname` VARCHAR(20) NULL,
+        PRIMARY KEY (`id`),
+        UNIQUE KEY `name_UNIQUE` (`name`)
+    );
+    CREATE TABLE `post` (`id` INT UNSIGNED NOT NULL, `author` INT UNSIGNED NOT NULL, PRIMARY KEY (`id`), FOREIGN KEY (`author`) REFERENCES `user` (`id`));";
+
+    #[test]
+    fn migration_test() {
+        let old = Database::from_sql(OLD).unwrap();
+        let new = Database::from_sql(NEW).unwrap();
+        let migration = old.migration(&new, Dialect::MySql).unwrap();
+        assert_eq!(migration.up, vec![
+            "ALTER TABLE `user` MODIFY COLUMN `name` VARCHAR(60) NOT NULL;",
+            "ALTER TABLE `user` ADD COLUMN `nickname` VARCHAR(20);",
+            "ALTER TABLE `user` MODIFY COLUMN `type` ENUM('Admin','Basic','Guest') NOT NULL DEFAULT 'Basic';",
+            "ALTER TABLE `user` DROP COLUMN `email`;",
+            "CREATE TABLE `post` (\n  `id` INT UNSIGNED NOT NULL,\n  `author` INT UNSIGNED NOT NULL,\n  PRIMARY KEY (`id`),\n  \
+                CONSTRAINT `post_author_fkey` FOREIGN KEY (`author`) REFERENCES `user` (`id`)\n);",
+            "DROP TABLE `session`;",
+            "ALTER TABLE `user` ADD UNIQUE KEY `name_UNIQUE` (`name`);",
+        ]);
+        // Indexes are dropped before the columns they use
+        assert_eq!(migration.down[0], "ALTER TABLE `user` DROP INDEX `name_UNIQUE`;");
+        assert!(migration.down.contains(&"ALTER TABLE `user` ADD COLUMN `email` VARCHAR(45) NOT NULL;".to_string()));
+        assert!(old.migration(&old, Dialect::MySql).unwrap().is_empty());
+
+        // PostgreSQL enums are created again to remove values
+        let postgres = old.migration(&new, Dialect::PostgreSql).unwrap().up_sql();
+        assert!(postgres.contains("ALTER TYPE user_type RENAME TO user_type_old;\n\n\
+            CREATE TYPE user_type AS ENUM ('Admin', 'Basic', 'Guest');\n\n\
+            ALTER TABLE \"user\" ALTER COLUMN \"type\" TYPE user_type USING \"type\"::text::user_type;\n\n\
+            DROP TYPE user_type_old;"));
+        assert!(postgres.contains("ALTER TABLE \"user\" ALTER COLUMN \"type\" SET DEFAULT 'Basic';"));
+    }
+
+    /// Reads the design back from saved migrations, which should match the design they were written from.
+    #[test]
+    fn replay_test() {
+        let folder = "./migrations_replay_test";
+        let old = Database::from_sql(OLD).unwrap();
+        let new = Database::from_sql(NEW).unwrap();
+        for dialect in [Dialect::MySql, Dialect::PostgreSql, Dialect::Sqlite] {
+            let _ = std::fs::remove_dir_all(folder);
+            Database::new().migration(&old, dialect).unwrap().save(folder, "create users").unwrap();
+            old.migration(&new, dialect).unwrap().save(folder, "add posts").unwrap();
+
+            let read = Database::from_migrations(folder, dialect).unwrap();
+            let expected = Database::from_sql_dialect(&new.to_sql(dialect).unwrap(), dialect).unwrap();
+            assert!(expected.diff(&read).is_empty(), "{} migrations were read as {}", dialect, expected.diff(&read));
+            assert_eq!(read.table("user").unwrap().indexes, expected.table("user").unwrap().indexes);
+        }
+        std::fs::remove_dir_all(folder).unwrap();
+    }
+
+    /// Runs the migrations on a SQLite database, since SQLite must create changed tables again.
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn sqlite_test() {
+        let path = "./migration_test.db";
+        let _ = std::fs::remove_file(path);
+        let old = Database::from_sql(OLD).unwrap();
+        let new = Database::from_sql(NEW).unwrap();
+        let migration = old.migration(&new, Dialect::Sqlite).unwrap();
+        let read = |sql: &str| Database::from_sql_dialect(sql, Dialect::Sqlite).unwrap();
+
+        let connection = rusqlite::Connection::open(path).unwrap();
+        connection.execute_batch(&old.to_sql(Dialect::Sqlite).unwrap()).unwrap();
+        connection.execute("INSERT INTO user (name, email, type) VALUES ('Test', 'test@test.com', 'Admin')", []).unwrap();
+        connection.execute_batch(&migration.up_sql()).unwrap();
+        assert!(Database::from_sqlite_file(path).unwrap().diff(&read(&new.to_sql(Dialect::Sqlite).unwrap())).is_empty());
+        let name: String = connection.query_row("SELECT name FROM user", [], |row| row.get(0)).unwrap();
+        assert_eq!(name, "Test");
+
+        // The down migration adds back the required email, which the row does not have
+        connection.execute("DELETE FROM user", []).unwrap();
+        connection.execute_batch(&migration.down_sql()).unwrap();
+        assert!(Database::from_sqlite_file(path).unwrap().diff(&read(&old.to_sql(Dialect::Sqlite).unwrap())).is_empty());
+        drop(connection);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn save_test() {
+        let folder = "./migrations_test";
+        let _ = std::fs::remove_dir_all(folder);
+        let old = Database::from_sql(OLD).unwrap();
+        let new = Database::from_sql(NEW).unwrap();
+        let migration = old.migration(&new, Dialect::MySql).unwrap();
+
+        let first = migration.save(folder, "Add posts").unwrap();
+        let second = migration.save(folder, "add posts").unwrap();
+        assert!(first.ends_with("_add_posts") && first.len() == "YYYYMMDDHHMMSS_add_posts".len());
+        assert!(second > first);
+        assert_eq!(std::fs::read_to_string(format!("{}/{}.up.sql", folder, first)).unwrap(), migration.up_sql());
+        assert_eq!(std::fs::read_to_string(format!("{}/{}.down.sql", folder, first)).unwrap(), migration.down_sql());
+        std::fs::remove_dir_all(folder).unwrap();
+    }
+}
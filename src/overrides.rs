@@ -0,0 +1,312 @@
+use std::collections::BTreeMap;
+use serde::{Serialize,Deserialize};
+use crate::db::Database;
+use crate::error::{RustractError, GenericError};
+use crate::field::FieldDesign;
+use crate::filesystem::read_file;
+use crate::format::Format;
+use crate::table::TableDesign;
+
+/// Changes to apply to a database design after it is read from the schema.
+///
+/// Overrides are kept in their own file (set by the config's `overrides_path`),
+/// so they are not lost when the design is read from the schema again.
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct Overrides {
+    /// The changes to each table, by the table's title in the schema.
+    #[serde(default)]
+    pub tables: BTreeMap<String, TableOverride>
+}
+
+/// Changes to a single table's design.
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct TableOverride {
+    /// A new title for the table, such as one that reads better in TypeScript.
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    pub rename: Option<String>,
+    /// The changes to each field, by the field's title in the schema.
+    #[serde(default)]
+    pub fields: BTreeMap<String, FieldOverride>
+}
+
+/// Changes to a single field's design, where each setting that is left out is kept as it was read.
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct FieldOverride {
+    /// A new title for the field, which is used in JSON and TypeScript instead of the column's name.
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    pub rename: Option<String>,
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    pub regex: Option<String>,
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    pub characters: Option<isize>,
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    pub required: Option<bool>,
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    pub format: Option<Format>,
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    pub min: Option<f64>,
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    pub max: Option<f64>,
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    pub hidden: Option<bool>,
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    pub case_sensitive: Option<bool>
+}
+
+impl Overrides {
+    /// Creates an instance of this struct from the JSON file at the specified path.
+    pub fn from(filepath: &str) -> Result<Self, RustractError> {
+        Ok(serde_json::from_str(&read_file(filepath)?)?)
+    }
+
+    /// Saves the overrides to a JSON file.
+    pub fn save(&self, filepath: &str) -> Result<(), RustractError> {
+        std::fs::write(
+            filepath,
+            serde_json::to_string_pretty(self)?
+        )?;
+        Ok(())
+    }
+
+    /// Applies these overrides to the database design.
+    ///
+    /// Fails if an override refers to a table or field that does not exist (such as one removed from the schema),
+    /// or renames one to a title that is already used or that another is also renamed to.
+    pub fn apply(&self, db: &mut Database) -> Result<(), RustractError> {
+        // Every override is checked before any are applied, so a failure leaves the design unchanged
+        for (title, table_override) in &self.tables {
+            let table = match db.table(title) {
+                Some(table) => table,
+                None => return Err(RustractError::Table(GenericError {
+                    message: format!("the override for table {} refers to a table that does not exist", title)
+                })),
+            };
+            table_override.check(table, db)?;
+        }
+        let mut renamed = BTreeMap::new();
+        for (title, rename) in self.tables.iter().filter_map(|(title, table)| Some((title, table.rename.as_ref()?))) {
+            if let Some(other) = renamed.insert(rename, title) {
+                return Err(RustractError::Table(GenericError {
+                    message: format!("failed to rename both tables {} and {} to {}", other, title, rename)
+                }));
+            }
+        }
+
+        for (title, table_override) in &self.tables {
+            let mut renames = Vec::new();
+            if let Some(table) = db.table_mut(title) {
+                for (field_title, field_override) in &table_override.fields {
+                    if let Some(field) = table.field_mut(field_title) {
+                        field_override.apply_to(field);
+                    }
+                    if let Some(rename) = &field_override.rename {
+                        renames.push((field_title.clone(), rename.clone()));
+                    }
+                }
+                rename_fields(table, &renames);
+            }
+
+            // Foreign keys follow the fields and tables they refer to
+            let rename = table_override.rename.as_deref().unwrap_or(title);
+            for table in db.tables.values_mut() {
+                for field in table.fields.values_mut() {
+                    if let Some(key) = field.foreign.as_mut().filter(|key| key.table == *title) {
+                        if let Some((_, new_field)) = renames.iter().find(|(old_field, _)| *old_field == key.field) {
+                            key.field = new_field.clone();
+                        }
+                        key.table = rename.to_string();
+                    }
+                }
+            }
+            if let Some(mut table) = table_override.rename.as_ref().and_then(|_| db.tables.remove(title)) {
+                table.table_design_title = rename.to_string();
+                db.add(table);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TableOverride {
+    /// Checks that each field override refers to an existing field, and that no titles would be duplicated.
+    fn check(&self, table: &TableDesign, db: &Database) -> Result<(), RustractError> {
+        if let Some(rename) = &self.rename {
+            if rename != &table.table_design_title && db.table(rename).is_some() {
+                return Err(RustractError::Table(GenericError {
+                    message: format!("failed to rename table {} to {}, which already exists", table.table_design_title, rename)
+                }));
+            }
+        }
+        let mut renamed = BTreeMap::new();
+        for (title, field_override) in &self.fields {
+            if table.field(title).is_none() {
+                return Err(RustractError::Field(GenericError {
+                    message: format!(
+                        "the override for field {} refers to a field that does not exist in table {}",
+                        title,
+                        table.table_design_title
+                    )
+                }));
+            }
+            if let Some(rename) = &field_override.rename {
+                if let Some(other) = renamed.insert(rename, title) {
+                    return Err(RustractError::Field(GenericError {
+                        message: format!(
+                            "failed to rename both fields {} and {} to {} in table {}",
+                            other,
+                            title,
+                            rename,
+                            table.table_design_title
+                        )
+                    }));
+                }
+                let renamed_away = |other: &String| self.fields.get(other).is_some_and(|other| other.rename.is_some());
+                if rename != title && table.fields.keys().any(|other| other == rename && !renamed_away(other)) {
+                    return Err(RustractError::Field(GenericError {
+                        message: format!(
+                            "failed to rename field {} to {}, which already exists in table {}",
+                            title,
+                            rename,
+                            table.table_design_title
+                        )
+                    }));
+                }
+            }
+            if let Some(regex) = &field_override.regex {
+                regex::Regex::new(regex)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FieldOverride {
+    /// Replaces the settings of the field that this override sets.
+    fn apply_to(&self, field: &mut FieldDesign) {
+        if let Some(regex) = &self.regex {
+            field.regex = Some(regex.clone());
+        }
+        if let Some(characters) = self.characters {
+            field.characters = Some(characters);
+        }
+        if let Some(required) = self.required {
+            field.required = required;
+        }
+        if let Some(format) = self.format {
+            field.format = Some(format);
+        }
+        if let Some(min) = self.min {
+            field.min = Some(min);
+        }
+        if let Some(max) = self.max {
+            field.max = Some(max);
+        }
+        if let Some(hidden) = self.hidden {
+            field.hidden = hidden;
+        }
+        if let Some(case_sensitive) = self.case_sensitive {
+            field.case_sensitive = case_sensitive;
+        }
+    }
+}
+
+/// Renames fields of the table, keeping the names of their columns and updating the indexes that use them.
+///
+/// Every field is removed before any are added back, so fields may swap titles.
+fn rename_fields(table: &mut TableDesign, renames: &[(String, String)]) {
+    let fields: Vec<FieldDesign> = renames.iter()
+        .filter_map(|(title, rename)| {
+            let mut field = table.fields.remove(title)?;
+            field.column.get_or_insert_with(|| title.clone());
+            field.field_design_title = rename.clone();
+            Some(field)
+        })
+        .collect();
+    for field in fields {
+        table.add(field);
+    }
+    for index in &mut table.indexes {
+        for field in &mut index.fields {
+            if let Some((_, rename)) = renames.iter().find(|(title, _)| title == field) {
+                *field = rename.clone();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn overrides_test() {
+        let mut db = Database::from_schema("./tests/schema.sql").unwrap();
+        let overrides: Overrides = serde_json::from_str(r#"{
+            "tables": {
+                "user": {
+                    "rename": "account",
+                    "fields": {
+                        "id": { "rename": "account_id" },
+                        "name": { "characters": 20, "regex": "^[A-Za-z ]+$" },
+                        "email": { "format": "email", "hidden": true },
+                        "registered": { "required": true }
+                    }
+                }
+            }
+        }"#).unwrap();
+        overrides.apply(&mut db).unwrap();
+
+        assert!(db.table("user").is_none());
+        let account = db.table("account").unwrap();
+        let name = account.field("name").unwrap();
+        assert_eq!((name.characters, name.regex.as_deref()), (Some(20), Some("^[A-Za-z ]+$")));
+        let email = account.field("email").unwrap();
+        assert!(email.hidden && email.format == Some(Format::Email));
+        assert!(account.field("registered").unwrap().required);
+
+        // Renamed fields keep their column, and keys follow them
+        let id = account.field("account_id").unwrap();
+        assert_eq!(id.column.as_deref(), Some("id"));
+        assert_eq!(account.primary_key().unwrap().fields, vec!["account_id"]);
+        assert_eq!(db.table("post").unwrap().field("author").unwrap().foreign.as_ref().unwrap().to_string(), "account.account_id");
+    }
+
+    #[test]
+    fn missing_test() {
+        let mut db = Database::from_schema("./tests/schema.sql").unwrap();
+        let missing_field: Overrides = serde_json::from_str(r#"{ "tables": { "user": { "fields": { "nickname": { "required": true } } } } }"#).unwrap();
+        assert_eq!(
+            missing_field.apply(&mut db).unwrap_err().message(),
+            "the override for field nickname refers to a field that does not exist in table user"
+        );
+        let missing_table: Overrides = serde_json::from_str(r#"{ "tables": { "users": {} } }"#).unwrap();
+        assert!(missing_table.apply(&mut db).is_err());
+        let duplicate: Overrides = serde_json::from_str(r#"{ "tables": { "user": { "fields": { "name": { "rename": "email" } } } } }"#).unwrap();
+        assert!(duplicate.apply(&mut db).is_err());
+
+        let same_field: Overrides = serde_json::from_str(r#"{
+            "tables": { "user": { "fields": { "name": { "rename": "title" }, "email": { "rename": "title" } } } }
+        }"#).unwrap();
+        assert_eq!(
+            same_field.apply(&mut db).unwrap_err().message(),
+            "failed to rename both fields email and name to title in table user"
+        );
+        let kept_field: Overrides = serde_json::from_str(r#"{
+            "tables": { "user": { "fields": { "name": { "rename": "name" }, "email": { "rename": "name" } } } }
+        }"#).unwrap();
+        assert!(kept_field.apply(&mut db).is_err());
+        let same_table: Overrides = serde_json::from_str(r#"{ "tables": { "user": { "rename": "member" }, "post": { "rename": "member" } } }"#).unwrap();
+        assert_eq!(same_table.apply(&mut db).unwrap_err().message(), "failed to rename both tables post and user to member");
+        let existing_table: Overrides = serde_json::from_str(r#"{ "tables": { "user": { "rename": "post" } } }"#).unwrap();
+        assert!(existing_table.apply(&mut db).is_err());
+
+        // Unknown settings are rejected rather than ignored
+        assert!(serde_json::from_str::<Overrides>(r#"{ "tables": { "user": { "fields": { "name": { "length": 3 } } } } }"#).is_err());
+
+        // Failed overrides leave the design unchanged
+        assert_eq!(db.table("user").unwrap().fields.len(), 8);
+    }
+}
@@ -0,0 +1,116 @@
+use std::fmt::{Display, Formatter};
+use serde::{Serialize,Deserialize};
+
+/// Describes the validation rule that a field failed.
+#[derive(Deserialize, Serialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum Rule {
+    /// The field was missing or null, but is required.
+    Required,
+    /// The JSON value could not be read as the field's type.
+    Type,
+    /// The value is outside of the range its type allows.
+    Range,
+    /// The value has more characters or digits than allowed.
+    Characters,
+    /// The value has more bytes than allowed.
+    Bytes,
+    /// The value has a character its field's character set cannot store.
+    Charset,
+    /// The value failed to match the field's regex.
+    Regex,
+    /// The value does not follow the field's format, such as an email address.
+    Format,
+    /// The value is not part of the field's enum.
+    Enum,
+    /// The value is not part of the field's set.
+    Set,
+    /// The value failed a `CHECK` constraint of the field or table.
+    Check,
+    /// The field's design is invalid, so the value could not be tested.
+    Design
+}
+
+impl Display for Rule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", match self {
+            Rule::Required => "required",
+            Rule::Type => "type",
+            Rule::Range => "range",
+            Rule::Characters => "characters",
+            Rule::Bytes => "bytes",
+            Rule::Charset => "charset",
+            Rule::Regex => "regex",
+            Rule::Format => "format",
+            Rule::Enum => "enum",
+            Rule::Set => "set",
+            Rule::Check => "check",
+            Rule::Design => "design"
+        })
+    }
+}
+
+/// Describes a single field that failed validation.
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
+pub struct FieldFailure {
+    pub field: String,
+    pub rule: Rule,
+    #[serde(skip_serializing_if="Option::is_none")]
+    pub limit: Option<String>,
+    #[serde(skip_serializing_if="Option::is_none")]
+    pub actual: Option<String>,
+    pub message: String
+}
+
+impl Display for FieldFailure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+/// Collects every field failure found while validating a table.
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
+pub struct ValidationReport {
+    pub table: String,
+    pub failures: Vec<FieldFailure>
+}
+
+impl Display for ValidationReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} field(s) failed validation in {}", self.failures.len(), self.table)?;
+        for failure in &self.failures {
+            write!(f, "; {}", failure)?;
+        }
+        Ok(())
+    }
+}
+
+impl ValidationReport {
+    /// Constructs an empty report for the specified table.
+    pub fn new(table: &str) -> Self {
+        ValidationReport {
+            table: table.to_string(),
+            failures: Vec::new()
+        }
+    }
+
+    /// Adds the failure to this report.
+    pub fn add(&mut self, failure: FieldFailure) {
+        self.failures.push(failure);
+    }
+
+    /// Returns true if no failures were found.
+    pub fn is_empty(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Returns the number of failures found.
+    pub fn len(&self) -> usize {
+        self.failures.len()
+    }
+
+    /// Gets the failures found for the specified field.
+    pub fn field(&self, title: &str) -> Vec<&FieldFailure> {
+        self.failures.iter().filter(|failure| failure.field == title).collect()
+    }
+}
@@ -0,0 +1,252 @@
+use std::collections::BTreeMap;
+use std::collections::btree_map::Iter;
+use std::fmt::{Display, Formatter};
+use serde_json::{Map, Value};
+use serde::{Serialize,Deserialize};
+use crate::decimal::Decimal;
+use crate::temporal::{Date, DateTime, Time};
+use crate::types::DataTypeValue;
+
+/// A validated row of data extracted from JSON using a table's design.
+///
+/// Values are ordered by their field title, matching the order of the table design's fields.
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone, Default)]
+pub struct Row {
+    values: BTreeMap<String, DataTypeValue>
+}
+
+impl Display for Row {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{{ ")?;
+        for (index, (title, value)) in self.values.iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}: {}", title, value)?;
+        }
+        write!(f, " }}")
+    }
+}
+
+impl Row {
+    /// Constructs a new, empty row.
+    pub fn new() -> Self {
+        Row {
+            values: BTreeMap::new()
+        }
+    }
+
+    /// Adds the value to this row, replacing any previous value for the field.
+    pub fn insert(&mut self, title: &str, value: DataTypeValue) {
+        self.values.insert(title.to_string(), value);
+    }
+
+    /// Gets a reference to the value of the specified field.
+    pub fn get(&self, title: &str) -> Option<&DataTypeValue> {
+        self.values.get(title)
+    }
+
+    /// Returns true if a value (including an explicit null) was extracted for the specified field.
+    pub fn contains(&self, title: &str) -> bool {
+        self.values.contains_key(title)
+    }
+
+    /// Returns true if the specified field was given an explicit null, rather than a value or nothing.
+    pub fn is_null(&self, title: &str) -> bool {
+        matches!(self.get(title), Some(DataTypeValue::Null))
+    }
+
+    /// Returns the number of values in this row.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns true if the row contains no values.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Iterates over the field titles and values of this row in order.
+    pub fn iter(&self) -> Iter<'_, String, DataTypeValue> {
+        self.values.iter()
+    }
+
+    /// Gets the specified field as a string slice, if it is a string.
+    pub fn string(&self, title: &str) -> Option<&str> {
+        match self.get(title)? {
+            DataTypeValue::String(value) => Some(value),
+            _ => None
+        }
+    }
+
+    /// Gets the specified field as a signed integer, if it is any signed integer type.
+    pub fn signed(&self, title: &str) -> Option<i64> {
+        match self.get(title)? {
+            DataTypeValue::Signed64(value) => Some(*value),
+            DataTypeValue::Signed32(value) => Some(i64::from(*value)),
+            DataTypeValue::Signed16(value) => Some(i64::from(*value)),
+            _ => None
+        }
+    }
+
+    /// Gets the specified field as an unsigned integer, if it is any unsigned integer type.
+    pub fn unsigned(&self, title: &str) -> Option<u64> {
+        match self.get(title)? {
+            DataTypeValue::Unsigned64(value) => Some(*value),
+            DataTypeValue::Unsigned32(value) => Some(u64::from(*value)),
+            DataTypeValue::Unsigned16(value) => Some(u64::from(*value)),
+            DataTypeValue::Byte(value) => Some(u64::from(*value)),
+            DataTypeValue::Bit(value) => Some(*value),
+            _ => None
+        }
+    }
+
+    /// Gets the specified field as a float, if it is a float type.
+    pub fn float(&self, title: &str) -> Option<f64> {
+        match self.get(title)? {
+            DataTypeValue::Float64(value) => Some(*value),
+            DataTypeValue::Float32(value) => Some(f64::from(*value)),
+            _ => None
+        }
+    }
+
+    /// Gets the specified field as an exact decimal, if it is a decimal.
+    pub fn decimal(&self, title: &str) -> Option<&Decimal> {
+        match self.get(title)? {
+            DataTypeValue::Decimal(value) => Some(value),
+            _ => None
+        }
+    }
+
+    /// Gets the specified field as a date, if it is a date.
+    pub fn date(&self, title: &str) -> Option<Date> {
+        match self.get(title)? {
+            DataTypeValue::Date(value) => Some(*value),
+            _ => None
+        }
+    }
+
+    /// Gets the specified field as a time, if it is a time.
+    pub fn time(&self, title: &str) -> Option<Time> {
+        match self.get(title)? {
+            DataTypeValue::Time(value) => Some(*value),
+            _ => None
+        }
+    }
+
+    /// Gets the specified field as a date and time, if it is a datetime or timestamp.
+    pub fn date_time(&self, title: &str) -> Option<DateTime> {
+        match self.get(title)? {
+            DataTypeValue::DateTime(value) => Some(*value),
+            DataTypeValue::Timestamp(value) => Some(*value),
+            _ => None
+        }
+    }
+
+    /// Gets the specified field as a year, if it is a year.
+    pub fn year(&self, title: &str) -> Option<u16> {
+        match self.get(title)? {
+            DataTypeValue::Year(value) => Some(*value),
+            _ => None
+        }
+    }
+
+    /// Gets the specified field as a boolean, if it is a boolean.
+    pub fn boolean(&self, title: &str) -> Option<bool> {
+        match self.get(title)? {
+            DataTypeValue::Boolean(value) => Some(*value),
+            _ => None
+        }
+    }
+
+    /// Gets the specified field as a byte slice, if it is a byte string.
+    pub fn bytes(&self, title: &str) -> Option<&[u8]> {
+        match self.get(title)? {
+            DataTypeValue::ByteString(value) => Some(value),
+            _ => None
+        }
+    }
+
+    /// Gets the specified field as a JSON object, if it is JSON.
+    pub fn json(&self, title: &str) -> Option<&Map<String, Value>> {
+        match self.get(title)? {
+            DataTypeValue::Json(value) => Some(value),
+            _ => None
+        }
+    }
+
+    /// Gets the specified field's enum index, if it is an enum.
+    pub fn enum_index(&self, title: &str) -> Option<u32> {
+        match self.get(title)? {
+            DataTypeValue::Enum(index, _) => Some(*index),
+            _ => None
+        }
+    }
+
+    /// Gets the specified field's enum label, if it is an enum.
+    pub fn enum_label(&self, title: &str) -> Option<&str> {
+        match self.get(title)? {
+            DataTypeValue::Enum(_, label) => Some(label),
+            _ => None
+        }
+    }
+
+    /// Gets the specified field's set members, if it is a set.
+    pub fn set(&self, title: &str) -> Option<&[String]> {
+        match self.get(title)? {
+            DataTypeValue::Set(members) => Some(members),
+            _ => None
+        }
+    }
+
+    /// Gets the specified field's values, if it is an array.
+    pub fn array(&self, title: &str) -> Option<&[DataTypeValue]> {
+        match self.get(title)? {
+            DataTypeValue::Array(values) => Some(values),
+            _ => None
+        }
+    }
+}
+
+impl IntoIterator for Row {
+    type Item = (String, DataTypeValue);
+    type IntoIter = std::collections::btree_map::IntoIter<String, DataTypeValue>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.values.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Row {
+    type Item = (&'a String, &'a DataTypeValue);
+    type IntoIter = Iter<'a, String, DataTypeValue>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.values.iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn getter_test() {
+        let mut row = Row::new();
+        row.insert("id", DataTypeValue::Unsigned32(7));
+        row.insert("name", DataTypeValue::String("Test".to_string()));
+        row.insert("score", DataTypeValue::Signed16(-3));
+
+        assert_eq!(row.unsigned("id"), Some(7));
+        assert_eq!(row.string("name"), Some("Test"));
+        assert_eq!(row.signed("score"), Some(-3));
+
+        // Mismatched types and missing fields are both absent
+        assert_eq!(row.string("id"), None);
+        assert_eq!(row.boolean("missing"), None);
+
+        // Values are ordered by field title
+        let titles: Vec<&String> = row.iter().map(|(title, _)| title).collect();
+        assert_eq!(titles, vec!["id", "name", "score"]);
+    }
+}
@@ -0,0 +1,665 @@
+use std::cmp::Ordering;
+use crate::error::RustractError;
+use crate::types::Dialect;
+use super::cursor::{TokenCursor, syntax_error};
+use super::lexer::{Token, TokenKind, tokenize};
+
+/// A parsed `CHECK` constraint that can be evaluated against a row's values.
+///
+/// Like the database, a check only fails when its condition is false, so a condition that is unknown
+/// (such as one comparing a null value, or calling an unsupported function) passes.
+///
+/// Checks are evaluated like the dialect they were written in. In MySQL, `LENGTH()` counts bytes,
+/// and text is compared without case like its default collations.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct Check {
+    expression: Expr,
+    dialect: Dialect
+}
+
+/// A value used while evaluating a check.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) enum Scalar {
+    Null,
+    Number(f64),
+    Text(String),
+    Boolean(bool),
+    List(Vec<Scalar>)
+}
+
+/// An expression within a check's condition.
+#[derive(Debug, PartialEq, Clone)]
+enum Expr {
+    Literal(Scalar),
+    Column(String),
+    List(Vec<Expr>),
+    Function(String, Vec<Expr>),
+    Negate(Box<Expr>),
+    Arithmetic(Box<Expr>, String, Box<Expr>),
+    Compare(Box<Expr>, Ordering, bool, Box<Expr>),
+    /// Compares the value to each element of a list, such as `x IN (...)` or PostgreSQL's `x = ANY (ARRAY[...])`.
+    Any(Box<Expr>, Box<Expr>, Box<Expr>),
+    Between(Box<Expr>, Box<Expr>, Box<Expr>),
+    Like(Box<Expr>, Box<Expr>, bool),
+    IsNull(Box<Expr>),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>)
+}
+
+impl Check {
+    /// Parses the standard SQL text of a check's condition, written in the specified dialect.
+    pub fn parse(text: &str, dialect: Dialect) -> Result<Check, RustractError> {
+        let mut parser = CheckParser {
+            tokens: tokenize(text, Dialect::PostgreSql)?,
+            pos: 0
+        };
+        let expression = parser.or()?;
+        match parser.peek() {
+            Some(token) => Err(syntax_error(token, &format!("unexpected \"{}\" in check", token.text))),
+            None => Ok(Check { expression, dialect }),
+        }
+    }
+
+    /// Returns the names of the columns this check refers to, in order and without duplicates.
+    pub fn columns(&self) -> Vec<String> {
+        let mut columns = Vec::new();
+        self.expression.columns(&mut columns);
+        columns
+    }
+
+    /// Evaluates this check using the provided column values, returning `None` if the result is unknown.
+    pub fn evaluate<F>(&self, value: F) -> Option<bool>
+    where F: Fn(&str) -> Scalar
+    {
+        self.expression.evaluate(&value, self.dialect).truth()
+    }
+
+    /// Returns true unless this check is false for the provided column values.
+    pub fn passes<F>(&self, value: F) -> bool
+    where F: Fn(&str) -> Scalar
+    {
+        self.evaluate(value) != Some(false)
+    }
+}
+
+impl Scalar {
+    /// Reads this value as a condition, where null is unknown and numbers are true unless they are zero.
+    fn truth(&self) -> Option<bool> {
+        match self {
+            Scalar::Boolean(value) => Some(*value),
+            Scalar::Null | Scalar::List(_) => None,
+            _ => self.number().map(|number| number != 0.0),
+        }
+    }
+
+    /// Reads this value as a number, converting booleans and numeric text.
+    fn number(&self) -> Option<f64> {
+        match self {
+            Scalar::Number(number) => Some(*number),
+            Scalar::Boolean(value) => Some(if *value { 1.0 } else { 0.0 }),
+            Scalar::Text(text) => text.trim().parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// Reads this value as text, writing whole numbers without a fraction.
+    fn text(&self) -> Option<String> {
+        match self {
+            Scalar::Text(text) => Some(text.clone()),
+            Scalar::Number(number) if number.fract() == 0.0 && number.abs() < 1e15 => Some(format!("{}", *number as i64)),
+            Scalar::Number(number) => Some(number.to_string()),
+            Scalar::Boolean(value) => Some(value.to_string()),
+            _ => None,
+        }
+    }
+
+    /// Compares two values, comparing text as text (without case in MySQL) and anything else as numbers.
+    fn compare(&self, other: &Scalar, dialect: Dialect) -> Option<Ordering> {
+        match (self, other) {
+            (Scalar::Text(left), Scalar::Text(right)) if dialect == Dialect::MySql => Some(left.to_lowercase().cmp(&right.to_lowercase())),
+            (Scalar::Text(left), Scalar::Text(right)) => Some(left.cmp(right)),
+            _ => self.number()?.partial_cmp(&other.number()?),
+        }
+    }
+}
+
+impl Expr {
+    /// Collects the columns this expression refers to.
+    fn columns(&self, columns: &mut Vec<String>) {
+        match self {
+            Expr::Column(name) => if !columns.contains(name) {
+                columns.push(name.clone());
+            },
+            Expr::Literal(_) => (),
+            Expr::List(items) | Expr::Function(_, items) => items.iter().for_each(|item| item.columns(columns)),
+            Expr::Negate(value) | Expr::IsNull(value) | Expr::Not(value) => value.columns(columns),
+            Expr::Arithmetic(left, _, right) | Expr::Compare(left, _, _, right) | Expr::Like(left, right, _)
+                | Expr::And(left, right) | Expr::Or(left, right) => {
+                left.columns(columns);
+                right.columns(columns);
+            },
+            Expr::Any(value, comparison, list) | Expr::Between(value, comparison, list) => {
+                value.columns(columns);
+                comparison.columns(columns);
+                list.columns(columns);
+            },
+        }
+    }
+
+    /// Evaluates this expression, following SQL's rules for null values.
+    fn evaluate<F>(&self, value: &F, dialect: Dialect) -> Scalar
+    where F: Fn(&str) -> Scalar
+    {
+        match self {
+            Expr::Literal(literal) => literal.clone(),
+            Expr::Column(name) => value(name),
+            Expr::List(items) => Scalar::List(items.iter().map(|item| item.evaluate(value, dialect)).collect()),
+            Expr::Function(name, args) => {
+                let args: Vec<Scalar> = args.iter().map(|arg| arg.evaluate(value, dialect)).collect();
+                function(name, &args, dialect)
+            },
+            Expr::Negate(inner) => match inner.evaluate(value, dialect).number() {
+                Some(number) => Scalar::Number(-number),
+                None => Scalar::Null,
+            },
+            Expr::Arithmetic(left, operator, right) => arithmetic(&left.evaluate(value, dialect), operator, &right.evaluate(value, dialect)),
+            Expr::Compare(left, ordering, negated, right) => match left.evaluate(value, dialect).compare(&right.evaluate(value, dialect), dialect) {
+                Some(result) => Scalar::Boolean((result == *ordering) != *negated),
+                None => Scalar::Null,
+            },
+            Expr::Any(left, comparison, list) => {
+                // The comparison is a template, with the list's elements compared in place of its right side
+                let left = left.evaluate(value, dialect);
+                let elements = match list.evaluate(value, dialect) {
+                    Scalar::List(elements) => elements,
+                    Scalar::Null => return Scalar::Null,
+                    element => vec![element],
+                };
+                let mut result = Some(false);
+                for element in elements {
+                    let truth = match comparison.as_ref() {
+                        Expr::Compare(_, ordering, negated, _) => left.compare(&element, dialect).map(|result| (result == *ordering) != *negated),
+                        _ => None,
+                    };
+                    result = or(result, truth);
+                }
+                Scalar::from(result)
+            },
+            Expr::Between(inner, low, high) => {
+                let inner = inner.evaluate(value, dialect);
+                let above = inner.compare(&low.evaluate(value, dialect), dialect).map(|result| result != Ordering::Less);
+                let below = inner.compare(&high.evaluate(value, dialect), dialect).map(|result| result != Ordering::Greater);
+                Scalar::from(and(above, below))
+            },
+            Expr::Like(inner, pattern, insensitive) => match (inner.evaluate(value, dialect).text(), pattern.evaluate(value, dialect).text()) {
+                (Some(text), Some(pattern)) if *insensitive || dialect == Dialect::MySql => Scalar::Boolean(like(
+                    &text.to_lowercase().chars().collect::<Vec<char>>(),
+                    &pattern.to_lowercase().chars().collect::<Vec<char>>()
+                )),
+                (Some(text), Some(pattern)) => Scalar::Boolean(like(
+                    &text.chars().collect::<Vec<char>>(),
+                    &pattern.chars().collect::<Vec<char>>()
+                )),
+                _ => Scalar::Null,
+            },
+            Expr::IsNull(inner) => Scalar::Boolean(inner.evaluate(value, dialect) == Scalar::Null),
+            Expr::Not(inner) => Scalar::from(inner.evaluate(value, dialect).truth().map(|truth| !truth)),
+            Expr::And(left, right) => Scalar::from(and(left.evaluate(value, dialect).truth(), right.evaluate(value, dialect).truth())),
+            Expr::Or(left, right) => Scalar::from(or(left.evaluate(value, dialect).truth(), right.evaluate(value, dialect).truth())),
+        }
+    }
+}
+
+impl From<Option<bool>> for Scalar {
+    fn from(truth: Option<bool>) -> Self {
+        match truth {
+            Some(value) => Scalar::Boolean(value),
+            None => Scalar::Null,
+        }
+    }
+}
+
+/// Combines two conditions with SQL's `AND`, where false wins over unknown.
+fn and(left: Option<bool>, right: Option<bool>) -> Option<bool> {
+    match (left, right) {
+        (Some(false), _) | (_, Some(false)) => Some(false),
+        (Some(true), Some(true)) => Some(true),
+        _ => None,
+    }
+}
+
+/// Combines two conditions with SQL's `OR`, where true wins over unknown.
+fn or(left: Option<bool>, right: Option<bool>) -> Option<bool> {
+    match (left, right) {
+        (Some(true), _) | (_, Some(true)) => Some(true),
+        (Some(false), Some(false)) => Some(false),
+        _ => None,
+    }
+}
+
+/// Applies an arithmetic or concatenation operator to two values.
+fn arithmetic(left: &Scalar, operator: &str, right: &Scalar) -> Scalar {
+    if operator == "||" {
+        return match (left.text(), right.text()) {
+            (Some(left), Some(right)) => Scalar::Text(left + &right),
+            _ => Scalar::Null,
+        };
+    }
+    let (left, right) = match (left.number(), right.number()) {
+        (Some(left), Some(right)) => (left, right),
+        _ => return Scalar::Null,
+    };
+    match operator {
+        "+" => Scalar::Number(left + right),
+        "-" => Scalar::Number(left - right),
+        "*" => Scalar::Number(left * right),
+        // Dividing by zero results in null in MySQL
+        "/" | "%" if right == 0.0 => Scalar::Null,
+        "/" => Scalar::Number(left / right),
+        _ => Scalar::Number(left % right),
+    }
+}
+
+/// Calls a supported SQL function, returning null for any other function.
+///
+/// MySQL's `LENGTH()` counts bytes (of UTF-8 text), while other dialects count characters.
+fn function(name: &str, args: &[Scalar], dialect: Dialect) -> Scalar {
+    let text = args.first().and_then(Scalar::text);
+    match (name.to_ascii_lowercase().as_str(), text) {
+        ("length", Some(text)) if dialect == Dialect::MySql => Scalar::Number(text.len() as f64),
+        ("length" | "char_length" | "character_length", Some(text)) => Scalar::Number(text.chars().count() as f64),
+        ("octet_length", Some(text)) => Scalar::Number(text.len() as f64),
+        ("lower", Some(text)) => Scalar::Text(text.to_lowercase()),
+        ("upper", Some(text)) => Scalar::Text(text.to_uppercase()),
+        ("trim", Some(text)) => Scalar::Text(text.trim().to_string()),
+        ("abs", _) => match args.first().and_then(Scalar::number) {
+            Some(number) => Scalar::Number(number.abs()),
+            None => Scalar::Null,
+        },
+        ("coalesce", _) => args.iter().find(|arg| **arg != Scalar::Null).cloned().unwrap_or(Scalar::Null),
+        _ => Scalar::Null,
+    }
+}
+
+/// Matches text against a `LIKE` pattern, where `%` matches any characters and `_` matches one.
+///
+/// Only the last `%` is backtracked to, so matching takes at most the product of the lengths instead of growing with each `%`.
+fn like(text: &[char], pattern: &[char]) -> bool {
+    // Escaped characters are matched literally, so `None` is a `%`, `Some(None)` is a `_` and `Some(Some(c))` is a character
+    let mut parts = Vec::with_capacity(pattern.len());
+    let mut chars = pattern.iter();
+    while let Some(character) = chars.next() {
+        parts.push(match character {
+            '%' => None,
+            '_' => Some(None),
+            '\\' => Some(Some(*chars.next().unwrap_or(character))),
+            character => Some(Some(*character)),
+        });
+    }
+
+    let (mut position, mut part) = (0, 0);
+    let mut last_any: Option<(usize, usize)> = None;
+    while position < text.len() {
+        match parts.get(part) {
+            Some(None) => {
+                part += 1;
+                last_any = Some((part, position));
+            },
+            Some(Some(expected)) if expected.is_none_or(|expected| expected == text[position]) => {
+                part += 1;
+                position += 1;
+            },
+            // Lets the last `%` take one more character and tries again from there
+            _ => match last_any {
+                Some((any_part, any_position)) => {
+                    part = any_part;
+                    position = any_position + 1;
+                    last_any = Some((any_part, position));
+                },
+                None => return false,
+            },
+        }
+    }
+    parts[part..].iter().all(Option::is_none)
+}
+
+/// Walks over the tokens of a check to build its expression.
+struct CheckParser {
+    tokens: Vec<Token>,
+    pos: usize
+}
+
+impl TokenCursor for CheckParser {
+    const SOURCE: &'static str = "check";
+
+    fn tokens(&self) -> &[Token] {
+        &self.tokens
+    }
+
+    fn pos_mut(&mut self) -> &mut usize {
+        &mut self.pos
+    }
+
+    fn pos(&self) -> usize {
+        self.pos
+    }
+}
+
+impl CheckParser {
+    /// Parses conditions joined by `OR`.
+    fn or(&mut self) -> Result<Expr, RustractError> {
+        let mut expression = self.and()?;
+        while self.eat_keyword("OR") {
+            expression = Expr::Or(Box::new(expression), Box::new(self.and()?));
+        }
+        Ok(expression)
+    }
+
+    /// Parses conditions joined by `AND`.
+    fn and(&mut self) -> Result<Expr, RustractError> {
+        let mut expression = self.not()?;
+        while self.eat_keyword("AND") {
+            expression = Expr::And(Box::new(expression), Box::new(self.not()?));
+        }
+        Ok(expression)
+    }
+
+    /// Parses a condition that may be negated by `NOT`.
+    fn not(&mut self) -> Result<Expr, RustractError> {
+        if self.eat_keyword("NOT") {
+            return Ok(Expr::Not(Box::new(self.not()?)));
+        }
+        self.predicate()
+    }
+
+    /// Parses a comparison, or a test such as `IN`, `BETWEEN`, `LIKE` or `IS NULL`.
+    fn predicate(&mut self) -> Result<Expr, RustractError> {
+        let value = self.additive()?;
+        if self.eat_keyword("IS") {
+            let negated = self.eat_keyword("NOT");
+            if !self.eat_keyword("NULL") {
+                return Err(self.error("expected NULL"));
+            }
+            let test = Expr::IsNull(Box::new(value));
+            return Ok(if negated { Expr::Not(Box::new(test)) } else { test });
+        }
+
+        // PostgreSQL writes LIKE as ~~ and NOT LIKE as !~~
+        let mut negated = self.peek_at(1).is_some_and(|token| token.is_keyword("IN") || token.is_keyword("BETWEEN")
+            || token.is_keyword("LIKE") || token.is_keyword("ILIKE")) && self.eat_keyword("NOT");
+        let postgres_like = self.peek().is_some_and(|token| token.is_symbol("~") || token.is_symbol("!"));
+        let test = if self.eat_keyword("IN") {
+            self.expect_symbol("(")?;
+            let mut items = vec![self.or()?];
+            while self.eat_symbol(",") {
+                items.push(self.or()?);
+            }
+            self.expect_symbol(")")?;
+            Expr::Any(Box::new(value), Box::new(comparison(Ordering::Equal, false)), Box::new(Expr::List(items)))
+        } else if self.eat_keyword("BETWEEN") {
+            let low = self.additive()?;
+            if !self.eat_keyword("AND") {
+                return Err(self.error("expected AND"));
+            }
+            Expr::Between(Box::new(value), Box::new(low), Box::new(self.additive()?))
+        } else if self.eat_keyword("LIKE") || self.eat_keyword("ILIKE") || postgres_like {
+            let insensitive = self.tokens[self.pos - 1].is_keyword("ILIKE");
+            if postgres_like {
+                negated = self.eat_symbol("!");
+                for _ in 0..2 {
+                    self.expect_symbol("~")?;
+                }
+            }
+            Expr::Like(Box::new(value), Box::new(self.additive()?), insensitive)
+        } else if let Some((ordering, not_equal)) = self.comparison_operator() {
+            let template = comparison(ordering, not_equal);
+            if self.eat_keyword("ANY") || self.eat_keyword("SOME") {
+                Expr::Any(Box::new(value), Box::new(template), Box::new(self.additive()?))
+            } else if self.eat_keyword("ALL") {
+                // A value matches all elements when it does not fail to match any of them
+                negated = true;
+                Expr::Any(Box::new(value), Box::new(comparison(ordering, !not_equal)), Box::new(self.additive()?))
+            } else {
+                Expr::Compare(Box::new(value), ordering, not_equal, Box::new(self.additive()?))
+            }
+        } else {
+            return Ok(value);
+        };
+        Ok(if negated { Expr::Not(Box::new(test)) } else { test })
+    }
+
+    /// Moves past a comparison operator, returning the ordering it tests for and whether it is negated.
+    ///
+    /// Operators like `<=` are read as the negation of the opposite ordering (such as not greater).
+    fn comparison_operator(&mut self) -> Option<(Ordering, bool)> {
+        let operator = match self.peek() {
+            Some(token) if token.kind == TokenKind::Symbol => match token.text.as_str() {
+                "=" | "==" => (Ordering::Equal, false),
+                "<>" | "!=" => (Ordering::Equal, true),
+                "<" => (Ordering::Less, false),
+                ">=" => (Ordering::Less, true),
+                ">" => (Ordering::Greater, false),
+                "<=" => (Ordering::Greater, true),
+                _ => return None,
+            },
+            _ => return None,
+        };
+        self.pos += 1;
+        Some(operator)
+    }
+
+    /// Parses values joined by `+`, `-` or `||`.
+    fn additive(&mut self) -> Result<Expr, RustractError> {
+        let mut expression = self.multiplicative()?;
+        while let Some(operator) = ["+", "-", "||"].iter().find(|operator| self.peek().is_some_and(|token| token.is_symbol(operator))) {
+            self.pos += 1;
+            expression = Expr::Arithmetic(Box::new(expression), operator.to_string(), Box::new(self.multiplicative()?));
+        }
+        Ok(expression)
+    }
+
+    /// Parses values joined by `*`, `/` or `%`.
+    fn multiplicative(&mut self) -> Result<Expr, RustractError> {
+        let mut expression = self.unary()?;
+        while let Some(operator) = ["*", "/", "%"].iter().find(|operator| self.peek().is_some_and(|token| token.is_symbol(operator))) {
+            self.pos += 1;
+            expression = Expr::Arithmetic(Box::new(expression), operator.to_string(), Box::new(self.unary()?));
+        }
+        Ok(expression)
+    }
+
+    /// Parses a value that may have a sign.
+    fn unary(&mut self) -> Result<Expr, RustractError> {
+        if self.eat_symbol("-") {
+            return Ok(Expr::Negate(Box::new(self.unary()?)));
+        }
+        self.eat_symbol("+");
+        let value = self.primary()?;
+
+        // PostgreSQL casts (such as name::text) do not change the value
+        while self.eat_symbol("::") {
+            self.cast_type()?;
+        }
+        Ok(value)
+    }
+
+    /// Parses a literal, column, function call, array or parenthesized expression.
+    fn primary(&mut self) -> Result<Expr, RustractError> {
+        let token = self.next()?;
+        let next_is_string = self.peek().is_some_and(|next| next.kind == TokenKind::String);
+        Ok(match token.kind {
+            TokenKind::Number => match token.text.parse() {
+                Ok(number) => Expr::Literal(Scalar::Number(number)),
+                Err(_) => return Err(syntax_error(&token, "invalid number")),
+            },
+            TokenKind::String => Expr::Literal(Scalar::Text(token.text)),
+            // MySQL writes string literals with their character set, such as _utf8mb4'value'
+            TokenKind::Word if token.text.starts_with('_') && next_is_string => Expr::Literal(Scalar::Text(self.next()?.text)),
+            TokenKind::Word if token.is_keyword("NULL") => Expr::Literal(Scalar::Null),
+            TokenKind::Word if token.is_keyword("TRUE") => Expr::Literal(Scalar::Boolean(true)),
+            TokenKind::Word if token.is_keyword("FALSE") => Expr::Literal(Scalar::Boolean(false)),
+            TokenKind::Word if token.is_keyword("ARRAY") => {
+                self.expect_symbol("[")?;
+                let mut items = Vec::new();
+                while !self.eat_symbol("]") {
+                    items.push(self.or()?);
+                    if !self.eat_symbol(",") {
+                        self.expect_symbol("]")?;
+                        break;
+                    }
+                }
+                Expr::List(items)
+            },
+            TokenKind::Word if token.is_keyword("CAST") => {
+                self.expect_symbol("(")?;
+                let value = self.or()?;
+                if !self.eat_keyword("AS") {
+                    return Err(self.error("expected AS"));
+                }
+                self.cast_type()?;
+                self.expect_symbol(")")?;
+                value
+            },
+            TokenKind::Word | TokenKind::Identifier if self.eat_symbol("(") => {
+                let mut args = Vec::new();
+                while !self.eat_symbol(")") {
+                    args.push(self.or()?);
+                    if !self.eat_symbol(",") {
+                        self.expect_symbol(")")?;
+                        break;
+                    }
+                }
+                Expr::Function(token.text, args)
+            },
+            TokenKind::Word | TokenKind::Identifier => {
+                // Columns may be qualified by their table
+                let mut name = token.text;
+                while self.eat_symbol(".") {
+                    name = self.next()?.text;
+                }
+                Expr::Column(name)
+            },
+            TokenKind::Symbol if token.is_symbol("(") => {
+                let mut items = vec![self.or()?];
+                while self.eat_symbol(",") {
+                    items.push(self.or()?);
+                }
+                self.expect_symbol(")")?;
+                if items.len() == 1 { items.remove(0) } else { Expr::List(items) }
+            },
+            TokenKind::Symbol => return Err(syntax_error(&token, &format!("unexpected \"{}\" in check", token.text))),
+        })
+    }
+
+    /// Skips the type of a cast, such as `character varying(45)` or `text[]`.
+    fn cast_type(&mut self) -> Result<(), RustractError> {
+        self.next()?;
+        if !self.eat_keyword("VARYING") {
+            self.eat_keyword("PRECISION");
+        }
+        if self.eat_symbol("(") {
+            while !self.eat_symbol(")") {
+                self.next()?;
+            }
+        }
+        while self.eat_symbol("[") {
+            while !self.eat_symbol("]") {
+                self.next()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Creates a comparison for `Expr::Any` to apply to each element of its list.
+fn comparison(ordering: Ordering, negated: bool) -> Expr {
+    Expr::Compare(Box::new(Expr::Literal(Scalar::Null)), ordering, negated, Box::new(Expr::Literal(Scalar::Null)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Evaluates the check using the provided column values.
+    fn evaluate(check: &str, values: &[(&str, Scalar)]) -> Option<bool> {
+        evaluate_in(check, values, Dialect::PostgreSql)
+    }
+
+    /// Evaluates the check using the provided column values, as the specified dialect would.
+    fn evaluate_in(check: &str, values: &[(&str, Scalar)], dialect: Dialect) -> Option<bool> {
+        Check::parse(check, dialect).unwrap().evaluate(|name| {
+            values.iter().find(|(title, _)| *title == name).map(|(_, value)| value.clone()).unwrap_or(Scalar::Null)
+        })
+    }
+
+    #[test]
+    fn comparison_test() {
+        let age = |value: f64| vec![("age", Scalar::Number(value))];
+        assert_eq!(evaluate("age >= 0 AND age < 150", &age(30.0)), Some(true));
+        assert_eq!(evaluate("age >= 0 AND age < 150", &age(150.0)), Some(false));
+        assert_eq!(evaluate("age BETWEEN 18 AND 65", &age(17.0)), Some(false));
+        assert_eq!(evaluate("age NOT BETWEEN 18 AND 65", &age(17.0)), Some(true));
+        assert_eq!(evaluate("-age <= -1 OR age * 2 = 0", &age(0.0)), Some(true));
+
+        // Unknown values pass unless the condition is false either way
+        assert_eq!(evaluate("age >= 0", &[]), None);
+        assert_eq!(evaluate("age >= 0 AND 1 = 0", &[]), Some(false));
+        assert_eq!(evaluate("age >= 0 OR 1 = 1", &[]), Some(true));
+        assert!(Check::parse("NOT (age >= 0)", Dialect::PostgreSql).unwrap().passes(|_| Scalar::Null));
+        assert_eq!(evaluate("age IS NULL OR age > 0", &[]), Some(true));
+    }
+
+    #[test]
+    fn text_test() {
+        let status = |value: &str| vec![("status", Scalar::Text(value.to_string()))];
+        assert_eq!(evaluate("status IN ('active', 'closed')", &status("closed")), Some(true));
+        assert_eq!(evaluate("status NOT IN ('active', 'closed')", &status("closed")), Some(false));
+        assert_eq!(evaluate("length(status) <= 5", &status("closed")), Some(false));
+        assert_eq!(evaluate("char_length(status) = 4", &status("über")), Some(true));
+        assert_eq!(evaluate("status LIKE 'c_os%'", &status("closed")), Some(true));
+        assert_eq!(evaluate("status LIKE '%\\%'", &status("100%")), Some(true));
+        assert_eq!(evaluate("status NOT LIKE 'a%'", &status("closed")), Some(true));
+        assert_eq!(evaluate("status LIKE '%o_e%'", &status("closed")), Some(true));
+        assert_eq!(evaluate("status LIKE 'c%d_'", &status("closed")), Some(false));
+
+        // Patterns with many wildcards still match long text quickly
+        let long = "a".repeat(10_000);
+        assert_eq!(evaluate("status NOT LIKE '%a%a%a%a%a%a%a%a%b'", &status(&long)), Some(true));
+        assert_eq!(evaluate("status LIKE '%a%a%a%a%a%a%a%a%a'", &status(&long)), Some(true));
+
+        // Values outside of the list are false, unless the list has a null
+        assert_eq!(evaluate("status IN ('a', NULL)", &status("b")), None);
+
+        // Unsupported functions are unknown
+        assert_eq!(evaluate("json_valid(status)", &status("{}")), None);
+    }
+
+    #[test]
+    fn dialect_test() {
+        // MySQL writes strings with their character set
+        let check = Check::parse("((\"role\" in (_utf8mb4 'admin', _utf8mb4 'user')) and (\"age\" >= 0))", Dialect::MySql).unwrap();
+        assert_eq!(check.columns(), vec!["role", "age"]);
+        assert!(!check.passes(|name| if name == "role" { Scalar::Text("guest".to_string()) } else { Scalar::Number(1.0) }));
+
+        // PostgreSQL writes IN as a comparison to any element of an array, and LIKE as ~~
+        let check = "((status)::text = ANY ((ARRAY['active'::character varying, 'closed'::character varying])::text[]))";
+        assert_eq!(evaluate(check, &[("status", Scalar::Text("active".to_string()))]), Some(true));
+        assert_eq!(evaluate(check, &[("status", Scalar::Text("open".to_string()))]), Some(false));
+        assert_eq!(evaluate("((code)::text !~~ 'x%'::text)", &[("code", Scalar::Text("xy".to_string()))]), Some(false));
+        assert_eq!(evaluate("price <> ALL (ARRAY[1, 2])", &[("price", Scalar::Number(3.0))]), Some(true));
+        assert_eq!(evaluate("price <> ALL (ARRAY[1, 2])", &[("price", Scalar::Number(2.0))]), Some(false));
+
+        assert!(Check::parse("age >", Dialect::PostgreSql).is_err());
+        assert!(Check::parse("age > 1 1", Dialect::PostgreSql).is_err());
+
+        // MySQL counts the bytes of LENGTH() and compares text without case
+        let name = |value: &str| vec![("name", Scalar::Text(value.to_string()))];
+        assert_eq!(evaluate_in("length(name) <= 4", &name("über"), Dialect::MySql), Some(false));
+        assert_eq!(evaluate_in("char_length(name) <= 4", &name("über"), Dialect::MySql), Some(true));
+        assert_eq!(evaluate_in("length(name) <= 4", &name("über"), Dialect::Sqlite), Some(true));
+        assert_eq!(evaluate_in("name IN ('active')", &name("Active"), Dialect::MySql), Some(true));
+        assert_eq!(evaluate_in("name LIKE 'act%'", &name("Active"), Dialect::MySql), Some(true));
+        assert_eq!(evaluate_in("name IN ('active')", &name("Active"), Dialect::PostgreSql), Some(false));
+    }
+}
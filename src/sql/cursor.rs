@@ -0,0 +1,114 @@
+use crate::error::{RustractError, SyntaxError};
+use super::lexer::Token;
+
+/// Moves through a list of tokens, which the statement and check parsers both read with these helpers.
+pub(super) trait TokenCursor {
+    /// What the tokens were read from, which is named when they end too early (such as "input").
+    const SOURCE: &'static str;
+
+    /// Gets every token being read.
+    fn tokens(&self) -> &[Token];
+
+    /// Gets the position of the current token, which can be moved.
+    fn pos_mut(&mut self) -> &mut usize;
+
+    /// Gets the position of the current token.
+    fn pos(&self) -> usize;
+
+    /// Gets the current token.
+    fn peek(&self) -> Option<&Token> {
+        self.peek_at(0)
+    }
+
+    /// Gets the token at the specified offset from the current token.
+    fn peek_at(&self, offset: usize) -> Option<&Token> {
+        self.tokens().get(self.pos() + offset)
+    }
+
+    /// Moves past the current token and returns it, failing at the end of the source.
+    fn next(&mut self) -> Result<Token, RustractError> {
+        match self.peek().cloned() {
+            Some(token) => {
+                *self.pos_mut() += 1;
+                Ok(token)
+            },
+            None => Err(self.error(&format!("unexpected end of {}", Self::SOURCE))),
+        }
+    }
+
+    /// Creates a syntax error at the current token (or the last token if none are left).
+    fn error(&self, message: &str) -> RustractError {
+        match self.peek().or_else(|| self.tokens().last()) {
+            Some(token) => syntax_error(token, message),
+            None => RustractError::Syntax(SyntaxError {
+                message: message.to_string(),
+                line: 1,
+                column: 1
+            }),
+        }
+    }
+
+    /// Returns true if the current token is the specified keyword.
+    fn at_keyword(&self, keyword: &str) -> bool {
+        self.peek().is_some_and(|token| token.is_keyword(keyword))
+    }
+
+    /// Returns true if the current token is the specified symbol.
+    fn at_symbol(&self, symbol: &str) -> bool {
+        self.peek().is_some_and(|token| token.is_symbol(symbol))
+    }
+
+    /// Moves past the current token if it is the specified keyword.
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let found = self.at_keyword(keyword);
+        if found {
+            *self.pos_mut() += 1;
+        }
+        found
+    }
+
+    /// Moves past the sequence of keywords only if every keyword matches.
+    fn eat_keywords(&mut self, keywords: &[&str]) -> bool {
+        let found = keywords.iter().enumerate().all(|(offset, keyword)| {
+            self.peek_at(offset).is_some_and(|token| token.is_keyword(keyword))
+        });
+        if found {
+            *self.pos_mut() += keywords.len();
+        }
+        found
+    }
+
+    /// Moves past the current token if it is the specified symbol.
+    fn eat_symbol(&mut self, symbol: &str) -> bool {
+        let found = self.at_symbol(symbol);
+        if found {
+            *self.pos_mut() += 1;
+        }
+        found
+    }
+
+    /// Moves past the specified keyword, failing if it is not found.
+    fn expect_keyword(&mut self, keyword: &str) -> Result<(), RustractError> {
+        match self.eat_keyword(keyword) {
+            true => Ok(()),
+            false => Err(self.error(&format!("expected {}", keyword))),
+        }
+    }
+
+    /// Moves past the specified symbol, failing if it is not found.
+    fn expect_symbol(&mut self, symbol: &str) -> Result<(), RustractError> {
+        match self.eat_symbol(symbol) {
+            true => Ok(()),
+            false => Err(self.error(&format!("expected \"{}\"", symbol))),
+        }
+    }
+}
+
+/// Creates a syntax error at the specified token.
+pub(super) fn syntax_error(token: &Token, message: &str) -> RustractError {
+    RustractError::Syntax(SyntaxError {
+        message: message.to_string(),
+        line: token.line,
+        column: token.column
+    })
+}
@@ -0,0 +1,361 @@
+use crate::error::{RustractError, SyntaxError};
+use crate::types::Dialect;
+
+/// Describes the kind of a SQL token.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub(crate) enum TokenKind {
+    /// An unquoted keyword or identifier.
+    Word,
+    /// A quoted identifier, such as `name` (or "name" outside of MySQL).
+    Identifier,
+    /// A string literal, such as 'value'.
+    String,
+    /// A numeric literal.
+    Number,
+    /// Punctuation or an operator.
+    Symbol
+}
+
+/// A single SQL token along with its position in the source.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct Token {
+    pub kind: TokenKind,
+    pub text: String,
+    pub line: usize,
+    pub column: usize
+}
+
+impl Token {
+    /// Returns true if this token is the specified keyword (case insensitive).
+    pub fn is_keyword(&self, keyword: &str) -> bool {
+        self.kind == TokenKind::Word && self.text.eq_ignore_ascii_case(keyword)
+    }
+
+    /// Returns true if this token is the specified symbol.
+    pub fn is_symbol(&self, symbol: &str) -> bool {
+        self.kind == TokenKind::Symbol && self.text == symbol
+    }
+}
+
+/// Operators made of two characters, checked before single character symbols.
+const OPERATORS: [&str; 7] = [">=", "<=", "<>", "!=", "||", "::", "&&"];
+
+/// Splits SQL source into tokens, skipping whitespace and comments.
+///
+/// MySQL's versioned comments (`/*!40101 ... */`) are executed by the database, so their contents are tokenized.
+/// Outside of MySQL, double quotes surround identifiers (as do brackets in SQLite).
+/// In PostgreSQL, psql meta-commands (lines starting with `\`) are skipped.
+pub(crate) fn tokenize(source: &str, dialect: Dialect) -> Result<Vec<Token>, RustractError> {
+    let mut lexer = Lexer {
+        chars: source.chars().collect(),
+        pos: 0,
+        line: 1,
+        column: 1,
+        versioned: 0,
+        dialect
+    };
+    let mut tokens = Vec::new();
+    while let Some(token) = lexer.next_token()? {
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+/// Walks over the characters of a SQL source while tracking the current position.
+struct Lexer {
+    chars: Vec<char>,
+    pos: usize,
+    line: usize,
+    column: usize,
+    versioned: usize,
+    dialect: Dialect
+}
+
+impl Lexer {
+    /// Gets the character at the specified offset from the current position.
+    fn peek(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+
+    /// Moves past the current character, updating the line and column.
+    fn advance(&mut self) -> Option<char> {
+        let character = self.peek(0)?;
+        self.pos += 1;
+        if character == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(character)
+    }
+
+    /// Creates a syntax error at the specified position.
+    fn error(&self, message: &str, line: usize, column: usize) -> RustractError {
+        RustractError::Syntax(SyntaxError {
+            message: message.to_string(),
+            line,
+            column
+        })
+    }
+
+    /// Skips whitespace and comments, returning an error for unterminated comments.
+    fn skip_ignored(&mut self) -> Result<(), RustractError> {
+        while let Some(character) = self.peek(0) {
+            if character.is_whitespace() {
+                self.advance();
+            } else if (character == '#' && self.dialect == Dialect::MySql)
+                || (character == '-' && self.peek(1) == Some('-'))
+                || (character == '\\' && self.column == 1 && self.dialect == Dialect::PostgreSql) {
+                while let Some(next) = self.peek(0) {
+                    if next == '\n' {
+                        break;
+                    }
+                    self.advance();
+                }
+            } else if character == '/' && self.peek(1) == Some('*') {
+                let (line, column) = (self.line, self.column);
+                self.advance();
+                self.advance();
+
+                // Versioned comments contain code, so only the marker and version are skipped
+                if self.peek(0) == Some('!') {
+                    self.advance();
+                    while self.peek(0).is_some_and(|next| next.is_ascii_digit()) {
+                        self.advance();
+                    }
+                    self.versioned += 1;
+                    continue;
+                }
+
+                loop {
+                    match self.peek(0) {
+                        Some('*') if self.peek(1) == Some('/') => {
+                            self.advance();
+                            self.advance();
+                            break;
+                        },
+                        Some(_) => { self.advance(); },
+                        None => return Err(self.error("unterminated comment", line, column)),
+                    }
+                }
+            } else if character == '*' && self.peek(1) == Some('/') && self.versioned > 0 {
+                self.advance();
+                self.advance();
+                self.versioned -= 1;
+            } else {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads the next token, or returns `None` at the end of the source.
+    fn next_token(&mut self) -> Result<Option<Token>, RustractError> {
+        self.skip_ignored()?;
+        let (line, column) = (self.line, self.column);
+        let character = match self.peek(0) {
+            Some(character) => character,
+            None => return Ok(None),
+        };
+
+        let mysql = self.dialect == Dialect::MySql;
+        let postgres = self.dialect == Dialect::PostgreSql;
+        let (kind, text) = if postgres && (character == 'E' || character == 'e') && self.peek(1) == Some('\'') {
+            // PostgreSQL only reads backslash escapes in E'...' strings
+            self.advance();
+            (TokenKind::String, self.read_quoted(TokenKind::String, '\'', true, line, column)?)
+        } else if postgres && character == '$' && self.dollar_tag().is_some() {
+            (TokenKind::String, self.read_dollar_quoted(line, column)?)
+        } else if character.is_alphabetic() || character == '_' {
+            (TokenKind::Word, self.read_while(|next| next.is_alphanumeric() || next == '_' || next == '$'))
+        } else if character.is_ascii_digit() || (character == '.' && self.peek(1).is_some_and(|next| next.is_ascii_digit())) {
+            (TokenKind::Number, self.read_number())
+        } else if character == '`' || (character == '"' && !mysql) {
+            (TokenKind::Identifier, self.read_quoted(TokenKind::Identifier, character, false, line, column)?)
+        } else if character == '[' && self.dialect == Dialect::Sqlite {
+            (TokenKind::Identifier, self.read_quoted(TokenKind::Identifier, ']', false, line, column)?)
+        } else if character == '\'' || character == '"' {
+            (TokenKind::String, self.read_quoted(TokenKind::String, character, mysql, line, column)?)
+        } else {
+            (TokenKind::Symbol, self.read_symbol())
+        };
+
+        Ok(Some(Token { kind, text, line, column }))
+    }
+
+    /// Reads characters for as long as they match the predicate.
+    fn read_while<F>(&mut self, predicate: F) -> String
+    where F: Fn(char) -> bool
+    {
+        let mut text = String::new();
+        while let Some(character) = self.peek(0) {
+            if !predicate(character) {
+                break;
+            }
+            text.push(character);
+            self.advance();
+        }
+        text
+    }
+
+    /// Reads a numeric literal, including any fraction and exponent.
+    fn read_number(&mut self) -> String {
+        let mut text = self.read_while(|next| next.is_ascii_digit());
+        if self.peek(0) == Some('.') {
+            self.advance();
+            text.push('.');
+            text += &self.read_while(|next| next.is_ascii_digit());
+        }
+        if matches!(self.peek(0), Some('e') | Some('E')) {
+            let signed = matches!(self.peek(1), Some('+') | Some('-'));
+            let digit = self.peek(if signed { 2 } else { 1 }).is_some_and(|next| next.is_ascii_digit());
+            if digit {
+                text.push(self.advance().unwrap());
+                if signed {
+                    text.push(self.advance().unwrap());
+                }
+                text += &self.read_while(|next| next.is_ascii_digit());
+            }
+        }
+        text
+    }
+
+    /// Reads a quoted string or identifier, where doubling the quote escapes it.
+    fn read_quoted(&mut self, kind: TokenKind, quote: char, escapes: bool, line: usize, column: usize) -> Result<String, RustractError> {
+        self.advance();
+        let mut text = String::new();
+        loop {
+            match self.advance() {
+                Some(character) if character == quote => {
+                    if self.peek(0) == Some(quote) {
+                        self.advance();
+                        text.push(quote);
+                    } else {
+                        return Ok(text);
+                    }
+                },
+                Some('\\') if escapes => match self.advance() {
+                    Some('n') => text.push('\n'),
+                    Some('t') => text.push('\t'),
+                    Some('r') => text.push('\r'),
+                    Some('0') => text.push('\0'),
+                    Some(other) => text.push(other),
+                    None => break,
+                },
+                Some(character) => text.push(character),
+                None => break,
+            }
+        }
+
+        Err(self.error(
+            if kind == TokenKind::Identifier { "unterminated quoted identifier" } else { "unterminated string literal" },
+            line,
+            column
+        ))
+    }
+
+    /// Gets the tag of a dollar quote (such as `$body$` or `$$`) starting at the current position.
+    fn dollar_tag(&self) -> Option<String> {
+        let mut tag = String::from("$");
+        let mut offset = 1;
+        loop {
+            match self.peek(offset)? {
+                '$' => {
+                    tag.push('$');
+                    return Some(tag);
+                },
+                character if character.is_alphabetic() || character == '_' || (offset > 1 && character.is_ascii_digit()) => {
+                    tag.push(character);
+                },
+                _ => return None,
+            }
+            offset += 1;
+        }
+    }
+
+    /// Reads a PostgreSQL dollar quoted string (such as a function body), which has no escapes.
+    fn read_dollar_quoted(&mut self, line: usize, column: usize) -> Result<String, RustractError> {
+        let tag: Vec<char> = self.dollar_tag().unwrap_or_default().chars().collect();
+        for _ in 0..tag.len() {
+            self.advance();
+        }
+        let mut text = String::new();
+        while self.peek(0).is_some() {
+            if (0..tag.len()).all(|offset| self.peek(offset) == Some(tag[offset])) {
+                for _ in 0..tag.len() {
+                    self.advance();
+                }
+                return Ok(text);
+            }
+            text.push(self.advance().unwrap());
+        }
+        Err(self.error("unterminated string literal", line, column))
+    }
+
+    /// Reads an operator or a single punctuation character.
+    fn read_symbol(&mut self) -> String {
+        for operator in OPERATORS.iter() {
+            let mut chars = operator.chars();
+            if self.peek(0) == chars.next() && self.peek(1) == chars.next() {
+                self.advance();
+                self.advance();
+                return operator.to_string();
+            }
+        }
+        self.advance().unwrap().to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn token_test() {
+        let tokens = tokenize("CREATE TABLE `my table` (\n  `a` varchar(10) DEFAULT 'it''s, (here)' -- a comment\n);", Dialect::MySql).unwrap();
+        let texts: Vec<&str> = tokens.iter().map(|token| token.text.as_str()).collect();
+        assert_eq!(texts, vec!["CREATE", "TABLE", "my table", "(", "a", "varchar", "(", "10", ")", "DEFAULT", "it's, (here)", ")", ";"]);
+        assert_eq!(tokens[2].kind, TokenKind::Identifier);
+        assert_eq!(tokens[10].kind, TokenKind::String);
+
+        // Positions are tracked per line
+        assert_eq!((tokens[4].line, tokens[4].column), (2, 3));
+    }
+
+    #[test]
+    fn comment_test() {
+        let tokens = tokenize("/* skipped */ # skipped\n/*!40101 SET a = 1 */;", Dialect::MySql).unwrap();
+        let texts: Vec<&str> = tokens.iter().map(|token| token.text.as_str()).collect();
+        assert_eq!(texts, vec!["SET", "a", "=", "1", ";"]);
+    }
+
+    #[test]
+    fn postgres_test() {
+        let source = "\\connect app\nCREATE TABLE \"My \"\"Table\"\"\" (a text DEFAULT E'\\'s', b text DEFAULT 'c:\\');\n$fn$ SELECT ';' $fn$";
+        let tokens = tokenize(source, Dialect::PostgreSql).unwrap();
+        let texts: Vec<&str> = tokens.iter().map(|token| token.text.as_str()).collect();
+        assert_eq!(texts, vec![
+            "CREATE", "TABLE", "My \"Table\"", "(", "a", "text", "DEFAULT", "'s", ",", "b", "text", "DEFAULT", "c:\\", ")", ";", " SELECT ';' "
+        ]);
+        assert_eq!(tokens[2].kind, TokenKind::Identifier);
+        assert_eq!(tokens[15].kind, TokenKind::String);
+    }
+
+    #[test]
+    fn sqlite_test() {
+        let tokens = tokenize("CREATE TABLE [a b] (\"c\" TEXT DEFAULT 'd\\', `e` INT);", Dialect::Sqlite).unwrap();
+        let texts: Vec<&str> = tokens.iter().map(|token| token.text.as_str()).collect();
+        assert_eq!(texts, vec!["CREATE", "TABLE", "a b", "(", "c", "TEXT", "DEFAULT", "d\\", ",", "e", "INT", ")", ";"]);
+        assert_eq!(tokens[2].kind, TokenKind::Identifier);
+        assert_eq!(tokens[4].kind, TokenKind::Identifier);
+    }
+
+    #[test]
+    fn error_test() {
+        match tokenize("SELECT\n  'open", Dialect::MySql) {
+            Err(RustractError::Syntax(e)) => assert_eq!((e.line, e.column), (2, 3)),
+            other => panic!("test failed, expected a syntax error but found {:?}", other),
+        }
+    }
+}
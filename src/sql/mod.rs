@@ -0,0 +1,8 @@
+//! Reads SQL schemas into statements that can be used to build a database design.
+mod check;
+mod cursor;
+mod lexer;
+mod parser;
+
+pub(crate) use check::{Check, Scalar};
+pub(crate) use parser::{AlterAction, AlterTable, ColumnDef, CreateTable, CreateView, DefaultExpr, Reference, Statement, TableConstraint, ViewColumn, parse};
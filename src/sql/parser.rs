@@ -0,0 +1,1644 @@
+use crate::error::{RustractError, SyntaxError};
+use crate::field::ReferentialAction;
+use crate::types::Dialect;
+use super::cursor::{TokenCursor, syntax_error};
+use super::lexer::{Token, TokenKind, tokenize};
+
+/// A SQL statement that is relevant to a database design.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) enum Statement {
+    CreateTable(CreateTable),
+    CreateView(CreateView),
+    /// A PostgreSQL `CREATE TYPE ... AS ENUM` statement.
+    CreateEnum { name: String, values: Vec<String> },
+    AlterTable(AlterTable),
+    /// A `DROP TABLE` (or `DROP VIEW`) statement, which fails on missing tables unless it has `IF EXISTS`.
+    DropTable { names: Vec<String>, if_exists: bool },
+    /// A PostgreSQL `DROP INDEX` statement (MySQL's, which names the table, is read as an `ALTER TABLE`).
+    DropIndex { names: Vec<String>, if_exists: bool },
+    /// A MySQL `RENAME TABLE` statement, as pairs of the old and new names.
+    RenameTable(Vec<(String, String)>),
+    /// Any statement that does not affect the design, such as `SET` or `INSERT`.
+    Other
+}
+
+/// A parsed `CREATE TABLE` statement.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct CreateTable {
+    pub name: String,
+    /// Whether the statement has `IF NOT EXISTS`, which keeps a table that already exists.
+    pub if_not_exists: bool,
+    pub columns: Vec<ColumnDef>,
+    pub constraints: Vec<TableConstraint>,
+    /// The table's default character set, from its `DEFAULT CHARSET` or `COLLATE` option.
+    pub charset: Option<String>
+}
+
+/// A parsed `CREATE VIEW` statement, keeping the columns it selects and the tables they are selected from.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct CreateView {
+    pub name: String,
+    /// Whether the statement has `OR REPLACE`, which replaces a view that already exists.
+    pub or_replace: bool,
+    pub columns: Vec<ViewColumn>,
+    pub tables: Vec<ViewTable>
+}
+
+/// A column selected by a view.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct ViewColumn {
+    pub name: String,
+    /// The table (or alias) and column it selects, if it is a plain column rather than an expression.
+    ///
+    /// The column is `*` for a view that selects every column.
+    pub source: Option<(Option<String>, String)>
+}
+
+/// A table a view selects from.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct ViewTable {
+    pub name: String,
+    pub alias: Option<String>,
+    /// Whether the table is on the optional side of an outer join, so its columns may be null.
+    pub nullable: bool
+}
+
+/// Words that end a view's table or column rather than naming an alias.
+const VIEW_KEYWORDS: [&str; 20] = [
+    "ON", "USING", "JOIN", "STRAIGHT_JOIN", "LEFT", "RIGHT", "FULL", "INNER", "OUTER", "CROSS", "NATURAL",
+    "WHERE", "GROUP", "HAVING", "ORDER", "LIMIT", "UNION", "WINDOW", "WITH", "FROM"
+];
+
+/// A parsed `ALTER TABLE` statement, keeping only the changes that affect the design.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct AlterTable {
+    pub name: String,
+    pub actions: Vec<AlterAction>
+}
+
+/// A single change made by an `ALTER TABLE` statement.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) enum AlterAction {
+    AddConstraint(TableConstraint),
+    /// Makes the column increment, such as by setting its default to a sequence.
+    SetIncrement(String),
+    /// Sets or drops (with `None`) the default value of a column.
+    SetDefault { column: String, default: Option<DefaultExpr> },
+    /// Sets or drops (with `None`) the comment of a column, such as with PostgreSQL's `COMMENT ON COLUMN`.
+    SetComment { column: String, comment: Option<String> },
+    AddColumn(ColumnDef),
+    DropColumn(String),
+    /// Replaces a column's definition, such as with MySQL's `MODIFY` (or `CHANGE`, which may also rename it).
+    ModifyColumn { column: String, definition: ColumnDef },
+    /// Changes a column's type, such as with PostgreSQL's `ALTER COLUMN ... TYPE`.
+    SetType { column: String, data_type: SqlType },
+    SetNullable { column: String, nullable: bool },
+    RenameColumn { column: String, name: String },
+    RenameTable(String),
+    DropPrimaryKey,
+    /// Drops an index or unique key by name, such as with MySQL's `DROP INDEX`.
+    DropIndex(String),
+    DropForeignKey(String),
+    /// Drops a constraint of any kind by name, such as with PostgreSQL's `DROP CONSTRAINT` or MySQL's `DROP CHECK`.
+    DropConstraint(String)
+}
+
+/// A column's default value, as written in the schema.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) enum DefaultExpr {
+    Null,
+    String(String),
+    /// A numeric literal (including its sign), with bit and hex literals written as decimal numbers.
+    Number(String),
+    Boolean(bool),
+    /// The time the row is inserted, such as `CURRENT_TIMESTAMP` or `now()`.
+    CurrentTimestamp,
+    /// Any other expression, such as a function call, written in standard SQL.
+    Expression(String)
+}
+
+/// A column's declared type, such as `varchar(45)` or `int unsigned`.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct SqlType {
+    /// The lower case name of the type, with multiple word names shortened (such as `double precision` to `double`).
+    pub name: String,
+    /// The arguments in the type's parenthesis, such as sizes or enum elements.
+    pub args: Vec<String>,
+    pub unsigned: bool,
+    /// Whether the column holds an array of this type, such as `text[]`.
+    pub array: bool
+}
+
+/// A parsed column definition within a `CREATE TABLE` statement.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct ColumnDef {
+    pub name: String,
+    pub data_type: SqlType,
+    /// Whether the column was declared `NULL` or `NOT NULL`, if either.
+    pub nullable: Option<bool>,
+    pub auto_increment: bool,
+    pub primary: bool,
+    pub unique: bool,
+    /// The expressions of the column's `CHECK` constraints.
+    pub checks: Vec<String>,
+    pub references: Option<Reference>,
+    pub default: Option<DefaultExpr>,
+    pub comment: Option<String>,
+    /// The column's character set, from its `CHARACTER SET` or `COLLATE` clause.
+    pub charset: Option<String>,
+    pub line: usize,
+    pub column: usize
+}
+
+/// A table level constraint or index within a `CREATE TABLE` statement.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) enum TableConstraint {
+    PrimaryKey { name: Option<String>, columns: Vec<String> },
+    /// A `UNIQUE` constraint, or an index that may or may not be unique.
+    Index { name: Option<String>, columns: Vec<String>, unique: bool },
+    ForeignKey { name: Option<String>, columns: Vec<String>, reference: Reference },
+    Check { name: Option<String>, expression: String }
+}
+
+/// The target of a foreign key, from a `REFERENCES` clause.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct Reference {
+    pub table: String,
+    /// The referenced columns, which are empty if the clause refers to the primary key.
+    pub columns: Vec<String>,
+    pub on_delete: Option<ReferentialAction>,
+    pub on_update: Option<ReferentialAction>
+}
+
+/// Parses every statement in the provided SQL source.
+pub(crate) fn parse(source: &str, dialect: Dialect) -> Result<Vec<Statement>, RustractError> {
+    let mut parser = Parser {
+        tokens: tokenize(source, dialect)?,
+        pos: 0,
+        dialect
+    };
+    let mut statements = Vec::new();
+    while parser.peek().is_some() {
+        if parser.eat_symbol(";") {
+            continue;
+        }
+        statements.push(parser.statement()?);
+    }
+    Ok(statements)
+}
+
+/// Walks over a list of tokens to build statements.
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    dialect: Dialect
+}
+
+impl TokenCursor for Parser {
+    const SOURCE: &'static str = "input";
+
+    fn tokens(&self) -> &[Token] {
+        &self.tokens
+    }
+
+    fn pos_mut(&mut self) -> &mut usize {
+        &mut self.pos
+    }
+
+    fn pos(&self) -> usize {
+        self.pos
+    }
+}
+
+impl Parser {
+    /// Reads a quoted or unquoted identifier.
+    fn identifier(&mut self) -> Result<String, RustractError> {
+        match self.peek() {
+            Some(token) if token.kind == TokenKind::Word || token.kind == TokenKind::Identifier => {
+                Ok(self.next()?.text)
+            },
+            _ => Err(self.error("expected an identifier")),
+        }
+    }
+
+    /// Reads a string literal.
+    fn string(&mut self) -> Result<String, RustractError> {
+        match self.peek() {
+            Some(token) if token.kind == TokenKind::String => Ok(self.next()?.text),
+            _ => Err(self.error("expected a string")),
+        }
+    }
+
+    /// Reads a possibly qualified name (such as `schema`.`table`) and returns the last part.
+    fn object_name(&mut self) -> Result<String, RustractError> {
+        let mut name = self.identifier()?;
+        while self.eat_symbol(".") {
+            name = self.identifier()?;
+        }
+        Ok(name)
+    }
+
+    /// Skips a balanced group of parenthesis, if the current token opens one.
+    fn skip_group(&mut self) -> Result<(), RustractError> {
+        if !self.at_symbol("(") {
+            return Ok(());
+        }
+        let mut depth = 0;
+        loop {
+            let token = self.next()?;
+            if token.is_symbol("(") {
+                depth += 1;
+            } else if token.is_symbol(")") {
+                depth -= 1;
+                if depth == 0 {
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    /// Skips tokens until the next comma or closing parenthesis of the current list.
+    fn skip_element(&mut self) -> Result<(), RustractError> {
+        while self.peek().is_some() && !self.at_symbol(",") && !self.at_symbol(")") && !self.at_symbol(";") {
+            if self.at_symbol("(") {
+                self.skip_group()?;
+            } else {
+                self.next()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads a parenthesized expression (such as a `CHECK` condition) and returns its contents as standard SQL.
+    ///
+    /// Quoted identifiers are written with double quotes, so the text reads the same in any dialect but MySQL.
+    fn expression(&mut self) -> Result<String, RustractError> {
+        self.expect_symbol("(")?;
+        let mut text = String::new();
+        let mut depth = 0;
+        let mut previous: Option<Token> = None;
+        loop {
+            let token = self.next()?;
+            if token.is_symbol("(") {
+                depth += 1;
+            } else if token.is_symbol(")") {
+                if depth == 0 {
+                    return Ok(text);
+                }
+                depth -= 1;
+            }
+
+            // Spaces separate tokens, except within parenthesis, lists and function calls
+            let joined = match &previous {
+                None => true,
+                Some(last) => last.is_symbol("(") || last.is_symbol(".") || token.is_symbol(")")
+                    || token.is_symbol(",") || token.is_symbol(".")
+                    || (token.is_symbol("(") && last.kind != TokenKind::Symbol && !is_operator_keyword(last)),
+            };
+            if !joined {
+                text.push(' ');
+            }
+            match token.kind {
+                TokenKind::Identifier => text += &format!("\"{}\"", token.text.replace('"', "\"\"")),
+                TokenKind::String => text += &format!("'{}'", token.text.replace('\'', "''")),
+                _ => text += &token.text,
+            }
+            previous = Some(token);
+        }
+    }
+
+    /// Skips the rest of the current statement, including its semicolon.
+    fn skip_statement(&mut self) -> Result<(), RustractError> {
+        while let Some(token) = self.peek() {
+            if token.is_symbol(";") {
+                self.pos += 1;
+                break;
+            } else if token.is_symbol("(") {
+                self.skip_group()?;
+            } else {
+                self.pos += 1;
+            }
+        }
+        Ok(())
+    }
+
+    /// Parses a single statement, skipping any that are not relevant.
+    fn statement(&mut self) -> Result<Statement, RustractError> {
+        let mut statement = Statement::Other;
+        if self.eat_keyword("CREATE") {
+            let or_replace = self.eat_keywords(&["OR", "REPLACE"]);
+            if !self.eat_keyword("TEMPORARY") && !self.eat_keyword("TEMP") {
+                self.eat_keyword("UNLOGGED");
+            }
+
+            // MySQL dumps write a view's algorithm, definer and security before the VIEW keyword
+            if ["ALGORITHM", "DEFINER", "SQL", "RECURSIVE", "MATERIALIZED"].iter().any(|keyword| self.at_keyword(keyword)) {
+                while self.peek().is_some() && !self.at_keyword("VIEW") && !self.at_symbol(";") {
+                    self.pos += 1;
+                }
+            }
+            let unique = self.eat_keyword("UNIQUE");
+            if !unique && !self.eat_keyword("FULLTEXT") {
+                self.eat_keyword("SPATIAL");
+            }
+            if self.eat_keyword("INDEX") {
+                if let Some(table) = self.create_index(unique)? {
+                    statement = Statement::AlterTable(table);
+                }
+            } else if self.eat_keyword("TABLE") {
+                if let Some(table) = self.create_table()? {
+                    statement = Statement::CreateTable(table);
+                }
+            } else if self.eat_keyword("TYPE") {
+                statement = self.create_type()?;
+            } else if self.eat_keyword("VIEW") {
+                if let Some(view) = self.create_view(or_replace)? {
+                    statement = Statement::CreateView(view);
+                }
+            }
+        } else if self.eat_keywords(&["ALTER", "TABLE"]) {
+            if let Some(table) = self.alter_table()? {
+                statement = Statement::AlterTable(table);
+            }
+        } else if self.eat_keywords(&["DROP", "TABLE"]) || self.eat_keywords(&["DROP", "VIEW"]) {
+            let if_exists = self.eat_keywords(&["IF", "EXISTS"]);
+            let mut names = vec![self.object_name()?];
+            while self.eat_symbol(",") {
+                names.push(self.object_name()?);
+            }
+            statement = Statement::DropTable { names, if_exists };
+        } else if self.eat_keywords(&["DROP", "INDEX"]) {
+            statement = self.drop_index()?;
+        } else if self.eat_keywords(&["RENAME", "TABLE"]) {
+            let mut names = Vec::new();
+            loop {
+                let name = self.object_name()?;
+                self.expect_keyword("TO")?;
+                names.push((name, self.object_name()?));
+                if !self.eat_symbol(",") {
+                    break;
+                }
+            }
+            statement = Statement::RenameTable(names);
+        } else if self.eat_keywords(&["COMMENT", "ON", "COLUMN"]) {
+            statement = Statement::AlterTable(self.comment_on_column()?);
+        }
+
+        // Table options and unsupported statements are skipped
+        self.skip_statement()?;
+        Ok(statement)
+    }
+
+    /// Parses the body of a `CREATE TABLE` statement after the `TABLE` keyword.
+    ///
+    /// Returns `None` for tables that copy another (`LIKE` or `AS SELECT`).
+    fn create_table(&mut self) -> Result<Option<CreateTable>, RustractError> {
+        let if_not_exists = self.eat_keywords(&["IF", "NOT", "EXISTS"]);
+        let mut table = CreateTable {
+            name: self.object_name()?,
+            if_not_exists,
+            columns: Vec::new(),
+            constraints: Vec::new(),
+            charset: None
+        };
+        if !self.eat_symbol("(") {
+            return Ok(None);
+        }
+
+        loop {
+            self.table_element(&mut table)?;
+            if !self.eat_symbol(",") {
+                self.expect_symbol(")")?;
+                break;
+            }
+        }
+
+        // Table options other than the character set (such as ENGINE) do not affect the design
+        while self.peek().is_some() && !self.at_symbol(";") {
+            self.eat_keyword("DEFAULT");
+            if let Some(charset) = self.charset()? {
+                table.charset = table.charset.or(Some(charset));
+            } else if self.at_symbol("(") {
+                self.skip_group()?;
+            } else {
+                self.next()?;
+            }
+        }
+        Ok(Some(table))
+    }
+
+    /// Parses a MySQL `CHARACTER SET` (or `CHARSET`) or `COLLATE` option, returning the character set it names.
+    ///
+    /// A collation names its character set before the first underscore, such as `latin1` in `latin1_swedish_ci`.
+    fn charset(&mut self) -> Result<Option<String>, RustractError> {
+        if self.dialect != Dialect::MySql {
+            return Ok(None);
+        }
+        let collation = if self.eat_keywords(&["CHARACTER", "SET"]) || self.eat_keyword("CHARSET") {
+            false
+        } else if self.eat_keyword("COLLATE") {
+            true
+        } else {
+            return Ok(None);
+        };
+        self.eat_symbol("=");
+        let name = self.identifier()?;
+        if collation {
+            Ok(name.split('_').next().map(str::to_string))
+        } else {
+            Ok(Some(name))
+        }
+    }
+
+    /// Parses the body of a `CREATE VIEW` statement after the `VIEW` keyword.
+    ///
+    /// Returns `None` for views whose query is not a `SELECT` (such as one starting with `WITH`).
+    /// Only the first `SELECT` of a `UNION` is read, since it names the view's columns.
+    fn create_view(&mut self, or_replace: bool) -> Result<Option<CreateView>, RustractError> {
+        self.eat_keywords(&["IF", "NOT", "EXISTS"]);
+        let mut view = CreateView {
+            name: self.object_name()?,
+            or_replace,
+            columns: Vec::new(),
+            tables: Vec::new()
+        };
+        let mut names = Vec::new();
+        if self.eat_symbol("(") {
+            loop {
+                names.push(self.identifier()?);
+                if !self.eat_symbol(",") {
+                    self.expect_symbol(")")?;
+                    break;
+                }
+            }
+        }
+
+        // Skips options such as PostgreSQL's `WITH (security_barrier)`
+        while self.peek().is_some() && !self.at_keyword("AS") && !self.at_symbol(";") {
+            self.skip_element()?;
+            self.eat_symbol(",");
+        }
+        self.expect_keyword("AS")?;
+        if !self.eat_keyword("SELECT") {
+            return Ok(None);
+        }
+        if !self.eat_keyword("DISTINCT") && !self.eat_keyword("DISTINCTROW") {
+            self.eat_keyword("ALL");
+        }
+
+        loop {
+            view.columns.push(self.view_column()?);
+            if !self.eat_symbol(",") {
+                break;
+            }
+        }
+        if self.eat_keyword("FROM") {
+            self.view_tables(&mut view.tables)?;
+        }
+
+        // Column names listed after the view's name replace those of the query
+        if names.len() == view.columns.len() {
+            for (column, name) in view.columns.iter_mut().zip(names) {
+                column.name = name;
+            }
+        }
+        Ok(Some(view))
+    }
+
+    /// Parses a column of a view's `SELECT`, along with its alias.
+    fn view_column(&mut self) -> Result<ViewColumn, RustractError> {
+        let start = self.pos;
+        while let Some(token) = self.peek() {
+            if token.is_symbol(",") || token.is_symbol(";") || token.is_symbol(")") || token.is_keyword("FROM") {
+                break;
+            }
+            if token.is_symbol("(") {
+                self.skip_group()?;
+            } else {
+                self.pos += 1;
+            }
+        }
+        let mut tokens = &self.tokens[start..self.pos];
+        if tokens.is_empty() {
+            return Err(self.error("expected a column"));
+        }
+
+        // The alias follows the expression, with or without AS
+        let mut alias = None;
+        if let [rest @ .., as_keyword, last] = tokens {
+            if as_keyword.is_keyword("AS") && !rest.is_empty() && is_name(last) {
+                alias = Some(last.text.clone());
+                tokens = rest;
+            }
+        }
+        if alias.is_none() {
+            if let [rest @ .., before, last] = tokens {
+                let ends_expression = before.is_symbol(")") || matches!(before.kind, TokenKind::String | TokenKind::Number)
+                    || column_reference(&tokens[..tokens.len() - 1]).is_some();
+                let keyword = ["END", "NULL", "TRUE", "FALSE"].iter().any(|keyword| last.is_keyword(keyword));
+                if ends_expression && is_name(last) && !keyword && (before.kind != TokenKind::Symbol || before.is_symbol(")")) {
+                    alias = Some(last.text.clone());
+                    tokens = &tokens[..rest.len() + 1];
+                }
+            }
+        }
+
+        let source = column_reference(tokens);
+        let name = match (alias, &source) {
+            (Some(alias), _) => alias,
+            (None, Some((_, column))) => column.clone(),
+            (None, None) => tokens.iter().map(|token| token.text.as_str()).collect(),
+        };
+        Ok(ViewColumn { name, source })
+    }
+
+    /// Parses the tables of a view's `FROM` clause, up to the end of its joins.
+    ///
+    /// Tables are read through any parenthesized joins, while join conditions and subqueries are skipped.
+    fn view_tables(&mut self, tables: &mut Vec<ViewTable>) -> Result<(), RustractError> {
+        let mut depth = 0;
+        let mut expect_table = true;
+        let mut nullable = false;
+        while let Some(token) = self.peek().cloned() {
+            if token.is_symbol(";") || (token.is_symbol(")") && depth == 0)
+                || ["WHERE", "GROUP", "HAVING", "ORDER", "LIMIT", "UNION", "WINDOW", "WITH"].iter().any(|keyword| token.is_keyword(keyword)) {
+                break;
+            }
+
+            if self.eat_symbol("(") {
+                if self.at_keyword("SELECT") {
+                    // Columns of a subquery cannot be resolved, so only its alias is skipped
+                    self.pos -= 1;
+                    self.skip_group()?;
+                    self.eat_keyword("AS");
+                    if self.peek().is_some_and(|token| is_name(token) && !VIEW_KEYWORDS.iter().any(|keyword| token.is_keyword(keyword))) {
+                        self.pos += 1;
+                    }
+                    expect_table = false;
+                } else {
+                    depth += 1;
+                }
+            } else if self.eat_symbol(")") {
+                depth -= 1;
+            } else if self.eat_symbol(",") {
+                expect_table = true;
+                nullable = false;
+            } else if self.eat_keyword("LEFT") {
+                nullable = true;
+            } else if self.eat_keyword("RIGHT") || self.eat_keyword("FULL") {
+                // The tables before a right join are the optional side, as are both sides of a full join
+                let full = token.is_keyword("FULL");
+                for table in tables.iter_mut() {
+                    table.nullable = true;
+                }
+                nullable = full;
+            } else if self.eat_keyword("JOIN") || self.eat_keyword("STRAIGHT_JOIN") {
+                expect_table = true;
+            } else if self.eat_keyword("ON") || self.eat_keyword("USING") {
+                let joins = ["JOIN", "STRAIGHT_JOIN", "LEFT", "RIGHT", "FULL", "INNER", "CROSS", "NATURAL"];
+                while let Some(token) = self.peek() {
+                    if token.is_symbol(")") || token.is_symbol(",") || token.is_symbol(";")
+                        || joins.iter().chain(&["WHERE", "GROUP", "HAVING", "ORDER", "LIMIT", "UNION", "WINDOW"]).any(|keyword| token.is_keyword(keyword)) {
+                        break;
+                    }
+                    if token.is_symbol("(") {
+                        self.skip_group()?;
+                    } else {
+                        self.pos += 1;
+                    }
+                }
+            } else if expect_table && is_name(&token) {
+                let name = self.object_name()?;
+                self.eat_keyword("AS");
+                let alias = match self.peek() {
+                    Some(token) if is_name(token) && !VIEW_KEYWORDS.iter().any(|keyword| token.is_keyword(keyword)) => Some(self.identifier()?),
+                    _ => None,
+                };
+                tables.push(ViewTable { name, alias, nullable });
+                expect_table = false;
+                nullable = false;
+            } else {
+                self.pos += 1;
+            }
+        }
+        Ok(())
+    }
+
+    /// Parses the body of a `DROP INDEX` statement after the `INDEX` keyword.
+    fn drop_index(&mut self) -> Result<Statement, RustractError> {
+        self.eat_keyword("CONCURRENTLY");
+        let if_exists = self.eat_keywords(&["IF", "EXISTS"]);
+        let mut names = vec![self.object_name()?];
+        if self.eat_keyword("ON") {
+            return Ok(Statement::AlterTable(AlterTable {
+                name: self.object_name()?,
+                actions: vec![AlterAction::DropIndex(names.remove(0))]
+            }));
+        }
+        while self.eat_symbol(",") {
+            names.push(self.object_name()?);
+        }
+        Ok(Statement::DropIndex { names, if_exists })
+    }
+
+    /// Parses the body of a `CREATE TYPE` statement, returning `Statement::Other` for types that are not enums.
+    fn create_type(&mut self) -> Result<Statement, RustractError> {
+        let name = self.object_name()?;
+        if !self.eat_keywords(&["AS", "ENUM"]) {
+            return Ok(Statement::Other);
+        }
+
+        self.expect_symbol("(")?;
+        let mut values = Vec::new();
+        while !self.eat_symbol(")") {
+            match self.next()? {
+                token if token.kind == TokenKind::String => values.push(token.text),
+                _ => return Err(self.error("expected an enum label")),
+            }
+            if !self.eat_symbol(",") {
+                self.expect_symbol(")")?;
+                break;
+            }
+        }
+        Ok(Statement::CreateEnum { name, values })
+    }
+
+    /// Parses the body of a `CREATE INDEX` statement as the matching change to its table.
+    ///
+    /// Returns `None` for partial indexes (with a `WHERE` clause), since they only apply to some rows.
+    fn create_index(&mut self, unique: bool) -> Result<Option<AlterTable>, RustractError> {
+        self.eat_keyword("CONCURRENTLY");
+        self.eat_keywords(&["IF", "NOT", "EXISTS"]);
+        let name = if self.at_keyword("ON") { None } else { Some(self.object_name()?) };
+        self.expect_keyword("ON")?;
+        self.eat_keyword("ONLY");
+        let table = self.object_name()?;
+        let columns = self.index_columns()?;
+
+        let mut partial = false;
+        while let Some(token) = self.peek() {
+            if token.is_symbol(";") {
+                break;
+            }
+            partial |= token.is_keyword("WHERE");
+            if token.is_symbol("(") {
+                self.skip_group()?;
+            } else {
+                self.pos += 1;
+            }
+        }
+        if partial || columns.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(AlterTable {
+            name: table,
+            actions: vec![AlterAction::AddConstraint(TableConstraint::Index { name, columns, unique })]
+        }))
+    }
+
+    /// Parses the body of a PostgreSQL `COMMENT ON COLUMN` statement as the matching change to its table.
+    fn comment_on_column(&mut self) -> Result<AlterTable, RustractError> {
+        // The column is qualified by its table, which may also be qualified by its schema
+        let mut names = vec![self.identifier()?];
+        while self.eat_symbol(".") {
+            names.push(self.identifier()?);
+        }
+        let (column, name) = match (names.pop(), names.pop()) {
+            (Some(column), Some(table)) => (column, table),
+            _ => return Err(self.error("expected a column qualified by its table")),
+        };
+        self.expect_keyword("IS")?;
+        let comment = if self.eat_keyword("NULL") { None } else { Some(self.string()?) };
+        Ok(AlterTable {
+            name,
+            actions: vec![AlterAction::SetComment { column, comment }]
+        })
+    }
+
+    /// Parses the body of an `ALTER TABLE` statement after the `TABLE` keyword.
+    ///
+    /// Returns `None` if none of its changes affect the design (such as changing the owner).
+    fn alter_table(&mut self) -> Result<Option<AlterTable>, RustractError> {
+        self.eat_keywords(&["IF", "EXISTS"]);
+        self.eat_keyword("ONLY");
+        let mut table = AlterTable {
+            name: self.object_name()?,
+            actions: Vec::new()
+        };
+
+        loop {
+            if self.eat_keyword("ADD") {
+                let keyword = match self.peek() {
+                    Some(token) if token.kind == TokenKind::Word => token.text.to_ascii_uppercase(),
+                    _ => String::new(),
+                };
+                match keyword.as_str() {
+                    "CONSTRAINT" | "PRIMARY" | "UNIQUE" | "KEY" | "INDEX" | "FULLTEXT" | "SPATIAL" | "FOREIGN" | "CHECK" => {
+                        if let Some(constraint) = self.table_constraint()? {
+                            table.actions.push(AlterAction::AddConstraint(constraint));
+                        }
+                    },
+                    _ => {
+                        self.eat_keyword("COLUMN");
+                        self.eat_keywords(&["IF", "NOT", "EXISTS"]);
+
+                        // MySQL may add a list of columns at once, such as `ADD (a int, b int)`
+                        if self.eat_symbol("(") {
+                            loop {
+                                table.actions.push(AlterAction::AddColumn(self.column()?));
+                                if !self.eat_symbol(",") {
+                                    self.expect_symbol(")")?;
+                                    break;
+                                }
+                            }
+                        } else {
+                            table.actions.push(AlterAction::AddColumn(self.column()?));
+                        }
+                    },
+                }
+            } else if self.eat_keyword("DROP") {
+                if self.eat_keywords(&["PRIMARY", "KEY"]) {
+                    table.actions.push(AlterAction::DropPrimaryKey);
+                } else if self.eat_keyword("INDEX") || self.eat_keyword("KEY") {
+                    table.actions.push(AlterAction::DropIndex(self.identifier()?));
+                } else if self.eat_keywords(&["FOREIGN", "KEY"]) {
+                    table.actions.push(AlterAction::DropForeignKey(self.identifier()?));
+                } else if self.eat_keyword("CONSTRAINT") || self.eat_keyword("CHECK") {
+                    self.eat_keywords(&["IF", "EXISTS"]);
+                    table.actions.push(AlterAction::DropConstraint(self.identifier()?));
+                } else if !self.at_keyword("DEFAULT") {
+                    self.eat_keyword("COLUMN");
+                    self.eat_keywords(&["IF", "EXISTS"]);
+                    table.actions.push(AlterAction::DropColumn(self.identifier()?));
+                }
+            } else if self.eat_keyword("MODIFY") {
+                self.eat_keyword("COLUMN");
+                let definition = self.column()?;
+                table.actions.push(AlterAction::ModifyColumn { column: definition.name.clone(), definition });
+            } else if self.eat_keyword("CHANGE") {
+                self.eat_keyword("COLUMN");
+                let column = self.identifier()?;
+                table.actions.push(AlterAction::ModifyColumn { column, definition: self.column()? });
+            } else if self.eat_keyword("RENAME") {
+                // Renamed indexes and constraints do not change the design
+                let keyword = ["INDEX", "KEY", "CONSTRAINT"].iter().any(|keyword| self.at_keyword(keyword));
+
+                // PostgreSQL allows leaving out the COLUMN keyword
+                let column = !keyword && self.peek_at(1).is_some_and(|token| token.is_keyword("TO"));
+                if self.eat_keyword("COLUMN") || column {
+                    let column = self.identifier()?;
+                    self.expect_keyword("TO")?;
+                    table.actions.push(AlterAction::RenameColumn { column, name: self.identifier()? });
+                } else if !keyword {
+                    if !self.eat_keyword("TO") {
+                        self.eat_keyword("AS");
+                    }
+                    table.actions.push(AlterAction::RenameTable(self.object_name()?));
+                }
+            } else if self.eat_keyword("ALTER") {
+                self.eat_keyword("COLUMN");
+                let column = self.identifier()?;
+
+                // Sequence defaults and identities both make the column increment
+                if self.eat_keyword("TYPE") || self.eat_keywords(&["SET", "DATA", "TYPE"]) {
+                    table.actions.push(AlterAction::SetType { column, data_type: self.data_type()? });
+                } else if self.eat_keywords(&["SET", "NOT", "NULL"]) {
+                    table.actions.push(AlterAction::SetNullable { column, nullable: false });
+                } else if self.eat_keywords(&["DROP", "NOT", "NULL"]) {
+                    table.actions.push(AlterAction::SetNullable { column, nullable: true });
+                } else if self.eat_keywords(&["SET", "DEFAULT"]) {
+                    table.actions.push(match self.at_keyword("nextval") {
+                        true => AlterAction::SetIncrement(column),
+                        false => AlterAction::SetDefault { column, default: Some(self.default_value()?) },
+                    });
+                } else if self.eat_keywords(&["DROP", "DEFAULT"]) {
+                    table.actions.push(AlterAction::SetDefault { column, default: None });
+                } else if self.eat_keywords(&["ADD", "GENERATED"]) {
+                    table.actions.push(AlterAction::SetIncrement(column));
+                }
+            }
+            self.skip_element()?;
+            if !self.eat_symbol(",") {
+                break;
+            }
+        }
+
+        Ok(if table.actions.is_empty() { None } else { Some(table) })
+    }
+
+    /// Parses a column or a constraint of a `CREATE TABLE` statement.
+    fn table_element(&mut self, table: &mut CreateTable) -> Result<(), RustractError> {
+        let keyword = match self.peek() {
+            Some(token) if token.kind == TokenKind::Word => token.text.to_ascii_uppercase(),
+            _ => String::new(),
+        };
+        match keyword.as_str() {
+            "CONSTRAINT" | "PRIMARY" | "UNIQUE" | "KEY" | "INDEX" | "FULLTEXT" | "SPATIAL" | "FOREIGN" | "CHECK" => {
+                if let Some(constraint) = self.table_constraint()? {
+                    table.constraints.push(constraint);
+                }
+            },
+            _ => table.columns.push(self.column()?),
+        }
+        Ok(())
+    }
+
+    /// Parses a table constraint or index, returning `None` for unsupported constraints.
+    fn table_constraint(&mut self) -> Result<Option<TableConstraint>, RustractError> {
+        let mut name = None;
+        if self.eat_keyword("CONSTRAINT") && !self.at_keyword("PRIMARY") && !self.at_keyword("UNIQUE")
+            && !self.at_keyword("FOREIGN") && !self.at_keyword("CHECK") {
+            name = Some(self.identifier()?);
+        }
+
+        let unique = self.eat_keyword("UNIQUE");
+        let constraint = if !unique && self.eat_keyword("PRIMARY") {
+            self.expect_keyword("KEY")?;
+            Some(TableConstraint::PrimaryKey {
+                name,
+                columns: self.index_columns()?
+            })
+        } else if unique || self.at_keyword("KEY") || self.at_keyword("INDEX")
+            || self.eat_keyword("FULLTEXT") || self.eat_keyword("SPATIAL") {
+            if !self.eat_keyword("KEY") {
+                self.eat_keyword("INDEX");
+            }
+            if !self.at_symbol("(") && !self.at_keyword("USING") {
+                name = Some(self.identifier()?);
+            }
+            let columns = self.index_columns()?;
+            match columns.is_empty() {
+                true => None,
+                false => Some(TableConstraint::Index { name, columns, unique }),
+            }
+        } else if self.eat_keywords(&["FOREIGN", "KEY"]) {
+            let columns = self.index_columns()?;
+            self.expect_keyword("REFERENCES")?;
+            Some(TableConstraint::ForeignKey {
+                name,
+                columns,
+                reference: self.reference()?
+            })
+        } else if self.eat_keyword("CHECK") {
+            // MySQL keeps checks that are not enforced, but they do not limit the data
+            let expression = self.expression()?;
+            match self.eat_keywords(&["NOT", "ENFORCED"]) {
+                true => None,
+                false => Some(TableConstraint::Check { name, expression }),
+            }
+        } else {
+            None
+        };
+
+        self.skip_element()?;
+        Ok(constraint)
+    }
+
+    /// Parses a `REFERENCES` clause after the keyword.
+    fn reference(&mut self) -> Result<Reference, RustractError> {
+        let mut reference = Reference {
+            table: self.object_name()?,
+            columns: Vec::new(),
+            on_delete: None,
+            on_update: None
+        };
+        if self.at_symbol("(") {
+            reference.columns = self.index_columns()?;
+        }
+        loop {
+            if self.eat_keywords(&["ON", "DELETE"]) {
+                reference.on_delete = Some(self.referential_action()?);
+            } else if self.eat_keywords(&["ON", "UPDATE"]) {
+                reference.on_update = Some(self.referential_action()?);
+            } else if self.eat_keyword("MATCH") {
+                self.next()?;
+            } else {
+                break;
+            }
+        }
+        Ok(reference)
+    }
+
+    /// Parses the action of an `ON DELETE` or `ON UPDATE` clause.
+    fn referential_action(&mut self) -> Result<ReferentialAction, RustractError> {
+        if self.eat_keyword("RESTRICT") {
+            Ok(ReferentialAction::Restrict)
+        } else if self.eat_keyword("CASCADE") {
+            Ok(ReferentialAction::Cascade)
+        } else if self.eat_keywords(&["SET", "NULL"]) {
+            Ok(ReferentialAction::SetNull)
+        } else if self.eat_keywords(&["SET", "DEFAULT"]) {
+            Ok(ReferentialAction::SetDefault)
+        } else if self.eat_keywords(&["NO", "ACTION"]) {
+            Ok(ReferentialAction::NoAction)
+        } else {
+            Err(self.error("expected a referential action"))
+        }
+    }
+
+    /// Parses the list of columns of a key, ignoring prefix lengths, sort orders and key parts that are expressions.
+    fn index_columns(&mut self) -> Result<Vec<String>, RustractError> {
+        // Skips the optional index name and type, without passing the end of the statement or definition
+        while !self.at_symbol("(") {
+            if [";", ",", ")"].iter().any(|symbol| self.at_symbol(symbol)) {
+                return Err(self.error("expected a list of key columns"));
+            }
+            self.next()?;
+        }
+        self.expect_symbol("(")?;
+
+        let mut columns = Vec::new();
+        loop {
+            if self.at_symbol("(") {
+                // Functional key parts do not refer to a single column
+                self.skip_group()?;
+            } else {
+                let name = self.identifier()?;
+                // A column may be followed by its prefix length, while anything else in parentheses is a function's arguments
+                let prefix = matches!(
+                    self.tokens.get(self.pos..self.pos + 3),
+                    Some([open, length, close]) if open.is_symbol("(") && length.kind == TokenKind::Number && close.is_symbol(")")
+                );
+                if prefix || !self.at_symbol("(") {
+                    columns.push(name);
+                }
+                self.skip_group()?;
+            }
+            if !self.eat_keyword("ASC") {
+                self.eat_keyword("DESC");
+            }
+            if !self.eat_symbol(",") {
+                self.expect_symbol(")")?;
+                break;
+            }
+        }
+        Ok(columns)
+    }
+
+    /// Parses a column definition.
+    fn column(&mut self) -> Result<ColumnDef, RustractError> {
+        let (line, column) = match self.peek() {
+            Some(token) => (token.line, token.column),
+            None => return Err(self.error("expected a column definition")),
+        };
+        let mut definition = ColumnDef {
+            name: self.identifier()?,
+            data_type: self.data_type()?,
+            nullable: None,
+            auto_increment: false,
+            primary: false,
+            unique: false,
+            checks: Vec::new(),
+            references: None,
+            default: None,
+            comment: None,
+            charset: None,
+            line,
+            column
+        };
+
+        while self.peek().is_some() && !self.at_symbol(",") && !self.at_symbol(")") && !self.at_symbol(";") {
+            if self.eat_keywords(&["NOT", "NULL"]) {
+                definition.nullable = Some(false);
+            } else if self.eat_keyword("NULL") {
+                definition.nullable = Some(true);
+            } else if self.eat_keyword("AUTO_INCREMENT") || self.eat_keyword("AUTOINCREMENT") {
+                definition.auto_increment = true;
+            } else if self.eat_keyword("UNIQUE") {
+                self.eat_keyword("KEY");
+                definition.unique = true;
+            } else if self.eat_keywords(&["PRIMARY", "KEY"]) || self.eat_keyword("KEY") {
+                definition.primary = true;
+            } else if self.eat_keyword("DEFAULT") {
+                // PostgreSQL columns increment by defaulting to the next value of a sequence
+                if self.at_keyword("nextval") {
+                    definition.auto_increment = true;
+                }
+                definition.default = Some(self.default_value()?);
+            } else if self.eat_keyword("CHECK") {
+                let expression = self.expression()?;
+                if !self.eat_keywords(&["NOT", "ENFORCED"]) {
+                    definition.checks.push(expression);
+                }
+            } else if self.eat_keyword("COMMENT") {
+                definition.comment = Some(self.string()?);
+            } else if let Some(charset) = self.charset()? {
+                // A column's character set comes before its collation
+                definition.charset = definition.charset.or(Some(charset));
+            } else if self.eat_keyword("REFERENCES") {
+                // MySQL reads inline references, but does not enforce them
+                let reference = self.reference()?;
+                if self.dialect != Dialect::MySql {
+                    definition.references = Some(reference);
+                }
+            } else if self.eat_keyword("GENERATED") {
+                // Identity columns increment, while the expression of other generated columns is skipped
+                if !self.eat_keyword("ALWAYS") {
+                    self.eat_keywords(&["BY", "DEFAULT"]);
+                }
+                if self.eat_keywords(&["AS", "IDENTITY"]) {
+                    definition.auto_increment = true;
+                }
+            } else if self.at_symbol("(") {
+                self.skip_group()?;
+            } else {
+                // Other options (such as ON UPDATE) do not affect the design
+                self.next()?;
+            }
+        }
+        Ok(definition)
+    }
+
+    /// Parses a column's type name, arguments and attributes.
+    fn data_type(&mut self) -> Result<SqlType, RustractError> {
+        if self.dialect == Dialect::Sqlite {
+            return self.sqlite_type();
+        }
+
+        // User defined types may be quoted or qualified by their schema
+        let mut name = match self.peek() {
+            Some(token) if token.kind == TokenKind::Word || token.kind == TokenKind::Identifier => self.object_name()?.to_ascii_lowercase(),
+            _ => return Err(self.error("expected a data type")),
+        };
+        if name == "double" {
+            self.eat_keyword("PRECISION");
+        } else if (name == "character" || name == "char") && self.eat_keyword("VARYING") {
+            name = "varchar".to_string();
+        } else if name == "bit" && self.eat_keyword("VARYING") {
+            name = "varbit".to_string();
+        }
+        let mut data_type = SqlType {
+            name,
+            args: Vec::new(),
+            unsigned: false,
+            array: false
+        };
+
+        self.type_args(&mut data_type)?;
+
+        // Time zones are read as PostgreSQL's short names, such as timestamptz
+        if self.eat_keywords(&["WITH", "TIME", "ZONE"]) {
+            data_type.name += "tz";
+        } else {
+            self.eat_keywords(&["WITHOUT", "TIME", "ZONE"]);
+        }
+
+        loop {
+            if self.eat_keyword("UNSIGNED") {
+                data_type.unsigned = true;
+            } else if !self.eat_keyword("SIGNED") && !self.eat_keyword("ZEROFILL") {
+                break;
+            }
+        }
+
+        // Array dimensions (such as `[]`, `[3]` or `ARRAY[3]`) do not limit the value in PostgreSQL
+        while self.eat_keyword("ARRAY") || self.at_symbol("[") {
+            data_type.array = true;
+            if self.eat_symbol("[") {
+                while !self.eat_symbol("]") {
+                    self.next()?;
+                }
+            }
+        }
+        Ok(data_type)
+    }
+
+    /// Parses a SQLite column's type, which may be made of any number of words or left out entirely.
+    fn sqlite_type(&mut self) -> Result<SqlType, RustractError> {
+        let mut words = Vec::new();
+        while let Some(token) = self.peek() {
+            let constraint = ["CONSTRAINT", "PRIMARY", "NOT", "NULL", "UNIQUE", "CHECK", "DEFAULT", "COLLATE", "REFERENCES", "GENERATED", "AS"]
+                .iter()
+                .any(|keyword| token.is_keyword(keyword));
+            if token.kind != TokenKind::Word || constraint {
+                break;
+            }
+            words.push(self.next()?.text.to_ascii_lowercase());
+        }
+        let mut data_type = SqlType {
+            name: words.join(" "),
+            args: Vec::new(),
+            unsigned: false,
+            array: false
+        };
+        self.type_args(&mut data_type)?;
+        Ok(data_type)
+    }
+
+    /// Parses the arguments of a type, such as the size in `varchar(45)`.
+    fn type_args(&mut self, data_type: &mut SqlType) -> Result<(), RustractError> {
+        if self.eat_symbol("(") {
+            loop {
+                // Signs are kept with their numbers, such as SQLite's `decimal(+10, 2)`
+                let negative = self.eat_symbol("-");
+                if !negative {
+                    self.eat_symbol("+");
+                }
+                let token = self.next()?;
+                if token.kind == TokenKind::Symbol {
+                    return Err(RustractError::Syntax(SyntaxError {
+                        message: format!("unexpected \"{}\" in the arguments of type {}", token.text, data_type.name),
+                        line: token.line,
+                        column: token.column
+                    }));
+                }
+                data_type.args.push(if negative { format!("-{}", token.text) } else { token.text });
+                if !self.eat_symbol(",") {
+                    self.expect_symbol(")")?;
+                    break;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Parses a column's default value after the `DEFAULT` keyword.
+    fn default_value(&mut self) -> Result<DefaultExpr, RustractError> {
+        let value = if self.at_symbol("(") {
+            DefaultExpr::Expression(self.expression()?)
+        } else {
+            let negative = self.eat_symbol("-");
+            if !negative {
+                self.eat_symbol("+");
+            }
+            let token = self.next()?;
+            match token.kind {
+                TokenKind::Number => DefaultExpr::Number(if negative { format!("-{}", token.text) } else { token.text }),
+                TokenKind::String if !negative => DefaultExpr::String(token.text),
+                TokenKind::Word if !negative && (token.is_keyword("b") || token.is_keyword("x"))
+                    && self.peek().is_some_and(|next| next.kind == TokenKind::String) => {
+                    // Bit and hex literals, such as MySQL's b'1'
+                    let radix = if token.is_keyword("b") { 2 } else { 16 };
+                    let literal = self.next()?;
+                    match u64::from_str_radix(&literal.text, radix) {
+                        Ok(number) => DefaultExpr::Number(number.to_string()),
+                        Err(_) => return Err(syntax_error(&literal, "invalid bit or hex literal")),
+                    }
+                },
+                TokenKind::Word if !negative && token.is_keyword("NULL") => DefaultExpr::Null,
+                TokenKind::Word if !negative && token.is_keyword("TRUE") => DefaultExpr::Boolean(true),
+                TokenKind::Word if !negative && token.is_keyword("FALSE") => DefaultExpr::Boolean(false),
+                TokenKind::Word if !negative && is_current_time(&token) => {
+                    // Fractional seconds (such as CURRENT_TIMESTAMP(3)) and now() arguments are skipped
+                    self.skip_group()?;
+                    DefaultExpr::CurrentTimestamp
+                },
+                TokenKind::Word | TokenKind::Identifier if !negative && (self.at_symbol("(") || self.at_symbol(".")) => {
+                    // Functions may be qualified by their schema, such as public.uuid_generate_v4()
+                    let mut name = token.text;
+                    while self.eat_symbol(".") {
+                        name = format!("{}.{}", name, self.identifier()?);
+                    }
+                    match self.at_symbol("(") {
+                        true => DefaultExpr::Expression(format!("{}({})", name, self.expression()?)),
+                        false => DefaultExpr::Expression(name),
+                    }
+                },
+                TokenKind::Word if !negative => DefaultExpr::Expression(token.text),
+                _ => return Err(syntax_error(&token, "expected a default value")),
+            }
+        };
+
+        // PostgreSQL casts (such as 'none'::character varying) do not change the value
+        while self.eat_symbol("::") {
+            self.data_type()?;
+        }
+        Ok(value)
+    }
+}
+
+/// Returns true if the token is a keyword or function for the current time.
+fn is_current_time(token: &Token) -> bool {
+    ["CURRENT_TIMESTAMP", "CURRENT_DATE", "CURRENT_TIME", "LOCALTIMESTAMP", "LOCALTIME", "NOW"]
+        .iter()
+        .any(|keyword| token.is_keyword(keyword))
+}
+
+/// Returns true if the token is a keyword that can come before a parenthesized expression.
+fn is_operator_keyword(token: &Token) -> bool {
+    ["AND", "OR", "NOT", "IN", "IS", "LIKE", "BETWEEN", "WHEN", "THEN", "ELSE", "EXISTS"]
+        .iter()
+        .any(|keyword| token.is_keyword(keyword))
+}
+
+/// Returns true if the token is an identifier, quoted or not.
+fn is_name(token: &Token) -> bool {
+    token.kind == TokenKind::Word || token.kind == TokenKind::Identifier
+}
+
+/// Reads a plain column reference (such as `u.id`, `schema.user.id` or `*`) into its table and column.
+///
+/// Returns `None` for any other expression.
+fn column_reference(tokens: &[Token]) -> Option<(Option<String>, String)> {
+    if tokens.len().is_multiple_of(2) || tokens.len() > 5 {
+        return None;
+    }
+    let mut names = Vec::new();
+    for (position, token) in tokens.iter().enumerate() {
+        if position % 2 == 1 {
+            if !token.is_symbol(".") {
+                return None;
+            }
+        } else if is_name(token) || (token.is_symbol("*") && position == tokens.len() - 1) {
+            names.push(token.text.clone());
+        } else {
+            return None;
+        }
+    }
+    let column = names.pop()?;
+    Some((names.pop(), column))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn create_table_test() {
+        let statements = parse("
+            DROP TABLE IF EXISTS `user`;
+            CREATE TABLE IF NOT EXISTS `test`.`user` (
+              `id` int unsigned NOT NULL AUTO_INCREMENT,
+              `note` varchar(45)
+                DEFAULT 'not null, (really)' COMMENT 'not null',
+              `type` enum('A b','C,d') NOT NULL,
+              PRIMARY KEY (`id`, `type`),
+              UNIQUE KEY `note_UNIQUE` (`note`(10) ASC)
+            ) ENGINE=InnoDB;
+        ", Dialect::MySql).unwrap();
+        assert_eq!(statements.len(), 2);
+        assert_eq!(statements[0], Statement::DropTable { names: vec!["user".to_string()], if_exists: true });
+
+        let table = match &statements[1] {
+            Statement::CreateTable(table) => table,
+            other => panic!("test failed, expected a table but found {:?}", other),
+        };
+        assert_eq!(table.name, "user");
+        assert_eq!(table.columns.len(), 3);
+        assert!(table.columns[0].data_type.unsigned);
+        assert!(table.columns[0].auto_increment);
+        assert_eq!(table.columns[1].nullable, None);
+        assert_eq!(table.columns[1].data_type.args, vec!["45"]);
+        assert_eq!(table.columns[1].default, Some(DefaultExpr::String("not null, (really)".to_string())));
+        assert_eq!(table.columns[1].comment, Some("not null".to_string()));
+        assert_eq!(table.columns[2].data_type.args, vec!["A b", "C,d"]);
+        assert_eq!((table.columns[2].line, table.columns[2].column), (7, 15));
+        assert_eq!(table.constraints, vec![
+            TableConstraint::PrimaryKey {
+                name: None,
+                columns: vec!["id".to_string(), "type".to_string()]
+            },
+            TableConstraint::Index {
+                name: Some("note_UNIQUE".to_string()),
+                columns: vec!["note".to_string()],
+                unique: true
+            }
+        ]);
+    }
+
+    #[test]
+    fn postgres_test() {
+        let statements = parse("
+            CREATE TYPE public.mood AS ENUM (
+                'sad',
+                'happy'
+            );
+            CREATE TABLE public.person (
+                id integer NOT NULL,
+                name character varying(45) DEFAULT 'none'::character varying NOT NULL,
+                seen timestamp(3) with time zone,
+                feeling public.mood,
+                tags text[],
+                scores double precision ARRAY[3]
+            );
+            ALTER TABLE public.person OWNER TO postgres;
+            ALTER TABLE ONLY public.person ALTER COLUMN id SET DEFAULT nextval('public.person_id_seq'::regclass);
+            ALTER TABLE ONLY public.person
+                ADD CONSTRAINT person_pkey PRIMARY KEY (id);
+            COMMENT ON COLUMN public.person.name IS '@min(1)';
+            CREATE UNIQUE INDEX person_name_idx ON public.person USING btree (lower((name)::text));
+        ", Dialect::PostgreSql).unwrap();
+        assert_eq!(statements.len(), 7);
+        assert_eq!(statements[0], Statement::CreateEnum {
+            name: "mood".to_string(),
+            values: vec!["sad".to_string(), "happy".to_string()]
+        });
+        assert_eq!(statements[2], Statement::Other);
+
+        let table = match &statements[1] {
+            Statement::CreateTable(table) => table,
+            other => panic!("test failed, expected a table but found {:?}", other),
+        };
+        let types: Vec<(&str, bool)> = table.columns.iter()
+            .map(|column| (column.data_type.name.as_str(), column.data_type.array))
+            .collect();
+        assert_eq!(types, vec![
+            ("integer", false), ("varchar", false), ("timestamptz", false), ("mood", false), ("text", true), ("double", true)
+        ]);
+        assert_eq!(table.columns[1].nullable, Some(false));
+        assert_eq!(table.columns[1].default, Some(DefaultExpr::String("none".to_string())));
+        assert_eq!(table.columns[2].data_type.args, vec!["3"]);
+
+        assert_eq!(statements[3], Statement::AlterTable(AlterTable {
+            name: "person".to_string(),
+            actions: vec![AlterAction::SetIncrement("id".to_string())]
+        }));
+        assert_eq!(statements[4], Statement::AlterTable(AlterTable {
+            name: "person".to_string(),
+            actions: vec![AlterAction::AddConstraint(TableConstraint::PrimaryKey {
+                name: Some("person_pkey".to_string()),
+                columns: vec!["id".to_string()]
+            })]
+        }));
+        assert_eq!(statements[5], Statement::AlterTable(AlterTable {
+            name: "person".to_string(),
+            actions: vec![AlterAction::SetComment { column: "name".to_string(), comment: Some("@min(1)".to_string()) }]
+        }));
+
+        // Indexes on expressions do not limit a column's values, so they are skipped
+        assert_eq!(statements[6], Statement::Other);
+    }
+
+    #[test]
+    fn sqlite_test() {
+        let statements = parse("
+            CREATE TABLE [order] (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                note VARYING CHARACTER(255) NOT NULL CHECK (length(note) < 200),
+                anything,
+                total UNSIGNED BIG INT,
+                CONSTRAINT positive CHECK (\"total\" >= 0 AND note IN ('a', 'it''s'))
+            );
+        ", Dialect::Sqlite).unwrap();
+        let table = match &statements[0] {
+            Statement::CreateTable(table) => table,
+            other => panic!("test failed, expected a table but found {:?}", other),
+        };
+        assert_eq!(table.name, "order");
+        let types: Vec<&str> = table.columns.iter().map(|column| column.data_type.name.as_str()).collect();
+        assert_eq!(types, vec!["integer", "varying character", "", "unsigned big int"]);
+        assert!(table.columns[0].primary && table.columns[0].auto_increment);
+        assert_eq!(table.columns[1].data_type.args, vec!["255"]);
+        assert_eq!(table.columns[1].checks, vec!["length(note) < 200"]);
+        assert_eq!(table.constraints, vec![TableConstraint::Check {
+            name: Some("positive".to_string()),
+            expression: "\"total\" >= 0 AND note IN ('a', 'it''s')".to_string()
+        }]);
+    }
+
+    #[test]
+    fn index_test() {
+        let statements = parse("
+            CREATE TABLE person (
+                id integer NOT NULL UNIQUE,
+                first text,
+                last text,
+                CONSTRAINT full_name UNIQUE (first, last),
+                FULLTEXT KEY (last),
+                INDEX by_last USING btree (last DESC)
+            );
+            CREATE UNIQUE INDEX person_last ON public.person USING btree (last);
+            CREATE INDEX IF NOT EXISTS person_partial ON person (first) WHERE (first IS NOT NULL);
+            CREATE INDEX person_prefix ON person (first(10), lower(last));
+        ", Dialect::PostgreSql).unwrap();
+        let table = match &statements[0] {
+            Statement::CreateTable(table) => table,
+            other => panic!("test failed, expected a table but found {:?}", other),
+        };
+        assert!(table.columns[0].unique && !table.columns[0].primary);
+        let strings = |values: &[&str]| values.iter().map(|value| value.to_string()).collect::<Vec<String>>();
+        assert_eq!(table.constraints, vec![
+            TableConstraint::Index { name: Some("full_name".to_string()), columns: strings(&["first", "last"]), unique: true },
+            TableConstraint::Index { name: None, columns: strings(&["last"]), unique: false },
+            TableConstraint::Index { name: Some("by_last".to_string()), columns: strings(&["last"]), unique: false }
+        ]);
+        assert_eq!(statements[1], Statement::AlterTable(AlterTable {
+            name: "person".to_string(),
+            actions: vec![AlterAction::AddConstraint(TableConstraint::Index {
+                name: Some("person_last".to_string()),
+                columns: strings(&["last"]),
+                unique: true
+            })]
+        }));
+        assert_eq!(statements[2], Statement::Other);
+        assert_eq!(statements[3], Statement::AlterTable(AlterTable {
+            name: "person".to_string(),
+            actions: vec![AlterAction::AddConstraint(TableConstraint::Index {
+                name: Some("person_prefix".to_string()),
+                columns: strings(&["first"]),
+                unique: false
+            })]
+        }));
+    }
+
+    #[test]
+    fn alter_test() {
+        let statements = parse("
+            ALTER TABLE `user` ADD COLUMN `age` tinyint unsigned NOT NULL AFTER `name`, DROP `note`,
+                MODIFY `name` varchar(60), CHANGE `type` `kind` int, RENAME COLUMN `email` TO `mail`, RENAME TO `account`;
+            ALTER TABLE person ALTER COLUMN age TYPE bigint USING age::bigint, ALTER age DROP NOT NULL, RENAME first TO given;
+            RENAME TABLE a TO b, c TO d;
+            DROP TABLE IF EXISTS a, b;
+            ALTER TABLE t DROP PRIMARY KEY, DROP INDEX a, DROP KEY b, DROP FOREIGN KEY c, DROP CONSTRAINT d, DROP CHECK e;
+            DROP INDEX i ON t;
+            DROP INDEX IF EXISTS j, k;
+            ALTER TABLE t ADD (a int, b varchar(10) NOT NULL), ADD COLUMN (c date);
+        ", Dialect::MySql).unwrap();
+        let actions = match &statements[0] {
+            Statement::AlterTable(alter) => &alter.actions,
+            other => panic!("test failed, expected an alter table but found {:?}", other),
+        };
+        assert_eq!(actions.len(), 6);
+        match &actions[0] {
+            AlterAction::AddColumn(column) => assert!(column.name == "age" && column.nullable == Some(false)),
+            other => panic!("test failed, expected an added column but found {:?}", other),
+        }
+        assert_eq!(actions[1], AlterAction::DropColumn("note".to_string()));
+        assert!(matches!(&actions[2], AlterAction::ModifyColumn { column, .. } if column == "name"));
+        assert!(matches!(&actions[3], AlterAction::ModifyColumn { column, definition } if column == "type" && definition.name == "kind"));
+        assert_eq!(actions[4], AlterAction::RenameColumn { column: "email".to_string(), name: "mail".to_string() });
+        assert_eq!(actions[5], AlterAction::RenameTable("account".to_string()));
+
+        let actions = match &statements[1] {
+            Statement::AlterTable(alter) => &alter.actions,
+            other => panic!("test failed, expected an alter table but found {:?}", other),
+        };
+        assert!(matches!(&actions[0], AlterAction::SetType { column, data_type } if column == "age" && data_type.name == "bigint"));
+        assert_eq!(actions[1], AlterAction::SetNullable { column: "age".to_string(), nullable: true });
+        assert_eq!(actions[2], AlterAction::RenameColumn { column: "first".to_string(), name: "given".to_string() });
+
+        assert_eq!(statements[2], Statement::RenameTable(vec![
+            ("a".to_string(), "b".to_string()),
+            ("c".to_string(), "d".to_string())
+        ]));
+        assert_eq!(statements[3], Statement::DropTable { names: vec!["a".to_string(), "b".to_string()], if_exists: true });
+
+        let actions = match &statements[4] {
+            Statement::AlterTable(alter) => &alter.actions,
+            other => panic!("test failed, expected an alter table but found {:?}", other),
+        };
+        assert_eq!(actions, &vec![
+            AlterAction::DropPrimaryKey,
+            AlterAction::DropIndex("a".to_string()),
+            AlterAction::DropIndex("b".to_string()),
+            AlterAction::DropForeignKey("c".to_string()),
+            AlterAction::DropConstraint("d".to_string()),
+            AlterAction::DropConstraint("e".to_string())
+        ]);
+        assert_eq!(statements[5], Statement::AlterTable(AlterTable {
+            name: "t".to_string(),
+            actions: vec![AlterAction::DropIndex("i".to_string())]
+        }));
+        assert_eq!(statements[6], Statement::DropIndex { names: vec!["j".to_string(), "k".to_string()], if_exists: true });
+
+        // Each column of a list is added
+        let names: Vec<&str> = match &statements[7] {
+            Statement::AlterTable(alter) => alter.actions.iter().filter_map(|action| match action {
+                AlterAction::AddColumn(column) => Some(column.name.as_str()),
+                _ => None,
+            }).collect(),
+            other => panic!("test failed, expected an alter table but found {:?}", other),
+        };
+        assert_eq!(names, vec!["a", "b", "c"]);
+        assert!(parse("ALTER TABLE t ADD (a int, b int;", Dialect::MySql).is_err());
+    }
+
+    #[test]
+    fn view_test() {
+        let statements = parse("
+            /*!50001 CREATE ALGORITHM=UNDEFINED */
+            /*!50013 DEFINER=`root`@`localhost` SQL SECURITY DEFINER */
+            /*!50001 VIEW `user_posts` AS select `u`.`id` AS `id`,`p`.`title` AS `title`,count(0) AS `total`,`u`.`name` `author`
+                from (`user` `u` left join `post` `p` on((`p`.`author` = `u`.`id`))) group by `u`.`id` */;
+            CREATE OR REPLACE VIEW public.everyone (person_id, label) AS
+                SELECT p.id, upper(p.name) FROM public.person AS p, (SELECT 1) AS one RIGHT OUTER JOIN team USING (id);
+        ", Dialect::MySql).unwrap();
+        let view = match &statements[0] {
+            Statement::CreateView(view) => view,
+            other => panic!("test failed, expected a view but found {:?}", other),
+        };
+        assert_eq!(view.name, "user_posts");
+        let names: Vec<&str> = view.columns.iter().map(|column| column.name.as_str()).collect();
+        assert_eq!(names, vec!["id", "title", "total", "author"]);
+        assert_eq!(view.columns[0].source, Some((Some("u".to_string()), "id".to_string())));
+        assert_eq!(view.columns[2].source, None);
+        assert_eq!(view.columns[3].source, Some((Some("u".to_string()), "name".to_string())));
+        assert_eq!(view.tables, vec![
+            ViewTable { name: "user".to_string(), alias: Some("u".to_string()), nullable: false },
+            ViewTable { name: "post".to_string(), alias: Some("p".to_string()), nullable: true }
+        ]);
+
+        let view = match &statements[1] {
+            Statement::CreateView(view) => view,
+            other => panic!("test failed, expected a view but found {:?}", other),
+        };
+        assert!(view.or_replace);
+        assert_eq!(view.columns[0], ViewColumn { name: "person_id".to_string(), source: Some((Some("p".to_string()), "id".to_string())) });
+        assert_eq!(view.columns[1], ViewColumn { name: "label".to_string(), source: None });
+        assert_eq!(view.tables, vec![
+            ViewTable { name: "person".to_string(), alias: Some("p".to_string()), nullable: true },
+            ViewTable { name: "team".to_string(), alias: None, nullable: false }
+        ]);
+    }
+
+    #[test]
+    fn foreign_key_test() {
+        let statements = parse("
+            CREATE TABLE post (
+                author integer REFERENCES person ON DELETE CASCADE,
+                CONSTRAINT fk_post_topic FOREIGN KEY (topic_id, kind) REFERENCES public.topic (id, kind) MATCH FULL ON UPDATE SET NULL
+            );
+        ", Dialect::PostgreSql).unwrap();
+        let table = match &statements[0] {
+            Statement::CreateTable(table) => table,
+            other => panic!("test failed, expected a table but found {:?}", other),
+        };
+        assert_eq!(table.columns[0].references, Some(Reference {
+            table: "person".to_string(),
+            columns: Vec::new(),
+            on_delete: Some(ReferentialAction::Cascade),
+            on_update: None
+        }));
+        assert_eq!(table.constraints, vec![TableConstraint::ForeignKey {
+            name: Some("fk_post_topic".to_string()),
+            columns: vec!["topic_id".to_string(), "kind".to_string()],
+            reference: Reference {
+                table: "topic".to_string(),
+                columns: vec!["id".to_string(), "kind".to_string()],
+                on_delete: None,
+                on_update: Some(ReferentialAction::SetNull)
+            }
+        }]);
+    }
+
+    #[test]
+    fn default_test() {
+        let statements = parse("
+            CREATE TABLE `setting` (
+              `count` int DEFAULT -1,
+              `flag` bit(3) DEFAULT b'101',
+              `note` text DEFAULT NULL,
+              `on` tinyint(1) NOT NULL DEFAULT TRUE,
+              `changed` timestamp(3) NULL DEFAULT CURRENT_TIMESTAMP(3) ON UPDATE CURRENT_TIMESTAMP(3),
+              `code` varchar(8) DEFAULT (uuid())
+            );
+            ALTER TABLE `setting` ALTER COLUMN `count` SET DEFAULT 0, ALTER `note` DROP DEFAULT;
+        ", Dialect::MySql).unwrap();
+        let table = match &statements[0] {
+            Statement::CreateTable(table) => table,
+            other => panic!("test failed, expected a table but found {:?}", other),
+        };
+        let defaults: Vec<Option<DefaultExpr>> = table.columns.iter().map(|column| column.default.clone()).collect();
+        assert_eq!(defaults, vec![
+            Some(DefaultExpr::Number("-1".to_string())),
+            Some(DefaultExpr::Number("5".to_string())),
+            Some(DefaultExpr::Null),
+            Some(DefaultExpr::Boolean(true)),
+            Some(DefaultExpr::CurrentTimestamp),
+            Some(DefaultExpr::Expression("uuid()".to_string()))
+        ]);
+        assert_eq!(table.columns[3].nullable, Some(false));
+        assert_eq!(statements[1], Statement::AlterTable(AlterTable {
+            name: "setting".to_string(),
+            actions: vec![
+                AlterAction::SetDefault { column: "count".to_string(), default: Some(DefaultExpr::Number("0".to_string())) },
+                AlterAction::SetDefault { column: "note".to_string(), default: None }
+            ]
+        }));
+
+        // pg_dump qualifies functions by their schema
+        let statements = parse("
+            CREATE TABLE public.token (
+                uid uuid DEFAULT public.uuid_generate_v4() NOT NULL,
+                kind text DEFAULT public.default_kind
+            );
+        ", Dialect::PostgreSql).unwrap();
+        let table = match &statements[0] {
+            Statement::CreateTable(table) => table,
+            other => panic!("test failed, expected a table but found {:?}", other),
+        };
+        let defaults: Vec<Option<DefaultExpr>> = table.columns.iter().map(|column| column.default.clone()).collect();
+        assert_eq!(defaults, vec![
+            Some(DefaultExpr::Expression("public.uuid_generate_v4()".to_string())),
+            Some(DefaultExpr::Expression("public.default_kind".to_string()))
+        ]);
+        assert_eq!(table.columns[0].nullable, Some(false));
+    }
+
+    #[test]
+    fn error_test() {
+        match parse("CREATE TABLE `a` (\n  `b` varchar(,)\n);", Dialect::MySql) {
+            Err(RustractError::Syntax(e)) => assert_eq!((e.line, e.column), (2, 15)),
+            other => panic!("test failed, expected a syntax error but found {:?}", other),
+        }
+
+        // Keys without a column list fail where the statement ends, not in the next statement
+        match parse("ALTER TABLE ONLY a ADD CONSTRAINT a_code UNIQUE USING INDEX a_code_idx;
+CREATE TABLE b (
+  id int
+);", Dialect::PostgreSql) {
+            Err(RustractError::Syntax(e)) => assert_eq!((e.message.as_str(), e.line, e.column), ("expected a list of key columns", 1, 71)),
+            other => panic!("test failed, expected a syntax error but found {:?}", other),
+        }
+    }
+}
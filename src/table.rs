@@ -1,18 +1,45 @@
 use std::collections::{BTreeMap,HashSet};
 use std::fmt::{Display, Formatter};
+use std::future::Future;
 use serde_json::Value;
 use serde::{Serialize,Deserialize};
 use crate::error::{RustractError, GenericError};
-use crate::field::FieldDesign;
+use crate::field::{FieldDesign, ForeignKey};
 use crate::field::enum_name;
+use crate::report::{FieldFailure, Rule, ValidationReport};
+use crate::row::Row;
+use crate::sql::{Check, Scalar};
 use crate::types::capitalize;
-use crate::types::DataType;
+use crate::types::{DataType, DataTypeValue, Dialect};
 
 /// Describes a database table's design.
 #[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
 pub struct TableDesign {
     pub table_design_title: String,
-    pub fields: BTreeMap<String, FieldDesign>
+    pub fields: BTreeMap<String, FieldDesign>,
+    /// The expressions of the table's `CHECK` constraints, written in standard SQL.
+    #[serde(default, skip_serializing_if="Vec::is_empty")]
+    pub checks: Vec<String>,
+    /// The table's primary key, unique constraints and other indexes.
+    #[serde(default, skip_serializing_if="Vec::is_empty")]
+    pub indexes: Vec<Index>,
+    /// Whether the table is a view, which is read from but not written to, so it has no input type.
+    #[serde(default)]
+    pub read_only: bool,
+    /// The dialect of the schema the table was read from, which its checks are evaluated in.
+    #[serde(default)]
+    pub dialect: Dialect
+}
+
+/// Describes an index of a table, such as its primary key or a unique constraint.
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
+pub struct Index {
+    #[serde(skip_serializing_if="Option::is_none")]
+    pub name: Option<String>,
+    /// The titles of the indexed fields, in order.
+    pub fields: Vec<String>,
+    pub unique: bool,
+    pub primary: bool
 }
 
 impl Display for TableDesign {
@@ -25,13 +52,17 @@ impl TableDesign {
     pub fn new(title: &str) -> Self {
         TableDesign {
             table_design_title: String::from(title),
-            fields: BTreeMap::new()
+            fields: BTreeMap::new(),
+            checks: Vec::new(),
+            indexes: Vec::new(),
+            read_only: false,
+            dialect: Dialect::MySql
         }
     }
 
     /// Tests the provided JSON values against this table's design.
     /// 
-    /// Ignores the required check for any fields marked as generated if input is true.
+    /// Ignores the required check for any fields marked as generated (or with a default) if input is true.
     pub fn test(&self, fields: &[Value], input: bool) -> Result<(), RustractError> {
         // Iterates over the fields in this design and attempts to match each to the JSON
         for key in self.fields.keys() {
@@ -48,19 +79,185 @@ impl TableDesign {
             }
 
             // If a required field is missing in the request JSON, decline it
-            if !matched && field_design.required && (!field_design.generated || !input) {
-                return Err(RustractError::Table(GenericError {
-                    message: format!(
-                        "the {} field is required in {}, but was not included in the request",
-                        field_design.field_design_title,
-                        self.table_design_title
-                    ),
-                }));
+            if !matched && field_design.required && (!(field_design.generated || field_design.has_default()) || !input) {
+                return Err(self.missing_error(field_design));
             }
         }
         Ok(())
     }
 
+    /// Extracts each of this table's fields from the provided JSON object into a row.
+    ///
+    /// Fields marked as generated or with a default may be omitted, since the database will create them.
+    /// This stops at the first invalid field, use `validate` to find every invalid field.
+    pub fn extract(&self, json: &Value) -> Result<Row, RustractError> {
+        let object = self.object(json)?;
+        let mut row = Row::new();
+        for field_design in self.fields.values() {
+            match object.get(&field_design.field_design_title) {
+                Some(value) => row.insert(&field_design.field_design_title, field_design.extract(value)?),
+                None => if field_design.required && !field_design.generated && !field_design.has_default() {
+                    return Err(self.missing_error(field_design));
+                }
+            }
+        }
+        if let Some(failure) = self.test_checks(&row).into_iter().next() {
+            return Err(failure.into());
+        }
+        Ok(row)
+    }
+
+    /// Extracts a row like `extract`, then checks that each foreign key value exists using the provided hook.
+    ///
+    /// The hook is given each foreign key and its extracted value (usually to query the referenced table),
+    /// and should return `Ok(false)` if the referenced row does not exist.
+    pub async fn extract_with<F, Fut>(&self, json: &Value, exists: F) -> Result<Row, RustractError>
+    where
+        F: Fn(ForeignKey, DataTypeValue) -> Fut,
+        Fut: Future<Output = Result<bool, RustractError>>
+    {
+        let row = self.extract(json)?;
+        for field_design in self.fields.values() {
+            if let (Some(key), Some(value)) = (&field_design.foreign, row.get(&field_design.field_design_title)) {
+                // Null foreign keys do not refer to a row
+                if *value != DataTypeValue::Null && !exists(key.clone(), value.clone()).await? {
+                    return Err(RustractError::Field(GenericError {
+                        message: format!(
+                            "field {} refers to {}, but no {} with the value {} exists",
+                            field_design.field_design_title,
+                            key,
+                            key.table,
+                            value
+                        ),
+                    }));
+                }
+            }
+        }
+        Ok(row)
+    }
+
+    /// Extracts each of this table's fields from the provided JSON object into a row.
+    ///
+    /// Unlike `extract`, every field is tested before returning.
+    /// If any fields are invalid, a `RustractError::Validation` containing all of the failures is returned.
+    pub fn validate(&self, json: &Value) -> Result<Row, RustractError> {
+        let object = self.object(json)?;
+        let mut row = Row::new();
+        let mut report = ValidationReport::new(&self.table_design_title);
+        for field_design in self.fields.values() {
+            match object.get(&field_design.field_design_title) {
+                Some(value) => match field_design.validate(value) {
+                    Ok(data) => row.insert(&field_design.field_design_title, data),
+                    Err(failure) => report.add(failure),
+                },
+                None => if field_design.required && !field_design.generated && !field_design.has_default() {
+                    report.add(FieldFailure {
+                        field: field_design.field_design_title.clone(),
+                        rule: Rule::Required,
+                        limit: None,
+                        actual: None,
+                        message: self.missing_error(field_design).message()
+                    });
+                }
+            }
+        }
+
+        for failure in self.test_checks(&row) {
+            report.add(failure);
+        }
+
+        if report.is_empty() {
+            Ok(row)
+        } else {
+            Err(report.into())
+        }
+    }
+
+    /// Tests the row against this table's `CHECK` constraints, returning a failure for each one that is false.
+    ///
+    /// Missing fields are tested with their default value, or as null if it is not known.
+    fn test_checks(&self, row: &Row) -> Vec<FieldFailure> {
+        let mut failures = Vec::new();
+        for text in &self.checks {
+            let check = match Check::parse(text, self.dialect) {
+                Ok(check) => check,
+                Err(e) => {
+                    failures.push(FieldFailure {
+                        field: self.table_design_title.clone(),
+                        rule: Rule::Design,
+                        limit: Some(text.clone()),
+                        actual: None,
+                        message: format!("table {} has an invalid check: {}", self.table_design_title, e.message())
+                    });
+                    continue;
+                },
+            };
+            let field = |name: &str| self.fields.values().find(|field| field.column_name().eq_ignore_ascii_case(name));
+            let passed = check.passes(|name| {
+                let field = match field(name) {
+                    Some(field) => field,
+                    None => return Scalar::Null,
+                };
+                match row.get(&field.field_design_title).cloned().or_else(|| field.default_value()) {
+                    Some(value) => field.check_value(&value),
+                    None => Scalar::Null,
+                }
+            });
+            if !passed {
+                // The failure is reported for the first field the check refers to
+                let title = check.columns().iter()
+                    .find_map(|name| field(name))
+                    .map_or_else(|| self.table_design_title.clone(), |field| field.field_design_title.clone());
+                failures.push(FieldFailure {
+                    field: title,
+                    rule: Rule::Check,
+                    limit: Some(text.clone()),
+                    actual: None,
+                    message: format!("table {} failed the check {}", self.table_design_title, text)
+                });
+            }
+        }
+        failures
+    }
+
+    /// Fills each field missing from the row with its default value, so the row matches what the database stores.
+    ///
+    /// Defaults that are `NULL` or expressions only the database can evaluate are left out.
+    pub fn fill_defaults(&self, row: &mut Row) {
+        for field_design in self.fields.values() {
+            if !row.contains(&field_design.field_design_title) {
+                if let Some(value) = field_design.default_value() {
+                    row.insert(&field_design.field_design_title, value);
+                }
+            }
+        }
+    }
+
+    /// Reads the provided JSON as an object of fields.
+    fn object<'a>(&self, json: &'a Value) -> Result<&'a serde_json::Map<String, Value>, RustractError> {
+        match json.as_object() {
+            Some(object) => Ok(object),
+            None => Err(RustractError::Table(GenericError {
+                message: format!(
+                    "expected a JSON object for the {} table, but found: {}",
+                    self.table_design_title,
+                    json
+                ),
+            })),
+        }
+    }
+
+    /// Creates the error for a required field that was missing from the request.
+    fn missing_error(&self, field_design: &FieldDesign) -> RustractError {
+        RustractError::Table(GenericError {
+            message: format!(
+                "the {} field is required in {}, but was not included in the request",
+                field_design.field_design_title,
+                self.table_design_title
+            ),
+        })
+    }
+
     /// Saves the configuration info to a JSON file for quick loading.
     pub fn save(&self, filepath: &str) -> Result<(), RustractError> {
         std::fs::write(
@@ -81,6 +278,23 @@ impl TableDesign {
 
     }
 
+    /// Adds the provided index to this table.
+    ///
+    /// The field of a single column unique index (or primary key) is also marked as unique.
+    pub fn add_index(&mut self, index: Index) {
+        if let [title] = &index.fields[..] {
+            if let Some(field) = self.fields.get_mut(title) {
+                field.unique |= index.unique;
+            }
+        }
+        self.indexes.push(index);
+    }
+
+    /// Gets a reference to this table's primary key, if it has one.
+    pub fn primary_key(&self) -> Option<&Index> {
+        self.indexes.iter().find(|index| index.primary)
+    }
+
     /// Gets a reference to the specified field by its title.
     ///
     /// If there is a duplicate, the first is returned.
@@ -118,8 +332,8 @@ impl TableDesign {
         second_output += &format!("/** Generated database type for the {} table. (Input version) */\n", title);
         second_output += &format!("export interface {}Input {{\n", title);
 
-        // Exports each field to this file
-        for field in self.fields.values() {
+        // Exports each field to this file, leaving out hidden fields
+        for field in self.fields.values().filter(|field| !field.hidden) {
             // Handles custom type names
             output += &if field.datatype == DataType::Enum {
                 field.export(false, Some(&enum_name(
@@ -139,10 +353,12 @@ impl TableDesign {
             };
         }
 
+        // Views are only read, so they have no input version
         output += "}\n\n";
-        second_output += "}\n";
-        output += &second_output;
-        output += "\n";
+        second_output += "}\n\n";
+        if !self.read_only {
+            output += &second_output;
+        }
 
         // Creates any custom types that are needed
         output += &self.create_names()?;
@@ -160,7 +376,7 @@ impl TableDesign {
         let mut seen_enums: HashSet<Vec<String>> = HashSet::new();
         
         // Check if fields are enums and create any missing types
-        for field in self.fields.values() {
+        for field in self.fields.values().filter(|field| !field.hidden) {
             // Ignore non-enum types
             if field.datatype == DataType::Enum {
                 if let Some(set) = &field.enum_set {
@@ -188,6 +404,7 @@ impl TableDesign {
 mod test {
     use super::*;
     use crate::{filesystem::{_delete_file, read_file}, types::DataType};
+    use crate::field::DefaultValue;
 
     #[test]
     fn table_test() {
@@ -222,6 +439,128 @@ mod test {
         table_design.test(fields, true).unwrap();
     }
 
+    #[test]
+    fn table_extract_test() {
+        let table_design = default_table();
+        let json = serde_json::json!({
+            "email": "test@test.com",
+            "name": "User"
+        });
+        let row = table_design.extract(&json).unwrap();
+        assert_eq!(row.string("email"), Some("test@test.com"));
+        assert_eq!(row.string("name"), Some("User"));
+
+        // The generated id may be omitted, but the required email may not
+        assert!(!row.contains("id"));
+        assert!(table_design.extract(&serde_json::json!({ "name": "User" })).is_err());
+
+        // Explicit nulls are kept apart from missing fields, but only for fields that may be null
+        let row = table_design.extract(&serde_json::json!({ "email": "test@test.com", "name": null })).unwrap();
+        assert!(row.is_null("name"));
+        assert!(!row.is_null("id"));
+        let report = match table_design.validate(&serde_json::json!({ "email": null })) {
+            Err(RustractError::Validation(report)) => report,
+            other => panic!("expected a validation report, got {:?}", other),
+        };
+        assert_eq!(report.failures[0].rule, Rule::Required);
+
+        // Invalid fields and non-object JSON are rejected
+        assert!(table_design.extract(&serde_json::json!({ "email": "bad_email" })).is_err());
+        assert!(table_design.extract(&serde_json::json!(["test@test.com"])).is_err());
+    }
+
+    #[test]
+    fn table_validate_test() {
+        let table_design = default_table();
+        let json = serde_json::json!({
+            "id": "not a number",
+            "name": "This name is far too long for the name field, which only allows one hundred characters in total, okay"
+        });
+        let report = match table_design.validate(&json) {
+            Err(RustractError::Validation(report)) => report,
+            other => panic!("test failed, expected a validation report but found {:?}", other),
+        };
+
+        // Every invalid or missing field is reported in one pass
+        assert_eq!(report.len(), 3);
+        assert_eq!(report.field("id")[0].rule, Rule::Type);
+        assert_eq!(report.field("email")[0].rule, Rule::Required);
+        let name = report.field("name")[0];
+        assert_eq!(name.rule, Rule::Characters);
+        assert_eq!(name.limit, Some("100".to_string()));
+        assert_eq!(name.actual, Some("101".to_string()));
+
+        // Valid rows are still returned
+        let row = table_design.validate(&serde_json::json!({ "email": "test@test.com" })).unwrap();
+        assert_eq!(row.string("email"), Some("test@test.com"));
+    }
+
+    #[test]
+    fn table_default_test() {
+        let mut table_design = default_table();
+        table_design.field_mut("email").unwrap().default = Some(DefaultValue::Value(DataTypeValue::String("none@test.com".to_string())));
+        let mut joined = FieldDesign::new("joined");
+        joined.datatype = DataType::DateTime;
+        joined.required = true;
+        joined.default = Some(DefaultValue::CurrentTimestamp);
+        table_design.add(joined);
+
+        // Fields with a default may be left out, and are only filled when asked
+        let mut row = table_design.extract(&serde_json::json!({ "name": "User" })).unwrap();
+        assert!(!row.contains("email") && !row.contains("joined"));
+        table_design.fill_defaults(&mut row);
+        assert_eq!(row.string("email"), Some("none@test.com"));
+        assert!(row.date_time("joined").is_some());
+        assert!(!row.contains("id"));
+
+        // Provided values are kept
+        let mut row = table_design.extract(&serde_json::json!({ "email": "test@test.com" })).unwrap();
+        table_design.fill_defaults(&mut row);
+        assert_eq!(row.string("email"), Some("test@test.com"));
+    }
+
+    #[test]
+    fn table_check_test() {
+        let mut table_design = default_table();
+        table_design.checks.push("\"id\" > 0 AND (name IS NULL OR length(name) >= 3)".to_string());
+        table_design.field_mut("email").unwrap().checks.push("email LIKE '%@test.com'".to_string());
+
+        assert!(table_design.extract(&serde_json::json!({ "id": 1, "email": "a@test.com", "name": "User" })).is_ok());
+
+        // Missing values are unknown, which passes
+        assert!(table_design.extract(&serde_json::json!({ "email": "a@test.com" })).is_ok());
+
+        let report = match table_design.validate(&serde_json::json!({ "id": 0, "email": "a@example.com" })) {
+            Err(RustractError::Validation(report)) => report,
+            other => panic!("test failed, expected a validation report but found {:?}", other),
+        };
+        assert_eq!(report.len(), 2);
+        assert_eq!(report.field("id")[0].rule, Rule::Check);
+        assert_eq!(report.field("email")[0].limit, Some("email LIKE '%@test.com'".to_string()));
+        assert!(table_design.extract(&serde_json::json!({ "email": "a@test.com", "name": "Al" })).is_err());
+    }
+
+    #[tokio::test]
+    async fn table_foreign_test() {
+        let mut table_design = default_table();
+        table_design.field_mut("id").unwrap().foreign = Some(ForeignKey {
+            table: "account".to_string(),
+            field: "id".to_string(),
+            on_delete: None,
+            on_update: None
+        });
+
+        // Only the account with id 1 exists
+        let exists = |key: ForeignKey, value: DataTypeValue| async move {
+            assert_eq!(key.to_string(), "account.id");
+            Ok(value == DataTypeValue::Unsigned64(1))
+        };
+        let row = table_design.extract_with(&serde_json::json!({ "id": 1, "email": "test@test.com" }), exists).await.unwrap();
+        assert_eq!(row.unsigned("id"), Some(1));
+        let error = table_design.extract_with(&serde_json::json!({ "id": 2, "email": "test@test.com" }), exists).await.unwrap_err();
+        assert_eq!(error.message(), "field id refers to account.id, but no account with the value 2 exists");
+    }
+
     /// Creates a default TableDesign struct for use in testing.
     fn default_table() -> TableDesign {
         let mut table = TableDesign::new("User");
@@ -239,7 +578,19 @@ mod test {
                 increment: false,
                 generated: true,
                 enum_set: None,
-                set: None
+                set: None,
+                array: false,
+                fixed: false,
+                default: None,
+                checks: Vec::new(),
+                min: None,
+                max: None,
+                format: None,
+                hidden: false,
+                column: None,
+                charset: None,
+                case_sensitive: false,
+                dialect: Dialect::MySql
         });
         table.add(FieldDesign {
                 field_design_title: String::from("email"),
@@ -255,7 +606,19 @@ mod test {
                 increment: false,
                 generated: false,
                 enum_set: None,
-                set: None
+                set: None,
+                array: false,
+                fixed: false,
+                default: None,
+                checks: Vec::new(),
+                min: None,
+                max: None,
+                format: None,
+                hidden: false,
+                column: None,
+                charset: None,
+                case_sensitive: false,
+                dialect: Dialect::MySql
         });
         table.add(FieldDesign {
                 field_design_title: String::from("name"),
@@ -271,7 +634,19 @@ mod test {
                 increment: false,
                 generated: false,
                 enum_set: None,
-                set: None
+                set: None,
+                array: false,
+                fixed: false,
+                default: None,
+                checks: Vec::new(),
+                min: None,
+                max: None,
+                format: None,
+                hidden: false,
+                column: None,
+                charset: None,
+                case_sensitive: false,
+                dialect: Dialect::MySql
         });
 
         table
@@ -0,0 +1,295 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use crate::error::{RustractError, GenericError};
+
+/// A calendar date in the format YYYY-MM-DD.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub struct Date {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8
+}
+
+/// A time of day in the format HH:MM:SS.ffffff.
+///
+/// MySQL also uses this type for durations, so it can be negative or over 24 hours.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Time {
+    pub negative: bool,
+    pub hours: u16,
+    pub minutes: u8,
+    pub seconds: u8,
+    pub microseconds: u32
+}
+
+/// A date and time of day in the format YYYY-MM-DD HH:MM:SS.ffffff.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub struct DateTime {
+    pub date: Date,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+    pub microsecond: u32
+}
+
+impl Date {
+    /// The earliest date MySQL supports.
+    pub const MIN: Date = Date { year: 1000, month: 1, day: 1 };
+    /// The latest date MySQL supports.
+    pub const MAX: Date = Date { year: 9999, month: 12, day: 31 };
+
+    /// Parses a date in the format YYYY-MM-DD, returning `None` if it is invalid.
+    pub fn parse(text: &str) -> Option<Date> {
+        let mut parts = text.split('-');
+        let date = Date {
+            year: number(parts.next()?, 4)? as u16,
+            month: number(parts.next()?, 2)? as u8,
+            day: number(parts.next()?, 2)? as u8
+        };
+        if parts.next().is_some() || date.month == 0 || date.month > 12
+            || date.day == 0 || date.day > days_in_month(date.year, date.month) {
+            return None;
+        }
+        Some(date)
+    }
+}
+
+impl Time {
+    /// Parses a time in the format [-]HH:MM:SS[.ffffff], returning `None` if it is invalid.
+    pub fn parse(text: &str) -> Option<Time> {
+        let (negative, text) = match text.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, text),
+        };
+        let mut parts = text.split(':');
+        let hours = parts.next()?;
+        if hours.len() < 2 || hours.len() > 3 {
+            return None;
+        }
+        let hours = number(hours, hours.len())? as u16;
+        let minutes = number(parts.next()?, 2)? as u8;
+        let (seconds, microseconds) = seconds(parts.next()?)?;
+        if parts.next().is_some() || minutes > 59 {
+            return None;
+        }
+        Some(Time { negative, hours, minutes, seconds, microseconds })
+    }
+
+    /// Returns true if this time is within MySQL's supported range of -838:59:59 to 838:59:59.
+    pub fn in_range(&self) -> bool {
+        (self.hours, self.minutes, self.seconds, self.microseconds) <= (838, 59, 59, 0)
+    }
+}
+
+impl DateTime {
+    /// The earliest date and time MySQL supports.
+    pub const MIN: DateTime = DateTime { date: Date::MIN, hour: 0, minute: 0, second: 0, microsecond: 0 };
+    /// The latest date and time MySQL supports.
+    pub const MAX: DateTime = DateTime { date: Date::MAX, hour: 23, minute: 59, second: 59, microsecond: 999_999 };
+    /// The earliest timestamp MySQL supports (in UTC).
+    pub const TIMESTAMP_MIN: DateTime = DateTime {
+        date: Date { year: 1970, month: 1, day: 1 }, hour: 0, minute: 0, second: 1, microsecond: 0
+    };
+    /// The latest timestamp MySQL supports (in UTC).
+    pub const TIMESTAMP_MAX: DateTime = DateTime {
+        date: Date { year: 2038, month: 1, day: 19 }, hour: 3, minute: 14, second: 7, microsecond: 999_999
+    };
+
+    /// Parses a date and time in the format YYYY-MM-DD HH:MM:SS[.ffffff], returning `None` if it is invalid.
+    ///
+    /// ISO strings (such as JavaScript's `toISOString()` output) are also accepted,
+    /// using a `T` separator and an optional `Z` for UTC.
+    pub fn parse(text: &str) -> Option<DateTime> {
+        let text = text.strip_suffix('Z').unwrap_or(text);
+        let separator = text.find([' ', 'T'])?;
+        let date = Date::parse(&text[..separator])?;
+        let mut parts = text[separator + 1..].split(':');
+        let hour = number(parts.next()?, 2)? as u8;
+        let minute = number(parts.next()?, 2)? as u8;
+        let (second, microsecond) = seconds(parts.next()?)?;
+        if parts.next().is_some() || hour > 23 || minute > 59 {
+            return None;
+        }
+        Some(DateTime { date, hour, minute, second, microsecond })
+    }
+
+    /// Gets the current date and time in UTC.
+    pub fn now() -> DateTime {
+        let elapsed = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+        DateTime::from_unix(elapsed.as_secs(), elapsed.subsec_micros())
+    }
+
+    /// Converts the seconds since the Unix epoch to a date and time in UTC.
+    fn from_unix(seconds: u64, microsecond: u32) -> DateTime {
+        // Counts days in 400 year eras starting on March 1st, so leap days fall at the end of each year
+        let days = (seconds / 86_400) as i64 + 719_468;
+        let era = days / 146_097;
+        let day_of_era = days - era * 146_097;
+        let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * month + 2) / 5 + 1;
+        let month = if month < 10 { month + 3 } else { month - 9 };
+        let year = era * 400 + year_of_era + i64::from(month <= 2);
+
+        let time = seconds % 86_400;
+        DateTime {
+            date: Date { year: year as u16, month: month as u8, day: day as u8 },
+            hour: (time / 3_600) as u8,
+            minute: (time % 3_600 / 60) as u8,
+            second: (time % 60) as u8,
+            microsecond
+        }
+    }
+}
+
+/// Reads a number with exactly the specified amount of digits.
+fn number(text: &str, digits: usize) -> Option<u32> {
+    if text.len() != digits || !text.chars().all(|character| character.is_ascii_digit()) {
+        return None;
+    }
+    text.parse().ok()
+}
+
+/// Reads seconds with an optional fraction of up to 6 digits, returning the seconds and microseconds.
+fn seconds(text: &str) -> Option<(u8, u32)> {
+    let (whole, fraction) = match text.find('.') {
+        Some(index) => (&text[..index], &text[index + 1..]),
+        None => (text, ""),
+    };
+    let seconds = number(whole, 2)? as u8;
+    if seconds > 59 || fraction.len() > 6 {
+        return None;
+    }
+    let microseconds = if fraction.is_empty() {
+        0
+    } else {
+        number(fraction, fraction.len())? * 10_u32.pow(6 - fraction.len() as u32)
+    };
+    Some((seconds, microseconds))
+}
+
+/// Returns true if the year is a leap year.
+fn is_leap_year(year: u16) -> bool {
+    (year.is_multiple_of(4) && !year.is_multiple_of(100)) || year.is_multiple_of(400)
+}
+
+/// Returns the number of days in the specified month.
+fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 => if is_leap_year(year) { 29 } else { 28 },
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Writes a fraction of a second, if there is one.
+fn write_fraction(f: &mut Formatter<'_>, microseconds: u32) -> std::fmt::Result {
+    if microseconds > 0 {
+        write!(f, ".{}", format!("{:06}", microseconds).trim_end_matches('0'))?;
+    }
+    Ok(())
+}
+
+impl Display for Date {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl Display for Time {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}{:02}:{:02}:{:02}",
+            if self.negative { "-" } else { "" },
+            self.hours,
+            self.minutes,
+            self.seconds
+        )?;
+        write_fraction(f, self.microseconds)
+    }
+}
+
+impl Display for DateTime {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {:02}:{:02}:{:02}", self.date, self.hour, self.minute, self.second)?;
+        write_fraction(f, self.microsecond)
+    }
+}
+
+/// Implements string conversion and serialization for each temporal type.
+macro_rules! temporal_string {
+    ($name:ident) => {
+        impl FromStr for $name {
+            type Err = RustractError;
+
+            fn from_str(text: &str) -> Result<Self, Self::Err> {
+                $name::parse(text).ok_or_else(|| RustractError::Generic(GenericError {
+                    message: format!("{} is not a valid {}", text, stringify!($name)),
+                }))
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let text = String::deserialize(deserializer)?;
+                text.parse().map_err(serde::de::Error::custom)
+            }
+        }
+    };
+}
+
+temporal_string!(Date);
+temporal_string!(Time);
+temporal_string!(DateTime);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn date_test() {
+        assert_eq!(Date::parse("2020-02-29"), Some(Date { year: 2020, month: 2, day: 29 }));
+        assert_eq!(Date::parse("2021-02-29"), None);
+        assert_eq!(Date::parse("2021-01-001"), None);
+        assert_eq!(Date::parse("2021-01-01T00:00:00"), None);
+        assert!(Date::parse("0999-12-31").unwrap() < Date::MIN);
+    }
+
+    #[test]
+    fn time_test() {
+        let time = Time::parse("-838:59:59").unwrap();
+        assert!(time.negative && time.in_range());
+        assert!(!Time::parse("839:00:00").unwrap().in_range());
+        assert_eq!(Time::parse("12:30:00.25").unwrap().to_string(), "12:30:00.25");
+        assert_eq!(Time::parse("12:60:00"), None);
+    }
+
+    #[test]
+    fn date_time_test() {
+        let iso = DateTime::parse("2021-09-01T12:00:00.000Z").unwrap();
+        assert_eq!(iso, DateTime::parse("2021-09-01 12:00:00").unwrap());
+        assert_eq!(iso.to_string(), "2021-09-01 12:00:00");
+        assert!(DateTime::parse("2038-01-19 03:14:08").unwrap() > DateTime::TIMESTAMP_MAX);
+        assert_eq!(DateTime::parse("2021-09-01 24:00:00"), None);
+
+        // Unix time is converted to the UTC calendar
+        assert_eq!(DateTime::from_unix(0, 0).to_string(), "1970-01-01 00:00:00");
+        assert_eq!(DateTime::from_unix(1_630_497_600, 0), iso);
+        assert_eq!(DateTime::from_unix(951_825_600, 0).to_string(), "2000-02-29 12:00:00");
+        assert!(DateTime::now() > iso);
+
+        // Serializes as a string
+        assert_eq!(serde_json::to_string(&iso).unwrap(), "\"2021-09-01 12:00:00\"");
+        assert_eq!(serde_json::from_str::<DateTime>("\"2021-09-01 12:00:00\"").unwrap(), iso);
+    }
+}
@@ -3,14 +3,48 @@ use std::convert::TryInto;
 use std::fmt::{Display, Formatter};
 use serde::{Serialize,Deserialize};
 use crate::error::{RustractError, GenericError};
+use crate::decimal::Decimal;
+use crate::temporal::{Date, DateTime, Time};
 
 /// Holds configuration info for the library.
 #[derive(Deserialize, Serialize, Debug)]
 pub struct Config {
     pub db_path: String,
+    #[serde(default)]
     pub schema_path: String,
     #[serde(skip_serializing_if="Option::is_none")]
     pub type_path: Option<String>,
+    #[serde(default)]
+    pub dialect: Dialect,
+    /// The path to a JSON file of overrides to apply to the design read from the schema.
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    pub overrides_path: Option<String>,
+    /// The path to a folder of migrations to read the design from, instead of the schema.
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    pub migrations_path: Option<String>,
+}
+
+/// The SQL dialect a schema is written in.
+#[derive(Deserialize, Serialize, Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Dialect {
+    /// MySQL or MariaDB, as written by `mysqldump`.
+    #[default]
+    MySql,
+    /// PostgreSQL, as written by `pg_dump --schema-only`.
+    PostgreSql,
+    /// SQLite, as written by the `.schema` command (or read from a database file).
+    Sqlite
+}
+
+impl Display for Dialect {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", match self {
+            Dialect::MySql => "MySQL",
+            Dialect::PostgreSql => "PostgreSQL",
+            Dialect::Sqlite => "SQLite"
+        })
+    }
 }
 
 impl Config {
@@ -31,7 +65,7 @@ impl Config {
 
 impl Default for Config {
     fn default() -> Self {
-        Self { db_path: "./database.json".to_string(), schema_path: "./dump.sql".to_string(), type_path: None }
+        Self { db_path: "./database.json".to_string(), schema_path: "./dump.sql".to_string(), type_path: None, dialect: Dialect::MySql, overrides_path: None, migrations_path: None }
     }
 }
 
@@ -54,6 +88,14 @@ pub enum DataType {
     // Decimal
     Float64,
     Float32,
+    Decimal,
+
+    // Temporal
+    Date,
+    Time,
+    DateTime,
+    Timestamp,
+    Year,
 
     // Other
     Boolean,
@@ -77,11 +119,17 @@ impl DataType {
             DataType::Unsigned16 => "number",
             DataType::Float64 => "number",
             DataType::Float32 => "number",
+            DataType::Decimal => "`${number}`",
+            DataType::Date => "`${number}-${number}-${number}`",
+            DataType::Time => "`${number}:${number}:${number}`",
+            DataType::DateTime => "`${number}-${number}-${number}${\" \" | \"T\"}${number}:${number}:${string}`",
+            DataType::Timestamp => "`${number}-${number}-${number}${\" \" | \"T\"}${number}:${number}:${string}`",
+            DataType::Year => "number",
             DataType::Boolean => "bool",
             DataType::Bit => "number",
             DataType::Byte => "number",
             DataType::Enum => "Enum",
-            DataType::Set => "string"
+            DataType::Set => "string[]"
         }.to_string()
     }
 }
@@ -100,6 +148,12 @@ impl Display for DataType {
             DataType::Unsigned16 => "Unsigned 16-bit Integer",
             DataType::Float64 => "64-bit Float",
             DataType::Float32 => "32-bit Float",
+            DataType::Decimal => "Decimal",
+            DataType::Date => "Date",
+            DataType::Time => "Time",
+            DataType::DateTime => "Date Time",
+            DataType::Timestamp => "Timestamp",
+            DataType::Year => "Year",
             DataType::Boolean => "Boolean",
             DataType::Bit => "Bit",
             DataType::Byte => "Byte",
@@ -128,13 +182,27 @@ pub enum DataTypeValue {
     // Decimal
     Float64(f64),
     Float32(f32),
+    Decimal(Decimal),
+
+    // Temporal
+    Date(Date),
+    Time(Time),
+    DateTime(DateTime),
+    Timestamp(DateTime),
+    Year(u16),
 
     // Other
     Boolean(bool),
-    Bit(u8),
+    Bit(u64),
     Byte(u8),
-    Enum(u32),
-    Set(String)
+    /// The index and label of an enum's value.
+    Enum(u32, String),
+    /// The members of a set's value, in the order they were given.
+    Set(Vec<String>),
+    /// The values of an array field, in order.
+    Array(Vec<DataTypeValue>),
+    /// An explicit `null`, which only fields whose column may be null accept.
+    Null
 }
 
 impl Display for DataTypeValue {
@@ -151,11 +219,19 @@ impl Display for DataTypeValue {
             DataTypeValue::Unsigned16(v) => v.to_string(),
             DataTypeValue::Float64(v) => v.to_string(),
             DataTypeValue::Float32(v) => v.to_string(),
+            DataTypeValue::Decimal(v) => v.to_string(),
+            DataTypeValue::Date(v) => v.to_string(),
+            DataTypeValue::Time(v) => v.to_string(),
+            DataTypeValue::DateTime(v) => v.to_string(),
+            DataTypeValue::Timestamp(v) => v.to_string(),
+            DataTypeValue::Year(v) => v.to_string(),
             DataTypeValue::Boolean(v) => v.to_string(),
             DataTypeValue::Bit(v) => v.to_string(),
             DataTypeValue::Byte(v) => v.to_string(),
-            DataTypeValue::Enum(v) => v.to_string(),
-            DataTypeValue::Set(v) => v.to_string(),
+            DataTypeValue::Enum(_, label) => label.to_string(),
+            DataTypeValue::Set(members) => members.join(","),
+            DataTypeValue::Array(v) => format!("[{}]", v.iter().map(|value| value.to_string()).collect::<Vec<String>>().join(", ")),
+            DataTypeValue::Null => "null".to_string(),
         })
     }
 }
@@ -181,19 +257,9 @@ pub(crate) trait HasLength {
     fn length(&self) -> isize;
 }
 
-pub(crate) trait HasBytes {
-    fn byte_length(&self) -> isize;
-}
-
 impl HasLength for String {
     fn length(&self) -> isize {
-        self.len().try_into().unwrap()
-    }
-}
-
-impl HasBytes for String {
-    fn byte_length(&self) -> isize {
-        self.len().try_into().unwrap()
+        self.chars().count().try_into().unwrap()
     }
 }
 
@@ -239,90 +305,21 @@ impl HasLength for u8 {
     }
 }
 
+/// The length of a float is the number of digits before its decimal point.
 impl HasLength for f64 {
     fn length(&self) -> isize {
-        digits(self).try_into().unwrap()
+        let integer = self.abs().trunc();
+        if integer < 1.0 {
+            0
+        } else {
+            integer.to_string().len().try_into().unwrap()
+        }
     }
 }
 
 impl HasLength for f32 {
     fn length(&self) -> isize {
-        digits(self).try_into().unwrap()
-    }
-}
-
-/// Adds indexing functions to the implementing type.
-pub(crate) trait IndexOf {
-    /// Retrieves the first index of the specified sequence.
-    fn index_of(&self, sequence: &str) -> Option<usize>;
-
-    /// Retrieves the next index of the first sequence matched.
-    fn next_index_of(&self, sequence: &str, from: usize) -> Option<usize>;
-}
-
-impl IndexOf for String {
-    fn index_of(&self, sequence: &str) -> Option<usize> {
-        self.next_index_of(sequence, 0)
-    }
-
-    fn next_index_of(&self, sequence: &str, from: usize) -> Option<usize> {
-        let char_sequence: Vec<char> = sequence.chars().collect();
-        let mut index = 0;
-        let mut matching: bool;
-        for (pos, character) in self.chars().skip(from).enumerate() {
-            // Prevent out of bounds when doesn't exist
-            if index == char_sequence.len() {
-                break;
-            }
-
-            // Proceed through each character in the sequence and reset
-            if character == char_sequence[index] {
-                matching = true;
-                index += 1;
-            } else {
-                matching = false;
-                index = 0;
-            }
-
-            // If all characters matched, return the sequence
-            if matching && index == char_sequence.len() {
-                return Some(pos+from);
-            }
-        }
-        None
-    }
-}
-
-impl IndexOf for &str {
-    fn index_of(&self, sequence: &str) -> Option<usize> {
-        self.next_index_of(sequence, 0)
-    }
-
-    fn next_index_of(&self, sequence: &str, from: usize) -> Option<usize> {
-        let char_sequence: Vec<char> = sequence.chars().collect();
-        let mut index = 0;
-        let mut matching: bool;
-        for (pos, character) in self.chars().skip(from).enumerate() {
-            // Prevent out of bounds when doesn't exist
-            if index == char_sequence.len() {
-                break;
-            }
-
-            // Proceed through each character in the sequence and reset
-            if character == char_sequence[index] {
-                matching = true;
-                index += 1;
-            } else {
-                matching = false;
-                index = 0;
-            }
-
-            // If all characters matched, return the sequence
-            if matching && index == char_sequence.len() {
-                return Some(pos+from);
-            }
-        }
-        None
+        f64::from(*self).length()
     }
 }
 
@@ -343,7 +340,7 @@ impl IntoHashSet for Vec<String> {
 }
 
 pub(crate) fn capitalize(string: &str) -> Result<String, RustractError> {
-    return if string.is_empty() {
+    if string.is_empty() {
         Err(RustractError::Generic(GenericError {
             message: "cannot capitalize an empty string".to_string(),
         }))
@@ -357,25 +354,6 @@ mod test {
 
     use regex::Regex;
 
-    use super::*;
-
-    #[test]
-    fn index_test() {
-        // Tests &str indexing
-        let index_this = "Find the (! (Hint: there's two!)";
-        let index = index_this.index_of("(").unwrap();
-
-        assert_eq!(index, 9);
-        assert_eq!(index_this.next_index_of("(", index + 1).unwrap(), 12);
-
-        // Tests String indexing
-        let index_string = String::from(index_this);
-        let index_2 = index_string.index_of("(").unwrap();
-
-        assert_eq!(index_2, 9);
-        assert_eq!(index_string.next_index_of("(", index_2 + 1).unwrap(), 12);
-    }
-
     #[test]
     fn regex_test() {
         // Tests the Regex library itself
@@ -45,3 +45,70 @@ async fn warp_test() -> Result<(), RustractError> {
     warp::serve(routes).run(([127, 0, 0, 1], 3030)).await;
     Ok(())
 }
+
+/// Reads a design from a real SQLite database file, then round trips a row through it.
+#[cfg(feature = "sqlite")]
+#[test]
+fn sqlite_round_trip() -> Result<(), RustractError> {
+    use rusqlite::{Connection, params};
+    use rustract::db::Database;
+
+    let path = std::env::temp_dir().join("rustract_round_trip.db");
+    let path = path.to_str().unwrap();
+    let _ = std::fs::remove_file(path);
+    let connection = Connection::open(path)?;
+    connection.execute_batch("
+        CREATE TABLE item (
+            id INTEGER PRIMARY KEY,
+            name VARCHAR(20) NOT NULL CHECK (length(name) > 0),
+            price NUMERIC(6, 2),
+            added DATE,
+            active BOOLEAN
+        );
+    ")?;
+
+    // The design is read from the file itself
+    let db = Database::from_sqlite_file(path)?;
+    let table = db.table("item").unwrap();
+    assert!(table.field("id").unwrap().generated);
+
+    // Extracts a row from a request and inserts it
+    let row = table.extract(&serde_json::json!({
+        "name": "Lamp",
+        "price": "19.99",
+        "added": "2021-09-01",
+        "active": true
+    }))?;
+    connection.execute(
+        "INSERT INTO item (name, price, added, active) VALUES (?1, ?2, ?3, ?4)",
+        params![
+            row.string("name"),
+            row.decimal("price").map(|price| price.to_string()),
+            row.date("added").map(|added| added.to_string()),
+            row.boolean("active")
+        ],
+    )?;
+
+    // Reads the row back and extracts it again
+    let stored = connection.query_row("SELECT id, name, price, added, active FROM item", [], |stored| {
+        Ok(serde_json::json!({
+            "id": stored.get::<_, i64>(0)?,
+            "name": stored.get::<_, String>(1)?,
+            "price": stored.get::<_, f64>(2)?,
+            "added": stored.get::<_, String>(3)?,
+            "active": stored.get::<_, bool>(4)?
+        }))
+    })?;
+    let round_trip = table.extract(&stored)?;
+    assert_eq!(round_trip.signed("id"), Some(1));
+    for title in ["name", "price", "added", "active"].iter() {
+        assert_eq!(round_trip.get(title), row.get(title));
+    }
+
+    // Values the design rejects never reach the database
+    assert!(table.extract(&serde_json::json!({ "name": "A name that is far too long" })).is_err());
+
+    drop(connection);
+    std::fs::remove_file(path)?;
+    Ok(())
+}
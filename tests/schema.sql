@@ -26,7 +26,7 @@ CREATE TABLE `user` (
   `id` INT UNSIGNED NOT NULL AUTO_INCREMENT,
   `name` VARCHAR(45) NOT NULL,
   `email` VARCHAR(45) NOT NULL,
-  `registered` VARCHAR(10) NULL,
+  `registered` DATE NULL,
   `type` ENUM("Admin","Mod","Basic") NOT NULL,
   `twofa` TINYINT NOT NULL,
   `tag` TINYINT NOT NULL,
@@ -36,6 +36,23 @@ CREATE TABLE `user` (
   UNIQUE INDEX `email_UNIQUE` (`email` ASC) VISIBLE
 ) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
 /*!40101 SET character_set_client = @saved_cs_client */;
+
+--
+-- Table structure for table `post`
+--
+
+DROP TABLE IF EXISTS `post`;
+/*!40101 SET @saved_cs_client     = @@character_set_client */;
+/*!50503 SET character_set_client = utf8mb4 */;
+CREATE TABLE `post` (
+  `id` INT UNSIGNED NOT NULL AUTO_INCREMENT,
+  `author` INT UNSIGNED NOT NULL,
+  `body` VARCHAR(280) NOT NULL,
+  PRIMARY KEY (`id`),
+  KEY `fk_post_author_idx` (`author`),
+  CONSTRAINT `fk_post_author` FOREIGN KEY (`author`) REFERENCES `user` (`id`) ON DELETE CASCADE ON UPDATE NO ACTION
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+/*!40101 SET character_set_client = @saved_cs_client */;
 /*!40103 SET TIME_ZONE=@OLD_TIME_ZONE */;
 
 /*!40101 SET SQL_MODE=@OLD_SQL_MODE */;